@@ -0,0 +1,83 @@
+//! Tests for `error_mancer` reading defaults from `error_mancer.toml`, see the `tests` directory.
+//!
+//! The functions here use `cfg_derive(test, ..)`, so the unit tests below see the enums with
+//! the conditional derives and `tests/cfg_derive.rs` sees them without.
+
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+/// `PartialEq` from `error_mancer.toml` is replaced by the conditional derive.
+#[errors(ParseIntError, cfg_derive(test, PartialEq, Eq))]
+pub fn parse(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+/// The derived `Debug` replaces the generated one.
+#[errors(ParseIntError, cfg_derive(test, Debug))]
+pub fn debug_in_tests(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+pub struct Parser;
+
+#[errors(cfg_derive(test, Eq))]
+impl Parser {
+    /// `cfg_attr` isnt expanded on methods before `#[errors]` sees them.
+    #[errors(ParseIntError)]
+    #[cfg_attr(test, derive(Debug), allow(clippy::unused_self))]
+    pub fn number(&self, x: &str) -> Result<i32, _> {
+        Ok(x.parse()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error() -> ParseIntError {
+        "x".parse::<i32>().unwrap_err()
+    }
+
+    #[test]
+    fn conditional_derives() {
+        let err = parse("x").unwrap_err();
+        assert_eq!(err, err.clone());
+        assert_eq!(format!("{err:?}"), "ParseInt(<redacted>)");
+    }
+
+    #[test]
+    fn conditional_debug() {
+        let err = debug_in_tests("x").unwrap_err();
+        assert_eq!(format!("{err:?}"), format!("ParseInt({:?})", parse_error()));
+    }
+
+    #[test]
+    fn conditional_derives_on_methods() {
+        let err = Parser.number("x").unwrap_err();
+        assert_eq!(err, err.clone());
+        assert_eq!(format!("{err:?}"), format!("ParseInt({:?})", parse_error()));
+    }
+}
+
+/// The computed doc attribute has to stay on the function, `missing_docs` only applies to the
+/// function and not to the generated enum.
+pub mod computed_docs {
+    use std::num::ParseIntError;
+
+    use error_mancer::prelude::*;
+
+    #[errors(ParseIntError, no_doc)]
+    #[doc = include_str!("computed_docs.md")]
+    #[deny(missing_docs)]
+    pub fn parse(x: &str) -> Result<i32, _> {
+        Ok(x.parse()?)
+    }
+
+    #[doc = concat!("Parse a ", "number.")]
+    #[errors(ParseIntError, no_doc)]
+    #[deny(missing_docs)]
+    pub fn parse_concat(x: &str) -> Result<i32, _> {
+        Ok(x.parse()?)
+    }
+}
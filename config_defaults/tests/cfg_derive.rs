@@ -0,0 +1,81 @@
+//! The library isnt compiled with `cfg(test)` for integration tests, so the traits from
+//! `cfg_derive(test, ..)` arent derived.
+
+use std::marker::PhantomData;
+
+use config_defaults::{
+    debug_in_tests,
+    parse,
+    DebugInTestsFailure,
+    NumberFailure,
+    ParseFailure,
+    Parser,
+};
+
+/// `implements!(Type, is_eq)` is true if `Type` implements the trait, using autoref
+/// specialization.
+macro_rules! implements {
+    ($ty:ty, $method:ident) => {
+        (&Probe::<$ty>(PhantomData)).$method()
+    };
+}
+
+struct Probe<T>(PhantomData<T>);
+
+trait IsPartialEq {
+    fn is_partial_eq(&self) -> bool {
+        true
+    }
+}
+
+impl<T: PartialEq> IsPartialEq for Probe<T> {}
+
+trait IsEq {
+    fn is_eq(&self) -> bool {
+        true
+    }
+}
+
+impl<T: Eq> IsEq for Probe<T> {}
+
+trait Fallback {
+    fn is_partial_eq(&self) -> bool {
+        false
+    }
+
+    fn is_eq(&self) -> bool {
+        false
+    }
+}
+
+impl<T> Fallback for &Probe<T> {}
+
+#[test]
+fn probe_detects_traits() {
+    assert!(implements!(i32, is_partial_eq));
+    assert!(implements!(i32, is_eq));
+    assert!(!implements!(f32, is_eq));
+}
+
+#[test]
+fn conditional_derives_are_skipped() {
+    assert!(!implements!(ParseFailure, is_partial_eq));
+    assert!(!implements!(ParseFailure, is_eq));
+    // Unconditional derives from `error_mancer.toml` are kept.
+    let err = parse("x").unwrap_err();
+    let _ = err.clone();
+}
+
+#[test]
+fn generated_debug_is_used() {
+    assert!(implements!(DebugInTestsFailure, is_partial_eq));
+    let err = debug_in_tests("x").unwrap_err();
+    assert_eq!(format!("{err:?}"), "ParseInt(<redacted>)");
+}
+
+#[test]
+fn conditional_derives_on_methods_are_skipped() {
+    assert!(!implements!(NumberFailure, is_eq));
+    let err = Parser.number("x").unwrap_err();
+    assert_eq!(format!("{err:?}"), "ParseInt(<redacted>)");
+}
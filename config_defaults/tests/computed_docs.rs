@@ -0,0 +1,14 @@
+//! Doc attributes with computed values, i.e `#[doc = include_str!(..)]`, stay on the function.
+//! This is checked when compiling the library, which denies `missing_docs` on the functions.
+
+use config_defaults::computed_docs::{parse, parse_concat, ParseConcatFailure, ParseFailure};
+
+#[test]
+fn computed_docs() {
+    assert_eq!(parse("1").unwrap(), 1);
+    assert!(matches!(parse("x"), Err(ParseFailure::ParseInt(_))));
+    assert!(matches!(
+        parse_concat("x"),
+        Err(ParseConcatFailure::ParseInt(_))
+    ));
+}
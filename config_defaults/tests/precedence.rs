@@ -0,0 +1,84 @@
+//! `error_mancer.toml` sets `no_debug`, derives `Clone` and `PartialEq` and uses the `Failure`
+//! suffix. Impl blocks override the file, and the attribute overrides both.
+
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[errors(ParseIntError)]
+fn from_file(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+#[errors(ParseIntError, no_debug = false, enum_suffix = "Error")]
+fn from_attribute(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+#[errors(ParseIntError)]
+#[derive(Clone, Debug)]
+fn explicit_derive(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+#[errors(ParseIntError, group Inner { std::fmt::Error })]
+fn grouped(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+struct Parser;
+
+#[errors(no_debug = false, enum_suffix = "Problem")]
+impl Parser {
+    #[errors(ParseIntError)]
+    fn from_impl(&self, x: &str) -> Result<i32, _> {
+        Ok(x.parse()?)
+    }
+
+    #[errors(ParseIntError, no_debug)]
+    fn attribute_over_impl(&self, x: &str) -> Result<i32, _> {
+        Ok(x.parse()?)
+    }
+}
+
+fn parse_error() -> ParseIntError {
+    "x".parse::<i32>().unwrap_err()
+}
+
+#[test]
+fn file_defaults() {
+    let err: FromFileFailure = from_file("x").unwrap_err();
+    assert_eq!(format!("{err:?}"), "ParseInt(<redacted>)");
+    assert!(err.clone() == err);
+}
+
+#[test]
+fn attribute_overrides_file() {
+    let err: FromAttributeError = from_attribute("x").unwrap_err();
+    assert_eq!(format!("{err:?}"), format!("ParseInt({:?})", parse_error()));
+}
+
+#[test]
+fn derives_are_not_repeated() {
+    let err: ExplicitDeriveFailure = explicit_derive("x").unwrap_err();
+    assert_eq!(format!("{err:?}"), format!("ParseInt({:?})", parse_error()));
+    assert!(err.clone() == err);
+}
+
+#[test]
+fn group_names_use_suffix() {
+    let _: Option<GroupedInnerFailure> = None;
+    assert!(matches!(grouped("x"), Err(GroupedFailure::ParseInt(_))));
+}
+
+#[test]
+fn impl_overrides_file() {
+    let err: FromImplProblem = Parser.from_impl("x").unwrap_err();
+    assert_eq!(format!("{err:?}"), format!("ParseInt({:?})", parse_error()));
+}
+
+#[test]
+fn attribute_overrides_impl() {
+    let err: AttributeOverImplProblem = Parser.attribute_over_impl("x").unwrap_err();
+    assert_eq!(format!("{err:?}"), "ParseInt(<redacted>)");
+}
@@ -0,0 +1 @@
+//! Tests for `error_mancer` under edition 2024, see the `tests` directory.
@@ -0,0 +1,64 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+struct Store {
+    values: Vec<String>,
+}
+
+#[errors]
+impl Store {
+    #[errors(ParseIntError)]
+    async fn parse_at(&self, index: usize) -> Result<i32, _> {
+        Ok(self.values[index].parse()?)
+    }
+
+    #[errors(ParseIntError)]
+    async fn get<'k>(&self, key: &'k str) -> Result<(&str, &'k str), _> {
+        let value = self
+            .values
+            .iter()
+            .find(|value| value.starts_with(key))
+            .unwrap();
+        value.parse::<i32>()?;
+        Ok((value.as_str(), key))
+    }
+
+    #[errors(ParseIntError)]
+    async fn key<'s, 'k>(&'s self, key: &'k str) -> Result<&'k str, _> {
+        key.parse::<i32>()?;
+        Ok(key)
+    }
+}
+
+fn store() -> Store {
+    Store {
+        values: vec![String::from("12"), String::from("x")],
+    }
+}
+
+#[tokio::test]
+async fn borrows_self() {
+    let store = store();
+    assert_eq!(store.parse_at(0).await.unwrap(), 12);
+    assert!(store.parse_at(1).await.is_err());
+}
+
+#[tokio::test]
+async fn independent_lifetimes() {
+    let store = store();
+    let key = String::from("1");
+    let (value, key) = store.get(&key).await.unwrap();
+    assert_eq!((value, key), ("12", "1"));
+}
+
+#[tokio::test]
+async fn result_outlives_self() {
+    let key = String::from("42");
+    let result = {
+        let store = store();
+        let future = store.key(&key);
+        future.await
+    };
+    assert_eq!(result.unwrap(), "42");
+}
@@ -0,0 +1,57 @@
+use core::fmt::{self, Debug, Display};
+
+use crate::__private::Error;
+
+/// A error with a message attached to it, created by [`ResultExt::context`].
+///
+/// Unlike `anyhow::Context` the original error type is kept, so it can still be matched on via
+/// [`WithContext::inner`].
+///
+/// [`ResultExt::context`]: crate::ResultExt::context
+pub struct WithContext<E, M> {
+    inner: E,
+    context: M,
+}
+
+impl<E, M> WithContext<E, M> {
+    /// Attach `context` to `inner`.
+    pub fn new(inner: E, context: M) -> Self {
+        Self { inner, context }
+    }
+
+    /// The wrapped error.
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+
+    /// The context message.
+    pub fn context(&self) -> &M {
+        &self.context
+    }
+
+    /// Discard the context and return the wrapped error.
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+}
+
+impl<E: Display, M: Display> Display for WithContext<E, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.context, self.inner)
+    }
+}
+
+impl<E: Debug, M: Display> Debug for WithContext<E, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithContext")
+            .field("context", &format_args!("{}", self.context))
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<E: Error + 'static, M: Display> Error for WithContext<E, M> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.inner)
+    }
+}
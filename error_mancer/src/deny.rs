@@ -0,0 +1,118 @@
+//! Checks for the `deny(..)` option and `assert_not_allowed!`.
+//!
+//! The `?` operators in the function are rewritten to check the error using autoref
+//! specialization, `(&DenyProbe::new(&err)).check()` returns [`Denied`] when the error
+//! implements [`DeniedBy`] for the marker generated for the function and [`Allowed`]
+//! otherwise. [`assert_allowed`] then only accepts [`Allowed`].
+//!
+//! `assert_not_allowed!` works the same way, `(&ListedProbe::<T, E>(PhantomData)).check()` returns
+//! [`Listed`] when `T` converts from `E` and [`Unlisted`] otherwise, which
+//! [`assert_unlisted`] requires.
+
+use core::marker::PhantomData;
+
+/// Implemented for the denied errors, `M` is a marker type generated for each function.
+#[doc(hidden)]
+pub trait DeniedBy<M> {}
+
+#[doc(hidden)]
+pub struct DenyProbe<'r, E, M>(PhantomData<(&'r E, M)>);
+
+impl<'r, E, M> DenyProbe<'r, E, M> {
+    #[inline(always)]
+    pub fn new(_err: &'r E) -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub struct Denied;
+
+#[doc(hidden)]
+pub struct Allowed;
+
+#[doc(hidden)]
+pub trait CheckDenied {
+    fn check(&self) -> Denied;
+}
+
+impl<E: DeniedBy<M>, M> CheckDenied for DenyProbe<'_, E, M> {
+    #[inline(always)]
+    fn check(&self) -> Denied {
+        Denied
+    }
+}
+
+#[doc(hidden)]
+pub trait CheckAllowed {
+    fn check(&self) -> Allowed;
+}
+
+impl<E, M> CheckAllowed for &DenyProbe<'_, E, M> {
+    #[inline(always)]
+    fn check(&self) -> Allowed {
+        Allowed
+    }
+}
+
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`{E}` is denied in this function",
+    label = "`?` cannot be used on `{E}`",
+    note = "Convert it into a different error first, i.e with `.map_err(..)`."
+)]
+pub trait NotDenied<E> {}
+
+impl<E> NotDenied<E> for Allowed {}
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn assert_allowed<E, C: NotDenied<E>>(_err: &E, _check: C) {}
+
+#[doc(hidden)]
+pub struct ListedProbe<T, E>(pub PhantomData<(T, E)>);
+
+#[doc(hidden)]
+pub struct Listed;
+
+#[doc(hidden)]
+pub struct Unlisted;
+
+#[doc(hidden)]
+pub trait CheckListed {
+    fn check(&self) -> Listed;
+}
+
+impl<T: crate::ErrorMancerFrom<E>, E> CheckListed for ListedProbe<T, E> {
+    #[inline(always)]
+    fn check(&self) -> Listed {
+        Listed
+    }
+}
+
+#[doc(hidden)]
+pub trait CheckUnlisted {
+    fn check(&self) -> Unlisted;
+}
+
+impl<T, E> CheckUnlisted for &ListedProbe<T, E> {
+    #[inline(always)]
+    fn check(&self) -> Unlisted {
+        Unlisted
+    }
+}
+
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`{E}` is allowed in `{T}`",
+    label = "`{T}` converts from `{E}`",
+    note = "`assert_not_allowed!` expects `{E}` to not be listed in the `#[errors]` attribute of \
+            `{T}`."
+)]
+pub trait NotListed<T, E> {}
+
+impl<T, E> NotListed<T, E> for Unlisted {}
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn assert_unlisted<T, E, C: NotListed<T, E>>(_check: C) {}
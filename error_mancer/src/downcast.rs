@@ -0,0 +1,45 @@
+//! Downcasting for the generated `classify` functions.
+//!
+//! Not every payload implements `Error`, and only those can be downcast to, so the generated
+//! code uses autoref specialization, `(&Downcast::<T>::new()).forward_is(err)` checks the type
+//! when `T` implements `Error` and returns `false` otherwise.
+
+use core::marker::PhantomData;
+
+use crate::__private::Error;
+
+#[doc(hidden)]
+pub struct Downcast<T>(PhantomData<T>);
+
+impl<T> Downcast<T> {
+    #[inline(always)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait ForwardDowncast {
+    /// Whether `err` is a `T`.
+    fn forward_is(&self, err: &(dyn Error + 'static)) -> bool;
+}
+
+impl<T: Error + 'static> ForwardDowncast for Downcast<T> {
+    #[inline(always)]
+    fn forward_is(&self, err: &(dyn Error + 'static)) -> bool {
+        err.is::<T>()
+    }
+}
+
+#[doc(hidden)]
+pub trait NoDowncast {
+    fn forward_is(&self, err: &(dyn Error + 'static)) -> bool;
+}
+
+impl<T> NoDowncast for &Downcast<T> {
+    #[inline(always)]
+    fn forward_is(&self, _err: &(dyn Error + 'static)) -> bool {
+        false
+    }
+}
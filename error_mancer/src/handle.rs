@@ -0,0 +1,87 @@
+//! Propagation of the cases not handled in `handle!`.
+//!
+//! Errors are propagated for `Result`s (also inside a `Some`), other values are passed through.
+//! This uses autoref specialization, i.e `(&Propagator::new(value)).propagate::<S>()`.
+//! With the `anyhow` feature `wrap_rest` converts the errors into an `anyhow::Error` instead.
+
+use core::cell::Cell;
+
+#[cfg(feature = "anyhow")]
+use crate::__private::Error;
+use crate::ErrorMancerFrom;
+
+#[doc(hidden)]
+pub struct Propagator<T>(Cell<Option<T>>);
+
+impl<T> Propagator<T> {
+    #[inline(always)]
+    pub fn new(value: T) -> Self {
+        Self(Cell::new(Some(value)))
+    }
+
+    #[inline(always)]
+    fn take(&self) -> T {
+        match self.0.take() {
+            Some(value) => value,
+            None => unreachable!("propagated twice"),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait PropagateResult {
+    type Ok;
+    type Err;
+    fn propagate<S: ErrorMancerFrom<Self::Err>>(&self) -> Result<Self::Ok, S>;
+    #[cfg(feature = "anyhow")]
+    fn wrap_rest<C, F>(&self, context: F) -> Result<Self::Ok, anyhow::Error>
+    where
+        Self::Err: Error + Send + Sync + 'static,
+        C: core::fmt::Display + Send + Sync + 'static,
+        F: FnOnce() -> C;
+}
+
+impl<T, E> PropagateResult for Propagator<Result<T, E>> {
+    type Ok = T;
+    type Err = E;
+
+    #[inline(always)]
+    fn propagate<S: ErrorMancerFrom<E>>(&self) -> Result<T, S> {
+        self.take().map_err(<S as ErrorMancerFrom<E>>::from)
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[inline(always)]
+    fn wrap_rest<C, F>(&self, context: F) -> Result<T, anyhow::Error>
+    where
+        E: Error + Send + Sync + 'static,
+        C: core::fmt::Display + Send + Sync + 'static,
+        F: FnOnce() -> C,
+    {
+        self.take()
+            .map_err(|err| anyhow::Error::new(err).context(context()))
+    }
+}
+
+#[doc(hidden)]
+pub trait PropagateValue {
+    type Value;
+    fn propagate<S>(&self) -> Result<Self::Value, S>;
+    #[cfg(feature = "anyhow")]
+    fn wrap_rest<F>(&self, context: F) -> Result<Self::Value, anyhow::Error>;
+}
+
+impl<T> PropagateValue for &Propagator<T> {
+    type Value = T;
+
+    #[inline(always)]
+    fn propagate<S>(&self) -> Result<T, S> {
+        Ok(self.take())
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[inline(always)]
+    fn wrap_rest<F>(&self, _context: F) -> Result<T, anyhow::Error> {
+        Ok(self.take())
+    }
+}
@@ -0,0 +1,52 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt::{self, Display};
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+
+/// Implemented by `#[errors]` enums which have `msg_id` entries, mapping each variant to a
+/// Fluent message id.
+pub trait MessageId {
+    fn message_id(&self) -> &'static str;
+}
+
+/// Displays a error using a message from a `FluentBundle`.
+///
+/// The inner errors `Display` output is passed to the message as the `$error` argument. If the
+/// bundle does not contain the message the errors normal `Display` output is used instead.
+pub struct LocalizedDisplay<'a, E, R> {
+    error: &'a E,
+    bundle: &'a FluentBundle<R>,
+}
+
+impl<'a, E, R> LocalizedDisplay<'a, E, R> {
+    pub fn new(error: &'a E, bundle: &'a FluentBundle<R>) -> Self {
+        Self { error, bundle }
+    }
+}
+
+impl<E, R> Display for LocalizedDisplay<'_, E, R>
+where
+    E: MessageId + Display,
+    R: Borrow<FluentResource>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(pattern) = self
+            .bundle
+            .get_message(self.error.message_id())
+            .and_then(|message| message.value())
+        else {
+            return self.error.fmt(f);
+        };
+
+        let mut args = FluentArgs::new();
+        args.set("error", self.error.to_string());
+
+        let mut errors = Vec::new();
+        let message = self
+            .bundle
+            .format_pattern(pattern, Some(&args), &mut errors);
+        f.write_str(&message)
+    }
+}
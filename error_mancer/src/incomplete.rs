@@ -0,0 +1,24 @@
+use core::fmt::{self, Display};
+
+use winnow::stream::Needed;
+
+use crate::__private::Error;
+
+/// The payload of the `Incomplete` variant added for `winnow::error::ErrMode<E>` entries, a
+/// partial parser ran out of input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Incomplete {
+    /// How much more input the parser needed.
+    pub needed: Needed,
+}
+
+impl Display for Incomplete {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.needed {
+            Needed::Size(size) => write!(f, "{size} more bytes of input needed"),
+            Needed::Unknown => f.write_str("more input needed"),
+        }
+    }
+}
+
+impl Error for Incomplete {}
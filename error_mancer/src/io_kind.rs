@@ -0,0 +1,33 @@
+//! `embedded_io::Error` for the generated enums.
+//!
+//! Payloads implementing `embedded_io::Error` provide their own kind, for the others the kind
+//! given with `#[io_kind = Kind]` is used. This uses autoref specialization, i.e
+//! `(&Transparent(err)).forward_kind(ErrorKind::Other)`.
+
+use embedded_io::ErrorKind;
+
+use crate::__private::Transparent;
+
+#[doc(hidden)]
+pub trait ForwardKind {
+    fn forward_kind(&self, fallback: ErrorKind) -> ErrorKind;
+}
+
+impl<T: embedded_io::Error> ForwardKind for Transparent<'_, T> {
+    #[inline(always)]
+    fn forward_kind(&self, _fallback: ErrorKind) -> ErrorKind {
+        self.0.kind()
+    }
+}
+
+#[doc(hidden)]
+pub trait FallbackKind {
+    fn forward_kind(&self, fallback: ErrorKind) -> ErrorKind;
+}
+
+impl<T> FallbackKind for &Transparent<'_, T> {
+    #[inline(always)]
+    fn forward_kind(&self, fallback: ErrorKind) -> ErrorKind {
+        fallback
+    }
+}
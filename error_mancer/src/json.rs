@@ -0,0 +1,49 @@
+//! Serializing the `JsonError` wrappers generated by the `json_error` flag.
+
+use core::fmt::Display;
+
+use serde::ser::{SerializeMap, SerializeStruct};
+use serde::{Serialize, Serializer};
+
+/// Serialize `error` as `{"error": {"type": kind, "message": error.to_string()}}`.
+pub fn serialize_json_error<S: Serializer>(
+    kind: &str,
+    error: &dyn Display,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(1))?;
+    map.serialize_entry("error", &Body { kind, error })?;
+    map.end()
+}
+
+struct Body<'a> {
+    kind: &'a str,
+    error: &'a dyn Display,
+}
+
+impl Serialize for Body<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut body = serializer.serialize_struct("Error", 2)?;
+        body.serialize_field("type", self.kind)?;
+        body.serialize_field("message", &Message(self.error))?;
+        body.end()
+    }
+}
+
+struct Message<'a>(&'a dyn Display);
+
+impl Serialize for Message<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self.0)
+    }
+}
+
+/// A JSON response with the given status code, `500` if it isnt a valid status code.
+#[cfg(feature = "axum")]
+pub fn json_response<T: Serialize>(status: u16, body: T) -> axum::response::Response {
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+
+    let status = StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    (status, axum::Json(body)).into_response()
+}
@@ -39,6 +39,19 @@
 //!
 //! Defining no errors also works, which will generate an enum with no variants, enforcing that no errors are returned. This is useful for functions that are guaranteed not to fail but still require a `Result<...>` return type, such as in trait implementations. It provides extra safety by ensuring that no error paths are possible.
 //!
+//! These enums implement [`Uninhabited`], so the value can be taken out of the `Result` with
+//! [`InfallibleResultExt::into_value`] instead of `unwrap`:
+//!
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! #[errors]
+//! fn double(x: i32) -> Result<i32, _> {
+//!     Ok(x * 2)
+//! }
+//!
+//! assert_eq!(double(2).into_value(), 4);
+//! ```
+//!
 //! ## Enum name
 //! You can also explicitly set the enum name by using a Ident instead of `_` in the signature
 //! ```rust
@@ -58,6 +71,12 @@
 //! }
 //! ```
 //!
+//! ## Constructors
+//! Each variant also gets a constructor named after it in snake case, i.e
+//! `MyErrorEnum::std_io(err)`, for when `From` is ambiguous. Variants named after keywords use a
+//! raw identifier, such as `r#type`. A constructor named like a generated method, such as `kind`
+//! with the `kind` flag, is a error, rename the variant with `as Name`.
+//!
 //! ## Usage in `impl` Blocks
 //!
 //! To use the macro within an `impl` block, the block must also be annotated:
@@ -75,6 +94,54 @@
 //! }
 //! ```
 //!
+//! Methods can also use `#[cfg_attr(predicate, errors(...))]`, which is checked by the macro
+//! on the impl block since rustc doesnt expand it first.
+//!
+//! In a trait impl a method returning `Result<T, Self::Error>` sets the associated type to its
+//! generated enum, unless the impl already sets `type Error` itself. The enum is public, as it is
+//! reachable through the trait.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! trait Parse {
+//!     type Error;
+//!
+//!     fn parse(&self) -> Result<i32, Self::Error>;
+//! }
+//!
+//! struct Number(&'static str);
+//!
+//! #[errors]
+//! impl Parse for Number {
+//!     #[errors(std::num::ParseIntError)]
+//!     fn parse(&self) -> Result<i32, Self::Error> {
+//!         Ok(self.0.parse()?)
+//!     }
+//! }
+//!
+//! // type Error = ParseError;
+//! ```
+//!
+//! ## Usage on `static`s
+//! Closures without captures can be stored in a `static` as a `fn` pointer, `#[errors]` on the
+//! static generates the enum named after it. The attribute has to go on the static, attributes on
+//! closure expressions arent stable.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! # use std::num::ParseIntError;
+//! #[errors(ParseIntError)]
+//! static PARSE_NUMBER: fn(&str) -> Result<i32, _> = |x| Ok(x.parse()?);
+//!
+//! fn bar() {
+//!     match PARSE_NUMBER("x") {
+//!         Ok(_) => {}
+//!         Err(ParseNumberError::ParseInt(_)) => {}
+//!     }
+//! }
+//! ```
+//!
+//! Other closures and type aliases can use a enum declared with [`generate_error_impl!`], which
+//! implements the same traits as the enums generated by `#[errors]`.
+//!
 //! ## Usage with `anyhow::Result`
 //!
 //! The macro can also be used without overwriting an error type and is fully compatible with `anyhow::Result` and similar types. This is especially useful for developers who prefer using `anyhow` for general error handling but want to benefit from additional error type restrictions when needed, particularly in trait implementations:
@@ -91,6 +158,54 @@
 //! }
 //! ```
 //!
+//! The enum is then generated inside the function and converted into the outer error type by the
+//! final `?`, so the body is checked against the listed errors while the signature keeps
+//! `anyhow::Result<T>`. Writing `erase` makes this a requirement, a signature that would expose
+//! the enum (`_` or a name as the error type) fails to compile:
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! #[errors(erase; std::num::ParseIntError)]
+//! pub fn parse(x: &str) -> anyhow::Result<i32> {
+//!     Ok(x.parse()?)
+//! }
+//! ```
+//! This works with any error type the enum converts into with `?`, such as
+//! `Box<dyn Error + Send + Sync>`.
+//!
+//! ## Denying errors
+//! `deny(..)` does the opposite, every error can be returned except the denied ones. This only
+//! works with a passthrough error type, and `?` on a denied error fails to compile.
+//! ```rust,compile_fail
+//! # use error_mancer::prelude::*;
+//! #[errors(deny(std::io::Error))]
+//! fn foo() -> anyhow::Result<i32> {
+//!     let x: i32 = "10".parse()?;
+//!     // ❌ Compiler error: `std::io::Error` is denied in this function.
+//!     std::fs::File::open("hello.txt")?;
+//!     Ok(x)
+//! }
+//! ```
+//! This works by rewriting the `?` operators of the function, so a `?` inside a macro call isnt
+//! checked. The error type has to be known at the `?`, and the denied types cant use generic
+//! parameters of the function.
+//!
+//! ## Functions that cant fail
+//! A bare `#[errors]` generates a enum without variants, which is a different type for every
+//! function. `#[errors(none)]` uses the shared [`NoError`] instead, so these functions have the
+//! same error type, and it flattens into any other error.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! #[errors(none)]
+//! fn double(x: i32) -> Result<i32, _> {
+//!     Ok(x * 2)
+//! }
+//!
+//! #[errors(std::num::ParseIntError)]
+//! fn parse(x: &str) -> Result<i32, _> {
+//!     Ok(double(x.parse()?).into_super_error::<ParseError>()?)
+//! }
+//! ```
+//!
 //! ## Upcasting types
 //! ```rust
 //! # use error_mancer::prelude::*;
@@ -118,6 +233,236 @@
 //! }
 //! ```
 //!
+//! For error types which arent generated by `#[errors]`, `err_into::<S>()` converts with `From`
+//! instead, such as `foo().err_into::<anyhow::Error>()`.
+//!
+//! For a one-off error covering two functions, [`Union<A, B>`] holds either error without
+//! declaring a enum for it. `?` converts the left error, the right one has to be wrapped with
+//! `.map_err(Union::Right)` as `From` cant be implemented for both sides. A `Union` can be
+//! upcast into a enum covering the errors of both sides.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! # use error_mancer::Union;
+//! # use std::num::ParseIntError;
+//! #[errors(ParseIntError)]
+//! fn foo() -> Result<i32, _> {
+//!     # todo!()
+//! }
+//!
+//! #[errors(std::fmt::Error)]
+//! fn bar() -> Result<(), _> {
+//!     # todo!()
+//! }
+//!
+//! fn glue() -> Result<i32, Union<FooError, BarError>> {
+//!     bar().map_err(Union::Right)?;
+//!     Ok(foo()?)
+//! }
+//! ```
+//!
+//! When the target can only represent some of the errors, `try_flatten` converts the ones it
+//! can and hands back the original error for the rest. Both enums need the `try_flatten` flag.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! # use std::num::ParseIntError;
+//! #[errors(ParseIntError, std::fmt::Error, try_flatten)]
+//! fn foo(x: &str) -> Result<i32, _> {
+//!     Ok(x.parse()?)
+//! }
+//!
+//! #[errors(ParseIntError, try_flatten)]
+//! fn bar() -> Result<i32, _> {
+//!     match foo("x").try_flatten::<BarError>() {
+//!         Ok(value) => Ok(value),
+//!         Err(Ok(err)) => Err(err),
+//!         // Only `std::fmt::Error` is left over.
+//!         Err(Err(_)) => Ok(0),
+//!     }
+//! }
+//! ```
+//!
+//! ## Grouping errors
+//! Multiple errors can be stored in a single variant by grouping them, this generates a nested
+//! enum named after the outer enum and the group name. `?` works directly on each of the grouped
+//! errors.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! # use std::num::{ParseIntError, TryFromIntError};
+//! #[errors((ParseIntError, TryFromIntError) as Number, std::io::Error)]
+//! fn foo(x: &str) -> Result<u8, _> {
+//!     let x: i32 = x.parse()?;
+//!     Ok(x.try_into()?)
+//! }
+//!
+//! fn bar() {
+//!     match foo("300") {
+//!         Err(FooError::Number(FooNumberError::TryFromInt(_))) => {/* ... */},
+//!         Err(FooError::Number(_)) => {/* ... */},
+//!         Err(FooError::StdIo(_)) => {/* ... */},
+//!         Ok(_) => {/* ... */}
+//!     }
+//! }
+//! ```
+//! Groups can also be written as `group Number { ParseIntError, TryFromIntError }`, which reads
+//! better for larger groups.
+//!
+//! ## Blocks
+//! `errors_block!` restricts the errors of a single block, which is useful where a attribute
+//! cant be used (i.e inside `async_stream::try_stream!`). The generated enum is local to the
+//! block, so it is usually converted with `into_super_error` right away. Prefix the errors with
+//! `async` to allow `.await` in the block.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! # use std::num::ParseIntError;
+//! #[errors(ParseIntError, std::io::Error)]
+//! fn foo() -> Result<i32, _> {
+//!     let x = errors_block!(ParseIntError => {
+//!         let x: i32 = "10".parse()?;
+//!         Ok(x * 2)
+//!     })
+//!     .into_super_error::<FooError>()?;
+//!     Ok(x)
+//! }
+//! ```
+//!
+//! `errors_in_scope!` does the same for closures spawned in a `std::thread::scope`, returning
+//! the `ScopedJoinHandle`.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! # use std::num::ParseIntError;
+//! #[errors(ParseIntError)]
+//! fn sum(values: &[&str]) -> Result<i32, _> {
+//!     std::thread::scope(|scope| {
+//!         let handles = values
+//!             .iter()
+//!             .map(|value| errors_in_scope!(scope, #[errors(ParseIntError)] || {
+//!                 Ok(value.parse::<i32>()?)
+//!             }))
+//!             .collect::<Vec<_>>();
+//!         let mut sum = 0;
+//!         for handle in handles {
+//!             sum += handle.join().unwrap().into_super_error::<SumError>()?;
+//!         }
+//!         Ok(sum)
+//!     })
+//! }
+//! ```
+//!
+//! `errors_closure!` restricts a closure on its own, such as one passed to `Iterator::map`.
+//! Without a return type the `Ok` type is inferred.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! # use std::num::ParseIntError;
+//! #[errors(ParseIntError)]
+//! fn parse_all(values: &[&str]) -> Result<Vec<i32>, _> {
+//!     let parsed = values
+//!         .iter()
+//!         .map(errors_closure!(#[errors(ParseIntError)] |value: &&str| -> Result<i32, _> {
+//!             Ok(value.parse()?)
+//!         }))
+//!         .collect::<Result<Vec<_>, _>>()
+//!         .into_super_error::<ParseAllError>()?;
+//!     Ok(parsed)
+//! }
+//! ```
+//!
+//! ## Handling some errors
+//! `handle!` matches on a value like `match`, propagating the cases which arent handled. Errors
+//! in a `Result` are converted into the given enum and returned, while `Ok` values are passed
+//! through. For `Option`s, `None` has to be handled explicitly and the value in `Some` is
+//! propagated the same way, so `Option<Result<..>>` works too.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! # use std::num::ParseIntError;
+//! # #[derive(Debug)]
+//! # struct Missing;
+//! # impl std::fmt::Display for Missing {
+//! #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("missing") }
+//! # }
+//! #[errors(ParseIntError, Missing)]
+//! fn foo(values: &[&str]) -> Result<i32, _> {
+//!     let value = handle!(values.first().map(|value| value.parse::<i32>()) => FooError {
+//!         None => Err(Missing)?,
+//!         Some(Err(err)) if values.len() > 1 => 0,
+//!     });
+//!     Ok(value)
+//! }
+//! ```
+//!
+//! With the `anyhow` feature `wrap_rest(context)` can be used instead of the enum, wrapping the
+//! remaining errors in an `anyhow::Error` with the given context. The context expression is
+//! only evaluated when an error is returned.
+//! ```rust,ignore
+//! fn sync(path: &str) -> anyhow::Result<i32> {
+//!     let value = handle!(fetch(path) => wrap_rest(format!("while syncing {path}")) {
+//!         Err(FetchError::Timeout(_)) => 0,
+//!     });
+//!     Ok(value)
+//! }
+//! ```
+//!
+//! ## Splitting errors
+//! `split_variants!` generates two enums from the variants of a generated enum, and a
+//! conversion into a `Result` of the two. This is useful to separate errors that can be
+//! recovered from from the ones that cant.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! # use std::num::ParseIntError;
+//! #[errors(std::io::Error, ParseIntError)]
+//! fn fetch(path: &str) -> Result<i32, _> {
+//!     Ok(std::fs::read_to_string(path)?.trim().parse()?)
+//! }
+//!
+//! split_variants!(FetchError; pub Recoverable = (StdIo), pub Fatal = (ParseInt));
+//!
+//! #[errors(Fatal)]
+//! fn fetch_or_default(path: &str) -> Result<i32, _> {
+//!     match fetch(path).map_err(Result::<Recoverable, Fatal>::from) {
+//!         Ok(value) => Ok(value),
+//!         Err(Ok(_)) => Ok(0),
+//!         Err(Err(fatal)) => Err(fatal)?,
+//!     }
+//! }
+//! ```
+//!
+//! Every variant has to be listed in exactly one of the enums. The new enums display the same
+//! as the payload and can be converted back into the original enum with `From`. The macro has
+//! to be used in the module defining the enum or one of its children.
+//!
+//! ## Other payload types
+//! Types which arent a simple path, such as trait objects, need to be named explicitly with
+//! `as Name`. For payloads that dont implement `Display` the variant name is displayed instead,
+//! which can be changed with `#[display = "..."]` or
+//! `#[display_with = path::to::function]` (taking `(&Payload, &mut Formatter)`). The exception
+//! is `&'static str`, which is named `Str` and displays the message, so `Err("message")?` works
+//! without defining a error type.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! trait Diagnostic: core::fmt::Debug {}
+//!
+//! #[errors(
+//!     #[display = "diagnostic failure"]
+//!     Box<dyn Diagnostic + Send> as Diagnostic,
+//!     std::io::Error as Io,
+//! )]
+//! fn foo() -> Result<(), _> {
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ## Poisoned locks
+//! `std::sync::PoisonError<G>` holds the guard, which borrows the lock, so it cant be listed.
+//! With the `std` feature the `poison` entry adds a `Poisoned` variant holding
+//! `error_mancer::Poisoned`, and any `PoisonError` converts into it:
+//! ```rust,ignore
+//! #[errors(poison, std::num::ParseIntError)]
+//! fn add(total: &Mutex<i32>, value: &str) -> Result<i32, _> {
+//!     let mut total = total.lock()?;
+//!     *total += value.parse::<i32>()?;
+//!     Ok(*total)
+//! }
+//! ```
+//!
 //! ## Deriving traits for generated enum
 //! You can annotate the function with `#[derive]` to derive traits for the generated enum.
 //! Note that the `#[derive]` macro must be used after the `errors` macro. (technically in `impl`
@@ -140,20 +485,452 @@
 //! }
 //! ```
 //!
+//! `Debug` is always implemented, printing each variant like `#[derive(Debug)]` would. Error
+//! types that dont implement `Debug` can be marked `no_debug` (i.e `#[errors(Handle no_debug)]`)
+//! to print them as `<redacted>`, passing `no_debug` as a flag applies this to every error.
+//! To implement `Debug` by hand pass the `custom_debug` flag, an explicit `#[derive(Debug)]` on
+//! the function is used as-is as well.
+//!
+//! Errors holding secrets, such as connection strings, can be marked `redact` before or after
+//! the type (i.e `#[errors(redact sqlx::Error)]`). Their `Debug` output is `<redacted>`,
+//! `Display` prints `"Sqlx (details redacted)"` unless `#[display = "..."]` is given, and
+//! `Error::source` returns `None` so the secret cant leak through error chains.
+//!
+//! Traits can be derived conditionally with `cfg_derive(predicate, Traits..)`, which adds
+//! `#[cfg_attr(predicate, derive(Traits..))]` to the enum. A `#[cfg_attr(test, derive(..))]`
+//! on the function works the same.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! #[errors(std::num::ParseIntError, cfg_derive(test, PartialEq, Eq))]
+//! fn foo(x: &str) -> Result<i32, _> {
+//!     Ok(x.parse()?)
+//! }
+//! ```
+//! A trait derived with `#[derive]` isnt derived again conditionally, while a conditional derive
+//! replaces the same trait from the `derive` defaults in `error_mancer.toml`. A `cfg_derive` on a
+//! impl block is a default for the methods that dont derive the trait conditionally themselves,
+//! and a conditionally derived `Debug` replaces the generated implementation when the predicate
+//! holds.
+//!
+//! ## Catch-all variant
+//! `allow_extra = Name` adds a extra variant holding a
+//! `Box<dyn Error + Send + Sync>`, which can hold errors not listed in the attribute. Due to
+//! trait coherence unlisted errors are not boxed automatically, so they have to be boxed
+//! explicitly (`?` does work on already boxed errors). This requires the `alloc` feature.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! #[errors(allow_extra = Unexpected, std::num::ParseIntError)]
+//! fn foo(x: &str) -> Result<i32, _> {
+//!     let file = std::fs::read_to_string(x).map_err(|err| FooError::Unexpected(Box::new(err)))?;
+//!     Ok(file.trim().parse()?)
+//! }
+//! ```
+//!
+//! ## Retryable errors
+//! Errors marked `retryable` make the generated `retryable()` method return `true`, grouped
+//! errors are checked recursively.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! #[errors(std::io::Error retryable, std::num::ParseIntError)]
+//! fn foo(x: &str) -> Result<i32, _> {
+//!     Ok(std::fs::read_to_string(x)?.trim().parse()?)
+//! }
+//!
+//! # fn main() {
+//! if let Err(err) = foo("number.txt") {
+//!     if err.retryable() { /* try again */ }
+//! }
+//! # }
+//! ```
+//!
+//! ## Classifying errors
+//! With the `classify` flag `FooError::classify(err)` returns the name of the variant a
+//! `&(dyn Error + 'static)` would be converted into, by downcasting it to each listed error type. This is useful when a framework
+//! only hands back a `&dyn Error`. Errors in a group are classified as the group, and `FooError`
+//! itself is classified as its current variant. Payloads that dont implement `Error` are never
+//! matched, and with `allow_extra` every other error is classified as the catch-all variant.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! #[errors(std::io::Error, std::num::ParseIntError, classify)]
+//! fn foo(x: &str) -> Result<i32, _> {
+//!     Ok(std::fs::read_to_string(x)?.trim().parse()?)
+//! }
+//!
+//! # fn main() {
+//! let err: Box<dyn std::error::Error> = Box::new("x".parse::<i32>().unwrap_err());
+//! assert_eq!(FooError::classify(&*err), Some("StdNumParseInt"));
+//! # }
+//! ```
+//!
+//! ## Error kinds
+//! The `kind` flag generates a fieldless `FooErrorKind` enum next to `FooError` and a `kind()`
+//! method returning it. It is `#[repr(i32)]`, and `#[discriminant = ..]` sets the value of a
+//! variant, so the codes stay stable when passed over FFI.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! #[errors(
+//!     #[discriminant = 1] std::io::Error,
+//!     #[discriminant = 2] std::num::ParseIntError,
+//!     kind,
+//! )]
+//! fn foo(x: &str) -> Result<i32, _> {
+//!     Ok(std::fs::read_to_string(x)?.trim().parse()?)
+//! }
+//!
+//! # fn main() {
+//! let code = match foo("number.txt") {
+//!     Ok(_) => 0,
+//!     Err(err) => err.kind() as i32,
+//! };
+//! # }
+//! ```
+//!
+//! Adding `iter_kinds` also generates `FooError::kinds()`, iterating over every kind in
+//! declaration order, which is handy for listing the possible error codes.
+//!
+//! ## Opaque errors
+//! Libraries that want to add errors without a breaking change can start the arguments with
+//! `opaque;` (or pass `opaque` as a flag). This generates a `pub struct FooError` wrapping a
+//! hidden enum, so callers can only use its `kind()`, the message and the source. `?` and
+//! [`ResultExt::into_super_error`] work the same as with the enum.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! #[errors(opaque; std::io::Error, std::num::ParseIntError)]
+//! pub fn foo(x: &str) -> Result<i32, _> {
+//!     Ok(std::fs::read_to_string(x)?.trim().parse()?)
+//! }
+//!
+//! # fn main() {
+//! if let Err(err) = foo("number.txt") {
+//!     if err.kind() == FooErrorKind::StdIo {
+//!         println!("cant read the file: {err}");
+//!     }
+//! }
+//! # }
+//! ```
+//! The `zbus`, `clap` and `yew` implementations and `json_error` arent available for opaque
+//! errors.
+//!
+//! ## Boxed errors
+//! A large enum makes every `Result` holding it as large, even on the `Ok` path. Starting the
+//! arguments with `boxed_self;` returns `Box<FooError>` instead, so only the error path pays for
+//! a allocation, as clippy's `result_large_err` suggests. `?`,
+//! [`ResultExt::into_super_error`] and [`ResultExt::try_flatten`] (with `try_flatten`) work on the
+//! box as they do on the enum. The enum can be named with `Result<T, Box<Name>>`.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! #[errors(boxed_self; std::io::Error, std::num::ParseIntError)]
+//! fn foo(x: &str) -> Result<i32, _> {
+//!     Ok(std::fs::read_to_string(x)?.trim().parse()?)
+//! }
+//!
+//! fn bar() {
+//!     let _: Result<i32, Box<FooError>> = foo("number.txt");
+//! }
+//! ```
+//!
+//! ## Error metadata
+//! `#[ErrorMancerMeta(http_status = 503, retry = true)]` implements [`ErrorMeta`] for a error
+//! type. The generated enums implement [`ErrorMeta`] as well, returning the metadata of the
+//! contained error, or the defaults (`None` and `false`) when it has none. Entries marked
+//! `retryable` are always retryable.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! #[ErrorMancerMeta(http_status = 503, retry = true)]
+//! #[derive(Debug)]
+//! struct Unavailable;
+//! # impl std::fmt::Display for Unavailable {
+//! #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("unavailable") }
+//! # }
+//!
+//! #[errors(Unavailable, std::num::ParseIntError)]
+//! fn foo(x: &str) -> Result<i32, _> {
+//!     Ok(x.parse()?)
+//! }
+//!
+//! # fn main() {
+//! if let Err(err) = foo("x") {
+//!     let status = err.http_status_for().unwrap_or(500);
+//! }
+//! # }
+//! ```
+//!
+//! ## HTTP status codes
+//! With the `http` feature the enums implement `HttpStatusCode`. Each variant uses the status
+//! given with `#[http_status = 503]`, otherwise the one from the [`ErrorMeta`] of the error, and
+//! `500` as a last resort (which can be changed with `#[errors(http_status = 502)]`). This can be
+//! used to implement responses for web frameworks:
+//! ```rust,ignore
+//! #[errors(#[http_status = 503] DbError, #[http_status = 400] ValidationError)]
+//! fn create_user(body: &str) -> Result<User, _> { ... }
+//!
+//! impl IntoResponse for CreateUserError {
+//!     fn into_response(self) -> Response {
+//!         let status = StatusCode::from_u16(self.http_status()).unwrap();
+//!         (status, self.to_string()).into_response()
+//!     }
+//! }
+//! ```
+//!
+//! ## JSON errors
+//! With the `serde` feature the `json_error` flag generates a `FooJsonError` wrapper around
+//! `FooError`, serialized as `{"error": {"type": "StdIo", "message": "..."}}` with the variant
+//! name as the type and the `Display` text as the message. Anything `FooError` can be created
+//! from converts into it, so `?` works in handlers. The `axum` feature also implements
+//! `IntoResponse` for it, using the status from `HttpStatusCode`.
+//! ```rust,ignore
+//! #[errors(#[http_status = 404] NotFound, DbError, json_error)]
+//! fn load_user(id: u32) -> Result<User, _> { ... }
+//!
+//! async fn get_user(Path(id): Path<u32>) -> Result<Json<User>, LoadUserJsonError> {
+//!     Ok(Json(load_user(id)?))
+//! }
+//! ```
+//!
+//! ## Serde errors
+//! With the `serde` feature the `serde_error` flag implements `serde::ser::Error` and
+//! `serde::de::Error`, so the enum can be the error type of a `Serializer` or `Deserializer`.
+//! The messages passed to `custom` are stored in the `String` entry, which has to be listed:
+//! ```rust,ignore
+//! #[errors(NotADigit, String, serde_error)]
+//! fn write_digit(out: &mut String, value: u64) -> Result<(), DigitError> { ... }
+//!
+//! impl Serializer for DigitSerializer<'_> {
+//!     type Error = DigitError;
+//!     ...
+//! }
+//! ```
+//!
+//! ## OpenTelemetry
+//! With the `opentelemetry` feature the `opentelemetry` flag records errors on the active
+//! OpenTelemetry span when they are created, adding a exception event and setting the span
+//! status. The status is `Error` with the `Display` text as the description, other statuses can
+//! be selected with `#[otel_status = Ok]` or `#[otel_status = Unset]`. The generated
+//! `otel_status()` method returns the status of a error.
+//! ```rust,ignore
+//! #[errors(#[otel_status = Unset] Cancelled, DbError, opentelemetry)]
+//! fn load_user(id: u32) -> Result<User, _> { ... }
+//! ```
+//!
+//! ## Span traces
+//! Passing `span_trace` adds a [`SpanTrace`] to each variant which is captured when the error is
+//! converted, and a `span_trace(&self)` accessor to the enum. With the `tracing-error` feature
+//! this is a `tracing_error::SpanTrace`, otherwise its a zero sized placeholder.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! #[errors(std::io::Error, span_trace)]
+//! fn foo() -> Result<(), _> {
+//!     std::fs::read("config.toml")?;
+//!     Ok(())
+//! }
+//!
+//! fn bar() {
+//!     if let Err(err @ FooError::StdIo(_, _)) = foo() {
+//!         let _trace = err.span_trace();
+//!     }
+//! }
+//! ```
+//!
+//! ## Generated docs
+//! The macro appends a `# Errors` section to the function docs listing each error type, which
+//! keeps `clippy::missing_errors_doc` happy. Doc comments on a error type in the attribute are
+//! included in the list. This is skipped if the function already has a `# Errors` section or
+//! `no_doc` is passed.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! /// Open the config file.
+//! #[errors(
+//!     /// The config file could not be read.
+//!     std::io::Error
+//! )]
+//! pub fn open_config() -> Result<(), _> {
+//!     std::fs::read("config.toml")?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Other attributes stay on the function, including `#[expect(..)]`. Expecting a doc lint such
+//! as `clippy::missing_errors_doc` would always fail, as the lint cant trigger on the rewritten
+//! function, so these are turned into `#[allow(..)]`.
+//!
+//! ## Defaults
+//! Flags and options can be given defaults in a `error_mancer.toml`, the closest one to the
+//! crate (searching from `CARGO_MANIFEST_DIR` upwards) is used. The search stops at the workspace
+//! root, the first `Cargo.toml` with a `[workspace]` table. Besides the flags it supports
+//! `derive` and `allow`, which are added to every generated enum, and `enum_suffix` (`Error` by
+//! default).
+//! ```toml
+//! no_doc = true
+//! derive = ["Clone", "PartialEq"]
+//! allow = ["clippy::large_enum_variant"]
+//! enum_suffix = "Failure"
+//! ```
+//! Flags and options passed to `#[errors]` on a impl block are defaults for its methods. The
+//! precedence is `error_mancer.toml` < impl block < attribute, flags can be turned off with
+//! `flag = false`:
+//! ```rust,ignore
+//! #[errors(no_doc = false, enum_suffix = "Problem")]
+//! impl Parser {
+//!     #[errors(ParseIntError, no_doc)]
+//!     fn parse(&self, x: &str) -> Result<i32, _> { ... }
+//! }
+//! ```
+//! Traits from `derive` are skipped if the function derives them already. Changes to the file
+//! rebuild the crate, but a newly created file is only picked up by the next build of the crate.
+//!
+//! ## `const fn`
+//! `#[errors]` can be used on `const fn` on nightly by enabling the `const_closures` feature,
+//! which makes the generated wrapper use a `const` closure. This includes `const fn` methods in
+//! impl blocks, without the feature they are rejected with a error pointing at the `const`.
+//!
+//! ## Prototyping
+//! With the `prototyping` feature the `panic` flag makes the function panic on errors instead
+//! of returning them, while keeping the `Result` return type. This is meant for quickly
+//! sketching out code and should not be left in.
+//! ```rust,ignore
+//! #[errors(std::num::ParseIntError, panic)]
+//! fn parse(x: &str) -> Result<i32, _> {
+//!     Ok(x.parse()?)
+//! }
+//! ```
+//!
+//! ## Localized messages
+//! With the `i18n` feature every generated enum implements [`MessageId`], mapping each variant
+//! to a Fluent message id. The id defaults to the variant name in kebab case, and can be set
+//! with `msg_id "..."` after the error type. [`LocalizedDisplay`] renders a error using a
+//! `fluent_bundle::FluentBundle`, passing the normal `Display` output as `$error`.
+//! ```rust,ignore
+//! #[errors(std::io::Error msg_id "err-open-config")]
+//! fn open_config() -> Result<(), _> { ... }
+//!
+//! // err-open-config = Could not open config: { $error }
+//! let message = LocalizedDisplay::new(&err, &bundle).to_string();
+//! ```
+//! The `Display` implementation of the enum is unchanged.
+//!
+//! ## `winnow`
+//! With the `winnow` feature, `winnow::error::ErrMode<E>` entries store the inner `E` in the
+//! variant (named after `E`), so parser errors can be propagated with `?` without matching on
+//! `Backtrack`/`Cut` first.
+//! ```rust,ignore
+//! #[errors(ErrMode<ContextError>)]
+//! fn parse(mut input: &str) -> Result<Config, _> {
+//!     Ok(config.parse_next(&mut input)?)
+//! }
+//! // ParseError::Context(ContextError)
+//! ```
+//! `ErrMode::Incomplete` is only returned by partial parsers, it is stored in a `Incomplete`
+//! variant holding a [`Incomplete`] which is added to enums with `ErrMode` entries.
+//!
+//! ## `nom`
+//! With the `nom` feature the `nom` flag adds a `Nom` variant holding a [`NomError`] and
+//! implements nom's `ParseError`, `ContextError` and `FromExternalError`, so the enum can be the
+//! `E` in `IResult<I, O, E>`. Errors returned from `map_res` and similar are converted into their
+//! own variant, and `?` on a `nom::Err<E>` unwraps the error:
+//! ```rust,ignore
+//! fn entry(input: &str) -> IResult<&str, (&str, u32), ParseConfigError> {
+//!     separated_pair(alpha1, tag("="), map_res(digit1, str::parse)).parse(input)
+//! }
+//!
+//! #[errors(nom, ParseIntError, DuplicateKey)]
+//! fn parse_config(input: &str) -> Result<Config, _> {
+//!     let (_, entries) = separated_list1(tag(";"), entry).parse(input)?;
+//!     ...
+//! }
+//! // ParseConfigError::Nom(NomError), ParseConfigError::ParseInt(ParseIntError), ..
+//! ```
+//! [`NomError`] stores the length of the remaining input, `input_offset` turns it into the
+//! offset into the full input. `nom` only keeps the innermost error, passing `nom_trace` instead
+//! records the combinators and contexts it passed through in `NomError::trace`. A
+//! `nom::Err::Incomplete` from a streaming parser converts into a `NomError` with
+//! `needed` set.
+//!
+//! ## `embedded-io`
+//! With the `embedded-io` feature the enums implement `embedded_io::Error`, so they can be used
+//! as the error type of `embedded_io::Read`/`Write` implementations. Payloads implementing
+//! `embedded_io::Error` provide their own kind, otherwise the kind is `Other` unless set with
+//! `#[io_kind = Kind]`. This doesnt require `alloc`.
+//! ```rust,ignore
+//! #[errors(#[io_kind = InvalidData] Checksum, LinkError)]
+//! fn receive(transport: &mut Transport, buf: &mut [u8]) -> Result<usize, ReceiveError> { ... }
+//!
+//! impl ErrorType for Transport {
+//!     type Error = ReceiveError;
+//! }
+//! ```
+//!
+//! ## `zbus`
+//! With the `zbus` feature the `zbus` flag implements `From<FooError> for zbus::fdo::Error`, so
+//! restricted functions can be used with `?` in DBus method handlers. The `Display` text is used
+//! as the message and the error kind is `Failed` unless set with `#[fdo = Kind]`. Groups use the
+//! kinds of their entries. The implementation is only emitted when the enum is generated outside
+//! the function.
+//! ```rust,ignore
+//! #[errors(ParseIntError, #[fdo = AccessDenied] Forbidden, zbus)]
+//! fn parse(value: &str) -> Result<u32, _> { ... }
+//!
+//! #[interface(name = "org.example.Service")]
+//! impl Service {
+//!     fn parse(&self, value: &str) -> fdo::Result<u32> {
+//!         Ok(parse(value)?)
+//!     }
+//! }
+//! ```
+//!
+//! ## `clap`
+//! With the `clap` feature, `From<FooError> for clap::Error` is implemented, using the `Display`
+//! text as the message and `ErrorKind::ValueValidation` unless set with `#[clap_kind = Kind]`.
+//! `ResultExt::into_clap(cmd)` converts the error and formats it for the command, so it is printed
+//! with the usage like clap's own errors. Like with `zbus` the implementation is only emitted when
+//! the enum is generated outside the function.
+//! ```rust,ignore
+//! #[errors(ParseIntError, #[clap_kind = InvalidValue] Reserved)]
+//! fn parse_port(value: &str) -> Result<u16, _> { ... }
+//!
+//! impl TypedValueParser for PortParser {
+//!     type Value = u16;
+//!
+//!     fn parse_ref(&self, cmd: &Command, _: Option<&Arg>, value: &OsStr) -> Result<u16, clap::Error> {
+//!         parse_port(&value.to_string_lossy()).into_clap(cmd)
+//!     }
+//! }
+//! ```
+//!
+//! ## `yew`
+//! With the `yew` feature the `yew` flag implements `IntoPropValue<String>`, so a error can be
+//! passed directly as a `String` prop, displaying its message. `From<FooError> for Classes` gives the
+//! kebab-case variant name as a class, i.e `parse-int`, with grouped errors also getting the
+//! group name. Sending errors between agents only needs them to be serializable, which the
+//! `json_error` wrapper covers. Like with `zbus` the implementations are only emitted when the
+//! enum is generated outside the function.
+//! ```rust,ignore
+//! html! {
+//!     <ErrorMessage message={err} />
+//! }
+//! ```
+//!
+//! ## `Error::provide`
+//! On nightly the `provide` feature makes the generated `Error` implementation forward
+//! `provide` to the wrapped error, so i.e a `Backtrace` captured by a leaf error can be
+//! retrieved with `core::error::request_ref` on the enum. Payloads which dont implement `Error`
+//! provide nothing.
+//!
 //! # Specifics and Implementation Details
 //!
 //! ## Error Type Overwriting
 //!
 //! The macro looks for a type named `Result` in the root of the return type. If the second generic argument is `_`, it replaces it with the appropriate error type. See the examples below:
 //!
-//! | Original                    | Modified                                         |
-//! | --------------------------- | ------------------------------------------------ |
-//! | `Result<T, _>`              | `Result<T, FooError>`                            |
-//! | `Result<T, CustomName>`     | `Result<T, CustomName>`                          |
-//! | `Result<T, Box<dyn Error>>` | `Result<T, Box<dyn Error>>`                      |
-//! | `std::result::Result<T, _>` | `std::result::Result<T, FooError>`               |
-//! | `anyhow::Result<T>`         | `anyhow::Result<T>`                              |
-//! | `Vec<Result<T, _>>`         | ❌ compiler error, nested types arent replaced   |
+//! | Original                       | Modified                                      |
+//! | ------------------------------ | --------------------------------------------- |
+//! | `Result<T, _>`                 | `Result<T, FooError>`                         |
+//! | `Result<T, CustomName>`        | `Result<T, CustomName>`                       |
+//! | `Result<T, Box<dyn Error>>`    | `Result<T, Box<dyn Error>>`                   |
+//! | `std::result::Result<T, _>`    | `std::result::Result<T, FooError>`            |
+//! | `::core::result::Result<T, _>` | `::core::result::Result<T, FooError>`         |
+//! | `anyhow::Result<T>`            | `anyhow::Result<T>`                           |
+//! | `Result<Vec<FooError>, _>`     | `Result<Vec<FooError>, FooError>`             |
+//! | `Vec<Result<T, _>>`            | ❌ compiler error, nested types arent replaced |
 //!
 //! ## Enum Visibility
 //!
@@ -164,6 +941,9 @@
 //! * If the second generic argument in `Result` is a simple identifier its emitted outside.
 //! * Otherwise, the enum is generated inside the function body and cannot be accessed externally.
 //!
+//! An enum emitted outside the function gets the `#[cfg(..)]` attributes of the function, so it
+//! is left out along with the function.
+//!
 //! (in the following example the enums are include to illustrate where the macro would
 //! generate them.)
 //! ```rust,ignore
@@ -189,14 +969,227 @@
 //! }
 //! ```
 //!
+//! ## `Error` Trait
+//!
+//! The generated enums implement `core::error::Error`, so the crate needs Rust 1.85 or newer.
+//! Enable the `std_error` feature to implement `std::error::Error` instead, or pass
+//! `use_std_error` to do so for a single enum.
+//!
+//! ## `async fn`
+//!
+//! The body of a `async fn` is moved into a `async` closure, while the function itself stays a
+//! `async fn`. So the returned future captures the same lifetimes as without `#[errors]`, in
+//! every edition.
+//!
+//! ## `Future::poll`
+//!
+//! A `Poll<Result<T, _>>` return type is handled like `Result<T, _>`. In a `Future` impl the
+//! `Output` can be left open instead, it is set to the result with the generated enum:
+//! ```rust,ignore
+//! #[errors]
+//! impl Future for ParseLater {
+//!     type Output = Result<i32, _>;
+//!
+//!     #[errors(ParseIntError)]
+//!     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> { ... }
+//! }
+//! ```
+//! The enum is named after the method (`PollError`), so with multiple futures in a module name it
+//! with `type Output = Result<i32, ParseLaterError>`.
+//!
+//! ## `try` blocks
+//!
+//! On nightly `try` blocks dont convert errors, so a function body made of a single `try` block
+//! wouldnt convert into the generated enum. When the body ends in a `try` block its contents are
+//! used as the body of the closure instead, wrapped in `Ok`, so `?` converts the errors as
+//! usual. Other `try` blocks are left as they are.
+//!
+//! ## `Result<!, _>`
+//!
+//! Functions that only return on errors, such as a event loop, can use `Result<!, _>` on nightly
+//! with `#![feature(never_type)]`. Only the error of the wrapped body is matched, so the
+//! generated code doesnt cause `unreachable_code` warnings.
+//!
+//! ## Early returns
+//!
+//! The body is moved into a closure, so a `return` (including one expanded from a macro such as
+//! `ensure!`) returns from the closure. The closure's result is returned by the function right
+//! away, so this behaves the same as returning from the function. The returned error has to be
+//! the generated enum, so macros should convert their error with `From::from`:
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! # #[derive(Debug, thiserror::Error)]
+//! # #[error("out of range")]
+//! # struct OutOfRange;
+//! macro_rules! ensure {
+//!     ($cond:expr, $err:expr) => {
+//!         if !$cond {
+//!             return Err(From::from($err));
+//!         }
+//!     };
+//! }
+//!
+//! #[errors(OutOfRange)]
+//! fn check(x: i32) -> Result<i32, _> {
+//!     ensure!(x < 100, OutOfRange);
+//!     Ok(x)
+//! }
+//! ```
+//! `anyhow::ensure!` and `anyhow::bail!` return a `anyhow::Error` directly, which isnt one of the
+//! listed errors, so they are rejected even when the function returns `anyhow::Result`.
+//!
+//! `?` on a value that already is the generated enum, such as `Err(err)?` with
+//! `err: FooError`, uses the `From<T> for T` implementation from `core`, so the variant (and its
+//! span trace) is kept as is.
+//!
+//! ## Collecting results
+//! `.collect::<Result<Vec<_>, _>>()?` works in the body, the error type is taken from the items
+//! and converted by `?`. A closure using `?` itself has to name its error type, such as
+//! `|x| -> Result<_, FooError> { Ok(x.parse::<i32>()?) }`, as `?` can convert into any type.
+//!
+//! ## Partial success
+//! For errors that shouldnt stop the function return `Result<Partial<T, _>, _>`, both `_` are
+//! replaced with the generated enum. Fatal errors are returned with `?`, the others are pushed
+//! with `Partial::push_err` or collected with `ResultIteratorExt::collect_partial`:
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! # use std::num::ParseIntError;
+//! #[errors(ParseIntError, std::io::Error)]
+//! fn import(lines: &[&str]) -> Result<Partial<Vec<i32>, _>, _> {
+//!     if lines.is_empty() {
+//!         Err(std::io::Error::other("nothing to import"))?;
+//!     }
+//!     Ok(lines.iter().map(|line| line.parse::<i32>()).collect_partial())
+//! }
+//!
+//! let imported = import(&["1", "x", "3"]).unwrap();
+//! assert_eq!(imported.value, [1, 3]);
+//! assert!(matches!(imported.errors[..], [ImportError::ParseInt(_)]));
+//! assert!(imported.into_result(0).is_err());
+//! ```
+//! This needs the `alloc` feature.
+//!
+//! ## Wiring the enum by hand
+//! With `enum_only` the enum is generated as usual, but the function is left exactly as written,
+//! so it can return the enum in any shape:
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! # use std::num::ParseIntError;
+//! use std::sync::Arc;
+//!
+//! #[errors(ParseIntError, enum_only)]
+//! fn load(value: &str) -> Result<i32, Arc<LoadError>> {
+//!     value.parse().map_err(|err| Arc::new(LoadError::from(err)))
+//! }
+//!
+//! assert!(matches!(*load("x").unwrap_err(), LoadError::ParseInt(_)));
+//! ```
+//!
+//! ## Attribute Order
+//!
+//! `#[errors]` needs to see the original `Result` return type, so it has to be placed before
+//! attributes that rewrite the signature, such as `#[async_recursion]`:
+//! ```rust,ignore
+//! #[errors(ParseIntError)]
+//! #[async_recursion]
+//! async fn sum(values: &[&str]) -> Result<i32, _> { ... }
+//! ```
+//!
+//! ## `main`
+//!
+//! `main` can be annotated like any other function, for `#[tokio::main]` place it before
+//! `#[errors]`, so `#[errors]` sees the plain `async fn`:
+//! ```rust,ignore
+//! #[tokio::main]
+//! #[errors(std::io::Error)]
+//! async fn main() -> Result<(), _> { ... }
+//! ```
+//! The generated enum implements `Debug`, so returning a error prints it and exits with a
+//! failure code. To pick the exit code on success return `Result<ExitCode, _>`:
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! use std::process::ExitCode;
+//!
+//! #[errors(std::num::ParseIntError)]
+//! fn main() -> Result<ExitCode, _> {
+//!     let code: u8 = "0".parse()?;
+//!     Ok(ExitCode::from(code))
+//! }
+//! ```
+//!
+//! ## Re-exports
+//!
+//! A proc macro can only emit code in place of the item, so the enum cant be placed in another
+//! module. Instead `reexport = crate::errors` checks that the enum is re-exported from
+//! `crate::errors`, so a stable public path cant silently go missing.
+//! ```rust,ignore
+//! mod errors {
+//!     pub use super::parsers::ParseError;
+//! }
+//!
+//! mod parsers {
+//!     #[errors(std::num::ParseIntError, reexport = crate::errors)]
+//!     pub fn parse(x: &str) -> Result<i32, _> { ... }
+//! }
+//! ```
+//!
 //! ## Naming Conventions
 //!
 //! The enum name is derived from the function name, converted to Pascal case using the `case_fold` crate to conform to Rust naming conventions for types and enums. Similarly, variant names are derived from the path segments of the types, with the "Error" suffix removed if present. For example, `std::io::Error` would produce a variant called `StdIo`, while `io::Error` would produce `Io`.
 //!
+//! Since paths cant be resolved by the macro, listing a path that ends with another listed path of at least two segments (i.e `std::io::Error` and `io::Error`) is a warning, as they likely refer to the same type. Listing the exact same path twice is an error.
+//!
+//! A error with the same name as the generated enum, such as `ParseError` on a function `parse`,
+//! would be shadowed by the enum, so it is rejected. Write it as a path (`crate::ParseError`) or
+//! name the enum with `Result<T, Name>`. A variant named like the enum is fine.
+//!
+//! The macro cant see the other items in the module, so a type already named like the generated
+//! enum is only caught by the compiler, as "the name `FooError` is defined multiple times"
+//! followed by errors from the generated impls. Naming the enum with `Result<T, Name>` or
+//! changing `enum_suffix` avoids the conflict.
+//!
 //! ## Display Implementation
 //!
 //! The `Display` implementation simply delegates to each contained error, ensuring consistent and readable error messages.
 //!
+//! Likewise `Error::source` returns the source of the contained error, as the enum is only a
+//! transparent wrapper. Variants using `#[display = "..."]` or `#[display_with = ...]` return the
+//! contained error itself instead.
+//!
+//! With `display_context = "..."` every message is prefixed with the context, i.e
+//! `#[errors(std::io::Error, display_context = "function: foo")]` displays as
+//! `"function: foo: {err}"`. Grouped errors get the same prefix.
+//!
+//! The `dispatch` flag implements [`VariantDispatch`] instead, a single `match` returning the
+//! contained error as `&dyn Error`, and `Display` and `Error::source` call through it. This
+//! generates about half the code for these two traits, which adds up with many annotated
+//! functions. It requires every payload to implement `Error`, and cant be combined with
+//! `#[display = "..."]`, `#[display_with = ...]` or `display_context`.
+//!
+//! During development `impl_display_debug` displays the `Debug` output instead, such as
+//! `ParseInt(ParseIntError { kind: InvalidDigit })`, which shows the variant and every field
+//! before the payloads have good messages. `{:#}` uses the pretty `Debug` output.
+//!
+//! To write `Display` by hand pass the `no_display` flag, the generated `Error` implementation
+//! uses it. Grouped errors keep their generated `Display`, with `opaque` the struct is the one
+//! to implement it for. `display_context` has no effect then.
+//! ```rust
+//! # use error_mancer::prelude::*;
+//! # use std::fmt;
+//! #[errors(std::num::ParseIntError, no_display)]
+//! fn parse(x: &str) -> Result<i32, _> {
+//!     Ok(x.parse()?)
+//! }
+//!
+//! impl fmt::Display for ParseError {
+//!     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+//!         match self {
+//!             Self::StdNumParseInt(err) => write!(f, "not a number: {err}"),
+//!         }
+//!     }
+//! }
+//! ```
+//!
 //! ## `into_super_error`
 //! This function uses the `FlattenInto` trait which is automatically implemented by the macro for
 //! its errors, for all target types which implemnt `From<...>` for each of the errors variants. i.e a generated
@@ -225,14 +1218,194 @@
 //!         }
 //!     }
 //! ```
+//!
+//! If a error enum is never upcast you can pass `no_flatten` to skip generating this
+//! implementation, i.e `#[errors(std::io::Error, no_flatten)]`.
 #![no_std]
+#![cfg_attr(
+    feature = "provide",
+    feature(error_generic_member_access, allow_internal_unstable),
+    allow(internal_features)
+)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(any(feature = "std", feature = "std_error"))]
+extern crate std;
+
+mod context;
+mod deny;
+mod downcast;
+mod handle;
+#[cfg(feature = "i18n")]
+mod i18n;
+#[cfg(feature = "winnow")]
+mod incomplete;
+#[cfg(feature = "embedded-io")]
+mod io_kind;
+#[cfg(feature = "serde")]
+mod json;
+mod meta;
+mod no_error;
+#[cfg(feature = "nom")]
+mod nom_error;
+#[cfg(feature = "opentelemetry")]
+mod otel;
+#[cfg(feature = "alloc")]
+mod partial;
+#[cfg(feature = "std")]
+mod poison;
+mod span_trace;
+mod transparent;
+mod union;
+
+use core::fmt::Display;
+
+pub use context::WithContext;
+pub use error_mancer_macros::{errors, ErrorMancerMeta};
+#[cfg(feature = "i18n")]
+pub use i18n::{LocalizedDisplay, MessageId};
+#[cfg(feature = "winnow")]
+pub use incomplete::Incomplete;
+pub use meta::ErrorMeta;
+#[cfg(feature = "http")]
+pub use meta::HttpStatusCode;
+pub use no_error::NoError;
+#[cfg(feature = "nom")]
+pub use nom_error::{NomError, NomFrame};
+#[cfg(feature = "alloc")]
+pub use partial::Partial;
+#[cfg(feature = "std")]
+pub use poison::Poisoned;
+pub use span_trace::SpanTrace;
+pub use union::Union;
+
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "alloc")]
+    pub use alloc::boxed::Box;
+    #[cfg(feature = "alloc")]
+    pub use alloc::string::{String, ToString};
+    #[cfg(not(feature = "std_error"))]
+    pub use core::error::Error;
+    #[cfg(feature = "std_error")]
+    pub use std::error::Error;
+    #[cfg(feature = "std")]
+    pub use std::sync::PoisonError;
+
+    #[cfg(feature = "axum")]
+    pub use axum;
+    #[cfg(feature = "clap")]
+    pub use clap;
+    #[cfg(feature = "embedded-io")]
+    pub use embedded_io;
+    #[cfg(feature = "nom")]
+    pub use nom;
+    #[cfg(feature = "opentelemetry")]
+    pub use opentelemetry;
+    #[cfg(feature = "serde")]
+    pub use serde;
+    #[cfg(feature = "yew")]
+    pub use yew;
+    #[cfg(feature = "zbus")]
+    pub use zbus;
+
+    pub use crate::deny::{
+        assert_allowed,
+        assert_unlisted,
+        CheckAllowed,
+        CheckDenied,
+        CheckListed,
+        CheckUnlisted,
+        DeniedBy,
+        DenyProbe,
+        ListedProbe,
+        NotDenied,
+    };
+    pub use crate::downcast::{Downcast, ForwardDowncast, NoDowncast};
+    pub use crate::handle::{PropagateResult, PropagateValue, Propagator};
+    #[cfg(feature = "embedded-io")]
+    pub use crate::io_kind::{FallbackKind, ForwardKind};
+    #[cfg(feature = "axum")]
+    pub use crate::json::json_response;
+    #[cfg(feature = "serde")]
+    pub use crate::json::serialize_json_error;
+    pub use crate::meta::{ForwardMeta, ForwardNoMeta};
+    #[cfg(feature = "opentelemetry")]
+    pub use crate::otel::record_otel_error;
+
+    /// Bound of the generated `FlattenInto` implementations, so a missing error type is
+    /// reported in terms of the enums involved. This is implemented for each error a enum
+    /// accepts, a blanket implementation would report the missing `ErrorMancerFrom` instead.
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` cannot represent `{T}`, which `{Source}` may contain",
+        label = "`{T}` is not listed in the `#[errors]` attribute of `{Self}`",
+        note = "Add `{T}` to the `#[errors]` list of `{Self}`."
+    )]
+    pub trait Represents<T, Source>: crate::ErrorMancerFrom<T> {}
+    pub use crate::transparent::{ForwardError, ForwardNothing, Transparent};
+
+    /// Used by the generated `Debug` implementation, so a missing `Debug` implementation is
+    /// reported on the error type in the attribute.
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` doesn't implement `Debug`",
+        label = "`{Self}` is used in a `#[errors]` attribute",
+        note = "Add `no_debug` after `{Self}` to print it as `<redacted>` instead."
+    )]
+    pub trait PayloadDebug: core::fmt::Debug {}
+    impl<T: core::fmt::Debug> PayloadDebug for T {}
+
+    /// Used by the generated `Display` implementation, so a missing `Display` implementation is
+    /// reported on the error type in the attribute.
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` doesn't implement `Display`",
+        label = "`{Self}` is used in a `#[errors]` attribute",
+        note = "Add `#[display = \"...\"]` or `#[display_with = path]` before `{Self}` to display it \
+                another way."
+    )]
+    pub trait PayloadDisplay: core::fmt::Display {}
+    impl<T: core::fmt::Display> PayloadDisplay for T {}
+
+    /// Implemented by the generated enums, converting `payload` into the enum if it has a
+    /// variant for it. Used by the generated `TryFlattenInto` implementations.
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` isnt a enum generated by `#[errors]` with `try_flatten`",
+        note = "Errors can only be partially flattened into enums generated by `#[errors]` with the \
+                `try_flatten` flag."
+    )]
+    pub trait FromPayload: Sized {
+        fn from_payload<P: 'static>(payload: P) -> Result<Self, P>;
+    }
 
-pub use error_mancer_macros::errors;
+    #[inline(always)]
+    pub fn debug_payload<T: PayloadDebug>(payload: &T) -> &dyn core::fmt::Debug {
+        payload
+    }
+
+    #[inline(always)]
+    pub fn display_payload<T: PayloadDisplay>(payload: &T) -> &dyn core::fmt::Display {
+        payload
+    }
+}
 
 pub mod prelude {
-    pub use error_mancer_macros::errors;
+    pub use error_mancer_macros::{
+        errors,
+        errors_block,
+        errors_closure,
+        errors_in_scope,
+        handle,
+        split_variants,
+        ErrorMancerMeta,
+    };
 
-    pub use super::ResultExt;
+    #[cfg(feature = "http")]
+    pub use super::HttpStatusCode;
+    #[cfg(feature = "i18n")]
+    pub use super::MessageId;
+    pub use super::{ErrorMeta, InfallibleResultExt, OptionExt, ResultExt};
+    #[cfg(feature = "alloc")]
+    pub use super::{Partial, ResultIteratorExt};
 }
 
 #[doc(hidden)]
@@ -245,18 +1418,285 @@ pub trait ErrorMancerFrom<T> {
     fn from(value: T) -> Self;
 }
 
+/// Assert at compile time that a error enum does *not* accept the given error types.
+///
+/// This is useful for guarding a function that must never be able to return a certain error,
+/// i.e a offline-only function that shouldnt start returning network errors.
+///
+/// ```rust
+/// # use error_mancer::prelude::*;
+/// # use error_mancer::assert_not_allowed;
+/// #[errors(core::num::ParseIntError)]
+/// fn foo() -> Result<(), _> {
+///     Ok(())
+/// }
+///
+/// assert_not_allowed!(FooError, core::num::TryFromIntError);
+/// ```
+///
+/// ```rust,compile_fail
+/// # use error_mancer::prelude::*;
+/// # use error_mancer::assert_not_allowed;
+/// #[errors(core::num::ParseIntError)]
+/// fn foo() -> Result<(), _> {
+///     Ok(())
+/// }
+///
+/// assert_not_allowed!(FooError, core::num::ParseIntError);
+/// ```
+#[macro_export]
+macro_rules! assert_not_allowed {
+    ($enum:ty, $($err:ty),+ $(,)?) => {
+        $(
+            const _: fn() = || {
+                #[allow(unused_imports)]
+                use $crate::__private::{CheckListed as _, CheckUnlisted as _};
+                let probe = $crate::__private::ListedProbe::<$enum, $err>(::core::marker::PhantomData);
+                $crate::__private::assert_unlisted::<$enum, $err, _>((&probe).check());
+            };
+        )+
+    };
+}
+
+/// Declare a error enum with the implementations `#[errors]` generates, for places where
+/// `#[errors]` cant be used, such as closures and type aliases.
+///
+/// Each variant holds a single error type. The enum implements `Debug`, `Display` and `Error`
+/// by delegating to the contained error, `From` for each error type, and [`FlattenInto`].
+///
+/// ```rust
+/// # use error_mancer::prelude::*;
+/// use error_mancer::generate_error_impl;
+///
+/// generate_error_impl!(pub enum MyError {
+///     Io(std::io::Error),
+///     Parse(std::num::ParseIntError),
+/// });
+///
+/// let parse = |x: &str| -> Result<i32, MyError> { Ok(x.parse()?) };
+/// assert!(matches!(parse("x"), Err(MyError::Parse(_))));
+/// ```
+#[macro_export]
+macro_rules! generate_error_impl {
+    (
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident {
+            $($variant:ident($ty:ty)),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis enum $name {
+            $($variant($ty)),*
+        }
+
+        $(
+            #[doc(hidden)]
+            #[diagnostic::do_not_recommend]
+            impl $crate::ErrorMancerFrom<$ty> for $name {
+                fn from(value: $ty) -> Self {
+                    Self::$variant(value)
+                }
+            }
+
+            #[doc(hidden)]
+            impl<S> $crate::__private::Represents<$ty, S> for $name {}
+        )*
+
+        impl<T> ::core::convert::From<T> for $name where Self: $crate::ErrorMancerFrom<T> {
+            fn from(value: T) -> Self {
+                $crate::ErrorMancerFrom::from(value)
+            }
+        }
+
+        impl<T> $crate::FlattenInto<T> for $name
+        where
+            T: $($crate::__private::Represents<$ty, $name> +)*
+        {
+            fn flatten(self) -> T {
+                match self {
+                    $(Self::$variant(err) => <T as $crate::ErrorMancerFrom<$ty>>::from(err),)*
+                }
+            }
+        }
+
+        impl ::core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match *self {
+                    $(Self::$variant(ref err) => f
+                        .debug_tuple(::core::stringify!($variant))
+                        .field($crate::__private::debug_payload(err))
+                        .finish(),)*
+                }
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match *self {
+                    $(Self::$variant(ref err) => ::core::fmt::Display::fmt(
+                        $crate::__private::display_payload(err),
+                        f,
+                    ),)*
+                }
+            }
+        }
+
+        impl $crate::__private::Error for $name {
+            fn source(&self) -> ::core::option::Option<&(dyn $crate::__private::Error + 'static)> {
+                #[allow(unused_imports)]
+                use $crate::__private::{ForwardError as _, ForwardNothing as _};
+                match *self {
+                    $(Self::$variant(ref err) => (&$crate::__private::Transparent(err)).forward_source(),)*
+                }
+            }
+        }
+    };
+}
+
 /// This trait allows a error to be flattened into another one and is automatically implemented by
 /// the `#[errors]` macro for all super errors that implement `From<...>` for each of its fields.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be flattened into `{T}`",
+    note = "`FlattenInto` is implemented by `#[errors]` for every enum that can represent all of \
+            the errors, unless `no_flatten` is passed."
+)]
 pub trait FlattenInto<T> {
     fn flatten(self) -> T;
 }
 
-/// This trait extends `Result` with an additional method to upcast a error enum.
+/// Converting a error into another one which only covers some of its errors, returning the
+/// original error if it isnt covered. Implemented by the `#[errors]` macro for every enum `T`
+/// generated with the `try_flatten` flag.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be partially flattened into `{T}`",
+    note = "`TryFlattenInto` is implemented by `#[errors]` for enums generated with `try_flatten`."
+)]
+pub trait TryFlattenInto<T>: Sized {
+    fn try_flatten(self) -> Result<T, Self>;
+}
+
+/// The error held by the current variant, implemented by `#[errors]` for enums with the
+/// `dispatch` flag. Their `Display` and `Error` implementations go through it.
+pub trait VariantDispatch {
+    fn inner(&self) -> &(dyn __private::Error + 'static);
+}
+
+/// This trait extends `Result` with additional methods for working with error enums.
 pub trait ResultExt<T, E> {
     /// This will convert from the current `E` into the specified super error.
     fn into_super_error<S>(self) -> Result<T, S>
     where
         E: FlattenInto<S>;
+
+    /// Convert the error with `From`, the same as `.map_err(S::from)` but naming the target type.
+    ///
+    /// Unlike [`ResultExt::into_super_error`] this works for any `From` implementation.
+    fn err_into<S>(self) -> Result<T, S>
+    where
+        S: From<E>;
+
+    /// Convert the error into the specified error if it covers it, otherwise the original error
+    /// is kept, i.e `Err(Ok(converted))` or `Err(Err(original))`.
+    fn try_flatten<S>(self) -> Result<T, Result<S, E>>
+    where
+        E: TryFlattenInto<S>;
+
+    /// Convert the error into a `clap::Error` formatted for `cmd`, so it is printed with the usage
+    /// of the command. Useful in custom value parsers.
+    #[cfg(feature = "clap")]
+    fn into_clap(self, cmd: &clap::Command) -> Result<T, clap::Error>
+    where
+        E: Into<clap::Error>;
+
+    /// Attach a message to the error, similar to `anyhow::Context::context`.
+    ///
+    /// The resulting [`WithContext`] displays as `"{msg}: {err}"`, while still exposing the
+    /// original error for matching. With `anyhow::Context` also in scope the call is ambiguous,
+    /// write `ResultExt::context(result, msg)` then.
+    fn context<M>(self, msg: M) -> Result<T, WithContext<E, M>>
+    where
+        M: Display + Send + Sync + 'static;
+
+    /// Collapse a `Result<Result<T, A>, B>` into a `Result<T, S>` by flattening both `A` and `B`
+    /// into the specified super error.
+    fn flatten_nested<S>(self) -> Result<<T as IntoResult>::Ok, S>
+    where
+        T: IntoResult,
+        <T as IntoResult>::Err: FlattenInto<S>,
+        E: FlattenInto<S>;
+
+    /// Collapse a `Result<Option<T>, E>` into a `Result<T, S>` by flattening `E` into the
+    /// specified super error, and using `none` to create the error for a missing value.
+    fn flatten_option<S>(self, none: impl FnOnce() -> S) -> Result<<T as IntoOption>::Value, S>
+    where
+        T: IntoOption,
+        E: FlattenInto<S>;
+
+    /// Replace the error with its `Display` text, for logging. The typed error is dropped.
+    #[cfg(feature = "alloc")]
+    fn map_err_display(self) -> Result<T, alloc::string::String>
+    where
+        E: Display;
+}
+
+/// This trait extends `Option`s of `Result`s.
+pub trait OptionExt<T> {
+    /// Turn a `Option<Result<T, E>>` into a `Result<T, E>`, using `none` to create the error for
+    /// a missing value.
+    fn transpose_with(
+        self,
+        none: impl FnOnce() -> <T as IntoResult>::Err,
+    ) -> Result<<T as IntoResult>::Ok, <T as IntoResult>::Err>
+    where
+        T: IntoResult;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    #[inline(always)]
+    fn transpose_with(
+        self,
+        none: impl FnOnce() -> <T as IntoResult>::Err,
+    ) -> Result<<T as IntoResult>::Ok, <T as IntoResult>::Err>
+    where
+        T: IntoResult,
+    {
+        self.map_or_else(|| Err(none()), IntoResult::into_result)
+    }
+}
+
+/// Implemented for `Result`, used to name the inner result in [`ResultExt::flatten_nested`] and
+/// [`OptionExt`].
+pub trait IntoResult {
+    type Ok;
+    type Err;
+
+    fn into_result(self) -> Result<Self::Ok, Self::Err>;
+}
+
+impl<T, E> IntoResult for Result<T, E> {
+    type Ok = T;
+    type Err = E;
+
+    #[inline(always)]
+    fn into_result(self) -> Result<T, E> {
+        self
+    }
+}
+
+/// Implemented for `Option`, used to name the inner option in [`ResultExt::flatten_option`].
+pub trait IntoOption {
+    type Value;
+
+    fn into_option(self) -> Option<Self::Value>;
+}
+
+impl<T> IntoOption for Option<T> {
+    type Value = T;
+
+    #[inline(always)]
+    fn into_option(self) -> Option<T> {
+        self
+    }
 }
 
 impl<T, E> ResultExt<T, E> for Result<T, E> {
@@ -267,4 +1707,156 @@ impl<T, E> ResultExt<T, E> for Result<T, E> {
     {
         self.map_err(|err| err.flatten())
     }
+
+    #[inline(always)]
+    fn err_into<S>(self) -> Result<T, S>
+    where
+        S: From<E>,
+    {
+        self.map_err(S::from)
+    }
+
+    #[inline(always)]
+    fn try_flatten<S>(self) -> Result<T, Result<S, E>>
+    where
+        E: TryFlattenInto<S>,
+    {
+        self.map_err(TryFlattenInto::try_flatten)
+    }
+
+    #[cfg(feature = "clap")]
+    fn into_clap(self, cmd: &clap::Command) -> Result<T, clap::Error>
+    where
+        E: Into<clap::Error>,
+    {
+        self.map_err(|err| err.into().format(&mut cmd.clone()))
+    }
+
+    #[inline(always)]
+    fn context<M>(self, msg: M) -> Result<T, WithContext<E, M>>
+    where
+        M: Display + Send + Sync + 'static,
+    {
+        self.map_err(|err| WithContext::new(err, msg))
+    }
+
+    #[inline(always)]
+    fn flatten_nested<S>(self) -> Result<<T as IntoResult>::Ok, S>
+    where
+        T: IntoResult,
+        <T as IntoResult>::Err: FlattenInto<S>,
+        E: FlattenInto<S>,
+    {
+        match self {
+            Ok(inner) => inner.into_result().map_err(FlattenInto::flatten),
+            Err(err) => Err(err.flatten()),
+        }
+    }
+
+    #[inline(always)]
+    fn flatten_option<S>(self, none: impl FnOnce() -> S) -> Result<<T as IntoOption>::Value, S>
+    where
+        T: IntoOption,
+        E: FlattenInto<S>,
+    {
+        match self {
+            Ok(value) => value.into_option().ok_or_else(none),
+            Err(err) => Err(err.flatten()),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn map_err_display(self) -> Result<T, alloc::string::String>
+    where
+        E: Display,
+    {
+        self.map_err(|err| alloc::string::ToString::to_string(&err))
+    }
+}
+
+/// Implemented by error types without any values, i.e the enums generated for `#[errors]`
+/// without any errors.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can contain errors",
+    label = "`{Self}` isnt uninhabited",
+    note = "Only the enums generated for `#[errors]` without any errors implement `Uninhabited`."
+)]
+pub trait Uninhabited {
+    /// This cant be called, as no value of this type exists.
+    fn unreachable(self) -> !;
+}
+
+impl Uninhabited for core::convert::Infallible {
+    #[inline(always)]
+    fn unreachable(self) -> ! {
+        match self {}
+    }
+}
+
+/// This trait extends `Result`s which cant be `Err`.
+pub trait InfallibleResultExt<T> {
+    /// Get the `Ok` value, without a panic path like `unwrap` has.
+    ///
+    /// This isnt called `into_ok` to not collide with the unstable `Result::into_ok`.
+    fn into_value(self) -> T;
+}
+
+impl<T, E: Uninhabited> InfallibleResultExt<T> for Result<T, E> {
+    #[inline(always)]
+    fn into_value(self) -> T {
+        match self {
+            Ok(value) => value,
+            Err(err) => err.unreachable(),
+        }
+    }
+}
+
+/// This trait extends iterators of `Result`s, for validating a batch of values.
+#[cfg(feature = "alloc")]
+pub trait ResultIteratorExt<T, E>: Iterator<Item = Result<T, E>> {
+    /// Collect all the values if every item is `Ok`, otherwise all the errors.
+    ///
+    /// Unlike collecting into a `Result` this doesnt stop at the first error.
+    fn collect_errors<C: FromIterator<T>>(self) -> Result<C, alloc::vec::Vec<E>>;
+
+    /// Collect all the values, keeping the errors next to them as non-fatal errors.
+    fn collect_partial<C: FromIterator<T>, S: From<E>>(self) -> Partial<C, S>;
+}
+
+#[cfg(feature = "alloc")]
+impl<I, T, E> ResultIteratorExt<T, E> for I
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    fn collect_errors<C: FromIterator<T>>(self) -> Result<C, alloc::vec::Vec<E>> {
+        let mut errors = alloc::vec::Vec::new();
+        let values = self
+            .filter_map(|item| match item {
+                Ok(value) => Some(value),
+                Err(err) => {
+                    errors.push(err);
+                    None
+                }
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(values)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn collect_partial<C: FromIterator<T>, S: From<E>>(self) -> Partial<C, S> {
+        let mut errors = alloc::vec::Vec::new();
+        let value = self
+            .filter_map(|item| match item {
+                Ok(value) => Some(value),
+                Err(err) => {
+                    errors.push(S::from(err));
+                    None
+                }
+            })
+            .collect();
+        Partial { value, errors }
+    }
 }
@@ -0,0 +1,67 @@
+//! Metadata attached to error types with [`ErrorMancerMeta`](crate::ErrorMancerMeta).
+//!
+//! The generated enums forward to payloads implementing [`ErrorMeta`], payloads without metadata
+//! use the defaults. Like the `Error` forwarding this uses autoref specialization, i.e
+//! `(&Transparent(err)).forward_http_status()`.
+
+use crate::__private::Transparent;
+
+/// Metadata of a error type, implemented with `#[ErrorMancerMeta(...)]`.
+///
+/// The enums generated by `#[errors]` implement this by forwarding to the contained error.
+pub trait ErrorMeta {
+    /// The HTTP status code to respond with.
+    fn http_status_for(&self) -> Option<u16> {
+        None
+    }
+
+    /// Whether the operation that caused this error can be retried.
+    fn is_retryable(&self) -> bool {
+        false
+    }
+}
+
+/// The HTTP status code of a error, implemented by the generated enums with the `http` feature.
+///
+/// The status is taken from `#[http_status = 503]` on the entry, then from the [`ErrorMeta`] of
+/// the contained error, falling back to `500` (or `#[errors(http_status = ..)]`).
+#[cfg(feature = "http")]
+pub trait HttpStatusCode {
+    fn http_status(&self) -> u16;
+}
+
+#[doc(hidden)]
+pub trait ForwardMeta {
+    fn forward_http_status(&self) -> Option<u16>;
+    fn forward_retryable(&self) -> bool;
+}
+
+impl<T: ErrorMeta> ForwardMeta for Transparent<'_, T> {
+    #[inline(always)]
+    fn forward_http_status(&self) -> Option<u16> {
+        self.0.http_status_for()
+    }
+
+    #[inline(always)]
+    fn forward_retryable(&self) -> bool {
+        self.0.is_retryable()
+    }
+}
+
+#[doc(hidden)]
+pub trait ForwardNoMeta {
+    fn forward_http_status(&self) -> Option<u16>;
+    fn forward_retryable(&self) -> bool;
+}
+
+impl<T> ForwardNoMeta for &Transparent<'_, T> {
+    #[inline(always)]
+    fn forward_http_status(&self) -> Option<u16> {
+        None
+    }
+
+    #[inline(always)]
+    fn forward_retryable(&self) -> bool {
+        false
+    }
+}
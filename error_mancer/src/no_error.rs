@@ -0,0 +1,26 @@
+use core::fmt::{self, Display};
+
+use crate::__private::Error;
+use crate::FlattenInto;
+
+/// A error that can never occur, used by `#[errors(none)]` so every function that cant fail has
+/// the same error type.
+///
+/// It flattens into any error with [`ResultExt::into_super_error`](crate::ResultExt), which
+/// compiles down to nothing as there is no value to convert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NoError {}
+
+impl Display for NoError {
+    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {}
+    }
+}
+
+impl Error for NoError {}
+
+impl<T> FlattenInto<T> for NoError {
+    fn flatten(self) -> T {
+        match self {}
+    }
+}
@@ -0,0 +1,95 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+
+use nom::error::ErrorKind;
+use nom::{Input, Needed};
+
+use crate::__private::Error;
+
+/// The payload of the `Nom` variant added by `#[errors(nom)]`, one of nom's parsers failed.
+///
+/// nom only passes the remaining input to the error, so the position is stored as the length
+/// left and [`input_offset`](Self::input_offset) turns it into a offset into the full input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NomError {
+    /// Length of the input left when the parser failed.
+    pub remaining: usize,
+    /// The parser that failed.
+    pub kind: ErrorKind,
+    /// The parsers and contexts the error passed through, innermost first. Only filled with
+    /// `#[errors(nom_trace)]`.
+    pub trace: Vec<NomFrame>,
+    /// Set when a streaming parser ran out of input, converted from `nom::Err::Incomplete`. The
+    /// kind is `ErrorKind::Complete` and nothing is left then.
+    pub needed: Option<Needed>,
+}
+
+/// A parser or `context` a [`NomError`] passed through on its way out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NomFrame {
+    /// A combinator passed the error on, from `ParseError::append`.
+    Kind {
+        /// Length of the input left at the combinator.
+        remaining: usize,
+        kind: ErrorKind,
+    },
+    /// A `nom::error::context` wrapped the parser, from `ContextError::add_context`.
+    Context {
+        /// Length of the input left at the context.
+        remaining: usize,
+        context: &'static str,
+    },
+}
+
+impl NomError {
+    /// A error from `kind` failing with `input` left.
+    pub fn new<I: Input>(input: &I, kind: ErrorKind) -> Self {
+        Self {
+            remaining: input.input_len(),
+            kind,
+            trace: Vec::new(),
+            needed: None,
+        }
+    }
+
+    /// A error from a streaming parser needing more input.
+    pub fn incomplete(needed: Needed) -> Self {
+        Self {
+            remaining: 0,
+            kind: ErrorKind::Complete,
+            trace: Vec::new(),
+            needed: Some(needed),
+        }
+    }
+
+    /// The offset into `input` the parser failed at, `input` has to be the full input the
+    /// parser was given.
+    pub fn input_offset<I: Input>(&self, input: &I) -> usize {
+        input.input_len().saturating_sub(self.remaining)
+    }
+
+    /// The contexts the error passed through, innermost first.
+    pub fn contexts(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.trace.iter().filter_map(|frame| match *frame {
+            NomFrame::Context { context, .. } => Some(context),
+            NomFrame::Kind { .. } => None,
+        })
+    }
+}
+
+impl Display for NomError {
+    /// The outermost context first, i.e `entry: value: Digit`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let contexts = self.contexts().collect::<Vec<_>>();
+        for context in contexts.iter().rev() {
+            write!(f, "{context}: ")?;
+        }
+        match self.needed {
+            Some(Needed::Size(size)) => write!(f, "{size} more bytes of input needed"),
+            Some(Needed::Unknown) => f.write_str("more input needed"),
+            None => f.write_str(self.kind.description()),
+        }
+    }
+}
+
+impl Error for NomError {}
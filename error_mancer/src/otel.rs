@@ -0,0 +1,13 @@
+//! Recording errors on the active OpenTelemetry span, used by the `opentelemetry` flag.
+
+use opentelemetry::trace::{get_active_span, Status};
+
+use crate::__private::Error;
+
+/// Record `err` as a exception event on the active span and set the span status.
+pub fn record_otel_error(err: &(dyn Error + 'static), status: Status) {
+    get_active_span(|span| {
+        span.record_error(err);
+        span.set_status(status);
+    });
+}
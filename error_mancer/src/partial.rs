@@ -0,0 +1,41 @@
+use alloc::vec::Vec;
+
+/// A value together with the non-fatal errors that occurred while producing it, such as the
+/// records that failed to parse during an import.
+///
+/// `#[errors]` replaces the `_` in `Result<Partial<T, _>, _>` with the generated enum as well, so
+/// fatal errors are returned with `?` and non-fatal ones are pushed with [`Partial::push_err`].
+#[derive(Debug, Clone, Default)]
+pub struct Partial<T, E> {
+    pub value: T,
+    pub errors: Vec<E>,
+}
+
+impl<T, E> Partial<T, E> {
+    /// A value without errors.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Record a non-fatal error.
+    pub fn push_err(&mut self, err: impl Into<E>) {
+        self.errors.push(err.into());
+    }
+
+    /// Whether no errors occurred.
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// The value if at most `threshold` errors occurred, otherwise all the errors.
+    pub fn into_result(self, threshold: usize) -> Result<T, Vec<E>> {
+        if self.errors.len() > threshold {
+            Err(self.errors)
+        } else {
+            Ok(self.value)
+        }
+    }
+}
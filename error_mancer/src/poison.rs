@@ -0,0 +1,25 @@
+use core::fmt::{self, Display};
+use std::sync::PoisonError;
+
+use crate::__private::Error;
+
+/// The payload of the variant added by `#[errors(poison)]`, a lock was poisoned because a thread
+/// panicked while holding it.
+///
+/// The guard in [`PoisonError`] borrows the lock, so it is dropped when converting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Poisoned;
+
+impl Display for Poisoned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("poisoned lock: another task failed inside")
+    }
+}
+
+impl Error for Poisoned {}
+
+impl<G> From<PoisonError<G>> for Poisoned {
+    fn from(_: PoisonError<G>) -> Self {
+        Self
+    }
+}
@@ -0,0 +1,25 @@
+#[cfg(feature = "tracing-error")]
+pub use tracing_error::SpanTrace;
+
+/// Stand-in for `tracing_error::SpanTrace` used when the `tracing-error` feature is disabled.
+///
+/// This is zero sized, so enums using `span_trace` dont pay for it.
+#[cfg(not(feature = "tracing-error"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SpanTrace;
+
+#[cfg(not(feature = "tracing-error"))]
+impl SpanTrace {
+    /// Does nothing, enable the `tracing-error` feature to capture span traces.
+    #[inline(always)]
+    pub fn capture() -> Self {
+        SpanTrace
+    }
+}
+
+#[cfg(not(feature = "tracing-error"))]
+impl core::fmt::Display for SpanTrace {
+    fn fmt(&self, _f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Ok(())
+    }
+}
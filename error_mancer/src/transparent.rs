@@ -0,0 +1,85 @@
+//! Forwarding of `Error` methods to the wrapped errors.
+//!
+//! Not every payload implements `Error` (i.e `anyhow::Error`), so the generated code uses
+//! autoref specialization to pick [`ForwardError`] when it does and [`ForwardNothing`]
+//! otherwise, i.e `(&Transparent(err)).forward_source()`.
+
+#[cfg(feature = "provide")]
+use core::error::Request;
+
+use crate::__private::Error;
+
+#[doc(hidden)]
+pub struct Transparent<'r, T>(pub &'r T);
+
+#[doc(hidden)]
+pub trait ForwardError<'r> {
+    /// The source of the wrapped error.
+    fn forward_source(&self) -> Option<&'r (dyn Error + 'static)>;
+    /// The wrapped error itself.
+    fn forward_as_source(&self) -> Option<&'r (dyn Error + 'static)>;
+    #[cfg(feature = "provide")]
+    fn forward_provide(&self, request: &mut Request<'r>);
+}
+
+impl<'r, T: Error + 'static> ForwardError<'r> for Transparent<'r, T> {
+    #[inline(always)]
+    fn forward_source(&self) -> Option<&'r (dyn Error + 'static)> {
+        self.0.source()
+    }
+
+    #[inline(always)]
+    fn forward_as_source(&self) -> Option<&'r (dyn Error + 'static)> {
+        Some(self.0)
+    }
+
+    #[cfg(feature = "provide")]
+    #[inline(always)]
+    fn forward_provide(&self, request: &mut Request<'r>) {
+        self.0.provide(request);
+    }
+}
+
+#[doc(hidden)]
+pub trait ForwardNothing<'r> {
+    fn forward_source(&self) -> Option<&'r (dyn Error + 'static)>;
+    fn forward_as_source(&self) -> Option<&'r (dyn Error + 'static)>;
+    #[cfg(feature = "provide")]
+    fn forward_provide(&self, request: &mut Request<'r>);
+}
+
+impl<'r, T> ForwardNothing<'r> for &Transparent<'r, T> {
+    #[inline(always)]
+    fn forward_source(&self) -> Option<&'r (dyn Error + 'static)> {
+        None
+    }
+
+    #[inline(always)]
+    fn forward_as_source(&self) -> Option<&'r (dyn Error + 'static)> {
+        None
+    }
+
+    #[cfg(feature = "provide")]
+    #[inline(always)]
+    fn forward_provide(&self, _request: &mut Request<'r>) {}
+}
+
+/// Generates the `Error::provide` method of a error enum, each arm evaluates to the error to
+/// forward to.
+#[cfg(feature = "provide")]
+#[doc(hidden)]
+#[macro_export]
+#[allow_internal_unstable(error_generic_member_access)]
+macro_rules! __provide {
+    ($($pattern:pat => $error:expr),* $(,)?) => {
+        fn provide<'a>(&'a self, request: &mut ::core::error::Request<'a>) {
+            #[allow(unused_imports)]
+            use $crate::__private::{ForwardError as _, ForwardNothing as _};
+            match self {
+                $($pattern => (&$crate::__private::Transparent($error)).forward_provide(request),)*
+                #[allow(unreachable_patterns)]
+                _ => {}
+            }
+        }
+    };
+}
@@ -0,0 +1,49 @@
+use core::fmt::{self, Display};
+
+use crate::__private::Error;
+use crate::FlattenInto;
+
+/// Either of two errors, for a one-off error type covering two functions without declaring a
+/// enum for it.
+///
+/// `?` converts the left error with `From`. A `From` implementation for the right error would
+/// overlap with it when both are the same type, so the right error has to be wrapped with
+/// `.map_err(Union::Right)`.
+#[derive(Debug)]
+pub enum Union<A, B> {
+    Left(A),
+    Right(B),
+}
+
+impl<A, B> From<A> for Union<A, B> {
+    fn from(value: A) -> Self {
+        Self::Left(value)
+    }
+}
+
+impl<A: Display, B: Display> Display for Union<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Left(err) => Display::fmt(err, f),
+            Self::Right(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl<A: Error, B: Error> Error for Union<A, B> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Left(err) => err.source(),
+            Self::Right(err) => err.source(),
+        }
+    }
+}
+
+impl<T, A: FlattenInto<T>, B: FlattenInto<T>> FlattenInto<T> for Union<A, B> {
+    fn flatten(self) -> T {
+        match self {
+            Self::Left(err) => err.flatten(),
+            Self::Right(err) => err.flatten(),
+        }
+    }
+}
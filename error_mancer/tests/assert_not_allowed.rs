@@ -0,0 +1,23 @@
+use core::num::{ParseIntError, TryFromIntError};
+
+use error_mancer::assert_not_allowed;
+use error_mancer::prelude::*;
+
+#[errors(ParseIntError)]
+fn foo() -> Result<i32, _> {
+    Ok("10".parse()?)
+}
+
+#[errors]
+fn bar() -> Result<(), _> {
+    Ok(())
+}
+
+assert_not_allowed!(FooError, TryFromIntError);
+assert_not_allowed!(BarError, ParseIntError, TryFromIntError, std::io::Error);
+
+#[test]
+fn still_callable() {
+    assert_eq!(foo().unwrap(), 10);
+    assert!(bar().is_ok());
+}
@@ -0,0 +1,62 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+pub trait Parse {
+    type Error;
+
+    fn parse(&self) -> Result<i32, Self::Error>;
+}
+
+pub trait Load {
+    type Error;
+    type Output;
+
+    fn load(&self) -> Result<Self::Output, Self::Error>;
+    fn name(&self) -> Result<&str, Self::Error>;
+}
+
+pub struct Number(&'static str);
+
+#[errors]
+impl Parse for Number {
+    #[errors(ParseIntError)]
+    fn parse(&self) -> Result<i32, Self::Error> {
+        Ok(self.0.parse()?)
+    }
+}
+
+#[errors]
+impl Load for Number {
+    type Output = i32;
+
+    #[errors(ParseIntError, std::fmt::Error)]
+    fn load(&self) -> Result<Self::Output, Self::Error> {
+        Ok(self.0.parse()?)
+    }
+
+    fn name(&self) -> Result<&str, Self::Error> {
+        Ok(self.0)
+    }
+}
+
+fn parse_generic<P: Parse>(value: &P) -> Result<i32, P::Error> {
+    value.parse()
+}
+
+#[test]
+fn sets_associated_type() {
+    assert_eq!(Number("1").parse().unwrap(), 1);
+    assert!(matches!(Number("x").parse(), Err(ParseError::ParseInt(_))));
+    assert!(matches!(
+        parse_generic(&Number("x")),
+        Err(ParseError::ParseInt(_))
+    ));
+}
+
+#[test]
+fn shared_by_other_methods() {
+    assert_eq!(Number("2").load().unwrap(), 2);
+    assert!(matches!(Number("x").load(), Err(LoadError::ParseInt(_))));
+    assert_eq!(Number("3").name().unwrap(), "3");
+}
@@ -14,3 +14,27 @@ async fn async_works() -> Result<i32, _> {
 async fn test_async() {
     assert_eq!(async_works().await, Ok(10));
 }
+
+struct Store {
+    values: Vec<String>,
+}
+
+#[errors]
+impl Store {
+    #[errors(std::num::ParseIntError)]
+    async fn key<'s, 'k>(&'s self, key: &'k str) -> Result<&'k str, _> {
+        self.values.iter().find(|value| *value == key);
+        key.parse::<i32>()?;
+        Ok(key)
+    }
+}
+
+#[tokio::test]
+async fn result_outlives_self() {
+    let key = String::from("42");
+    let result = {
+        let store = Store { values: Vec::new() };
+        store.key(&key).await
+    };
+    assert_eq!(result.unwrap(), "42");
+}
@@ -0,0 +1,23 @@
+use std::num::ParseIntError;
+
+use async_recursion::async_recursion;
+use error_mancer::prelude::*;
+
+// `#[errors]` has to come before `#[async_recursion]`, so it sees the `async fn`.
+#[errors(ParseIntError)]
+#[async_recursion]
+async fn sum(values: &[&str]) -> Result<i32, _> {
+    let Some((first, rest)) = values.split_first() else {
+        return Ok(0);
+    };
+    Ok(first.parse::<i32>()? + sum(rest).await?)
+}
+
+#[tokio::test]
+async fn recursion() {
+    assert_eq!(sum(&["1", "2", "3"]).await.unwrap(), 6);
+    assert!(matches!(
+        sum(&["1", "x", "3"]).await,
+        Err(SumError::ParseInt(_))
+    ));
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "axum")]
+
+use std::num::ParseIntError;
+
+use axum::body::to_bytes;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use error_mancer::prelude::*;
+
+#[errors(#[http_status = 400] ParseIntError, std::io::Error, json_error)]
+fn parse(x: &str) -> Result<i32, _> {
+    if x.is_empty() {
+        Err(std::io::Error::other("no input"))?;
+    }
+    Ok(x.parse()?)
+}
+
+async fn body(err: ParseJsonError) -> (StatusCode, String) {
+    let response = err.into_response();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn response() {
+    let (status, text) = body(ParseJsonError(parse("x").unwrap_err())).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        text,
+        r#"{"error":{"type":"ParseInt","message":"invalid digit found in string"}}"#
+    );
+
+    let (status, text) = body(ParseJsonError(parse("").unwrap_err())).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(text, r#"{"error":{"type":"StdIo","message":"no input"}}"#);
+}
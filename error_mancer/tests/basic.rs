@@ -1,7 +1,5 @@
-#![feature(assert_matches)]
 #![no_std]
 
-use core::assert_matches::assert_matches;
 use core::num::{ParseIntError, TryFromIntError};
 
 use error_mancer::prelude::*;
@@ -26,8 +24,8 @@ fn bar(x: &str) -> Result<u8, _> {
 #[test]
 fn specify_error_tests() {
     assert!(bar("10").is_ok());
-    assert_matches!(bar("abc"), Err(BarError::ParseInt(_)));
-    assert_matches!(bar("300"), Err(BarError::TryFromInt(_)));
+    assert!(matches!(bar("abc"), Err(BarError::ParseInt(_))));
+    assert!(matches!(bar("300"), Err(BarError::TryFromInt(_))));
 }
 
 mod module {
@@ -42,10 +40,10 @@ mod module {
 
 #[test]
 fn pub_works() {
-    assert_matches!(
+    assert!(matches!(
         module::in_module(),
         Err(module::InModuleError::TryFromInt(_))
-    );
+    ));
 }
 
 struct Test;
@@ -58,3 +56,28 @@ impl Test {
         Ok(x)
     }
 }
+
+#[test]
+fn impl_block() {
+    assert_eq!(Test.method(10).unwrap(), 10);
+    assert!(matches!(Test.method(300), Err(MethodError::TryFromInt(_))));
+}
+
+#[errors(TryFromIntError)]
+fn nested_result(x: &str) -> Result<Result<u8, ParseIntError>, _> {
+    let x: i16 = match x.parse() {
+        Ok(x) => x,
+        Err(err) => return Ok(Err(err)),
+    };
+    Ok(Ok(x.try_into()?))
+}
+
+#[test]
+fn nested_result_ok_type() {
+    assert!(matches!(nested_result("10"), Ok(Ok(10))));
+    assert!(matches!(nested_result("abc"), Ok(Err(_))));
+    assert!(matches!(
+        nested_result("300"),
+        Err(NestedResultError::TryFromInt(_))
+    ));
+}
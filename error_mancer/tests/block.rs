@@ -0,0 +1,61 @@
+use core::num::{ParseIntError, TryFromIntError};
+
+use async_stream::try_stream;
+use error_mancer::prelude::*;
+use futures::{Stream, StreamExt};
+
+#[errors(ParseIntError, TryFromIntError)]
+fn parse_all(values: &[&str]) -> Result<Vec<u8>, _> {
+    let mut result = Vec::new();
+    for value in values {
+        let value = errors_block!(ParseIntError => {
+            let value: i32 = value.parse()?;
+            Ok(value)
+        })
+        .into_super_error::<ParseAllError>()?;
+        result.push(value.try_into()?);
+    }
+    Ok(result)
+}
+
+#[test]
+fn sync_block() {
+    assert_eq!(parse_all(&["1", "2"]).unwrap(), vec![1, 2]);
+    assert!(matches!(
+        parse_all(&["abc"]),
+        Err(ParseAllError::ParseInt(_))
+    ));
+    assert!(matches!(
+        parse_all(&["300"]),
+        Err(ParseAllError::TryFromInt(_))
+    ));
+}
+
+// Only used to declare `StreamError` for the stream items.
+#[allow(dead_code)]
+#[errors(ParseIntError)]
+fn declare_stream_error() -> Result<(), StreamError> {
+    Ok(())
+}
+
+fn numbers(values: &'static [&'static str]) -> impl Stream<Item = Result<i32, StreamError>> {
+    try_stream! {
+        for value in values {
+            let value = errors_block!(async ParseIntError => {
+                tokio::task::yield_now().await;
+                Ok(value.parse::<i32>()?)
+            })
+            .into_super_error::<StreamError>()?;
+            yield value;
+        }
+    }
+}
+
+#[tokio::test]
+async fn inside_try_stream() {
+    let values = numbers(&["1", "2"]).collect::<Vec<_>>().await;
+    assert!(matches!(values[..], [Ok(1), Ok(2)]));
+
+    let values = numbers(&["1", "abc", "3"]).collect::<Vec<_>>().await;
+    assert!(matches!(values[..], [Ok(1), Err(StreamError::ParseInt(_))]));
+}
@@ -0,0 +1,79 @@
+use std::mem::size_of;
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+/// Big enough to widen every `Result` holding it.
+#[derive(Debug, thiserror::Error)]
+#[error("request failed with {}", .0.len())]
+struct Large([u8; 256]);
+
+#[errors(boxed_self; Large, ParseIntError, try_flatten)]
+fn parse(value: &str) -> Result<u32, _> {
+    if value.is_empty() {
+        Err(Large([0; 256]))?;
+    }
+    Ok(value.parse()?)
+}
+
+#[errors(Large, ParseIntError)]
+#[allow(clippy::result_large_err)]
+fn parse_unboxed(value: &str) -> Result<u32, _> {
+    Ok(value.parse()?)
+}
+
+#[errors(boxed_self; ParseIntError, try_flatten)]
+fn named(value: &str) -> Result<u32, Box<NamedFailure>> {
+    Ok(value.parse()?)
+}
+
+#[errors(boxed_self; Large, ParseIntError, std::io::Error)]
+fn load(value: &str) -> Result<u32, _> {
+    Ok(parse(value).into_super_error::<Box<LoadError>>()?)
+}
+
+#[errors(Large, ParseIntError)]
+#[allow(clippy::result_large_err)]
+fn load_unboxed(value: &str) -> Result<u32, _> {
+    Ok(parse(value).into_super_error::<LoadUnboxedError>()?)
+}
+
+#[test]
+fn returns_the_box() {
+    assert_eq!(parse("7").unwrap(), 7);
+    let err: Box<ParseError> = parse("x").unwrap_err();
+    assert!(matches!(*err, ParseError::ParseInt(_)));
+    assert!(matches!(*parse("").unwrap_err(), ParseError::Large(_)));
+    assert!(matches!(
+        *named("x").unwrap_err(),
+        NamedFailure::ParseInt(_)
+    ));
+}
+
+#[test]
+fn result_shrinks() {
+    assert!(size_of::<Result<u32, ParseUnboxedError>>() > 256);
+    assert_eq!(size_of::<Result<(), Box<ParseError>>>(), size_of::<usize>());
+    assert_eq!(
+        size_of::<Result<u32, Box<ParseError>>>(),
+        2 * size_of::<usize>()
+    );
+}
+
+#[test]
+fn flattens_from_the_box() {
+    assert!(matches!(*load("x").unwrap_err(), LoadError::ParseInt(_)));
+    assert!(matches!(load_unboxed(""), Err(LoadUnboxedError::Large(_))));
+}
+
+#[test]
+fn try_flatten_keeps_the_box() {
+    assert!(matches!(
+        parse("x").try_flatten::<NamedFailure>(),
+        Err(Ok(NamedFailure::ParseInt(_)))
+    ));
+    let Err(Err(err)) = parse("").try_flatten::<NamedFailure>() else {
+        panic!("`Large` cant be represented by `NamedFailure`");
+    };
+    assert!(matches!(*err, ParseError::Large(_)));
+}
@@ -0,0 +1,124 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[cfg_attr(all(), errors(ParseIntError))]
+fn enabled(x: &str) -> Result<i32, EnabledError> {
+    #[cfg(any())]
+    let x = "unused";
+    Ok(x.parse()?)
+}
+
+#[cfg_attr(any(), errors(ParseIntError))]
+fn disabled(x: &str) -> Result<i32, std::io::Error> {
+    x.parse::<i32>().map_err(std::io::Error::other)
+}
+
+#[cfg_attr(all(), errors(ParseIntError))]
+fn enabled_passthrough(x: &str) -> anyhow::Result<i32> {
+    Ok(x.parse()?)
+}
+
+#[cfg_attr(any(), errors(ParseIntError))]
+fn disabled_passthrough(x: &str) -> anyhow::Result<i32> {
+    std::fs::metadata(x)?;
+    Ok(x.parse()?)
+}
+
+#[cfg_attr(all(), errors(ParseIntError, #[cfg(any())] std::io::Error))]
+fn cfg_parameters(#[cfg(any())] _unused: i32, x: &str) -> Result<i32, _> {
+    #[cfg(test)]
+    let x = x.trim();
+    Ok(x.parse()?)
+}
+
+#[cfg_attr(all(), errors)]
+impl Parser {
+    #[cfg_attr(all(), errors(ParseIntError))]
+    fn parse(&self) -> Result<i32, _> {
+        Ok(self.0.parse()?)
+    }
+
+    #[cfg_attr(any(), errors(ParseIntError))]
+    fn parse_or_zero(&self) -> Result<i32, ParseIntError> {
+        Ok(self.0.parse().unwrap_or(0))
+    }
+
+    #[cfg_attr(all(), errors(ParseIntError), derive(Clone))]
+    fn parse_cloned(&self) -> Result<i32, _> {
+        Ok(self.0.parse()?)
+    }
+
+    #[cfg_attr(any(), errors(OnlyWhenEnabled), derive(Clone))]
+    fn missing_type(&self) -> Result<i32, ParseIntError> {
+        self.0.parse()
+    }
+}
+
+#[errors(OnlyWhenEnabled)]
+#[cfg(any())]
+fn gated() -> Result<(), _> {
+    Err(OnlyWhenEnabled)?
+}
+
+#[errors(OnlyWhenEnabled)]
+#[cfg_attr(all(), cfg(any()))]
+fn gated_by_cfg_attr() -> Result<(), _> {
+    Err(OnlyWhenEnabled)?
+}
+
+#[errors(FeatureOnly)]
+#[cfg(feature = "http")]
+fn feature_gated() -> Result<(), _> {
+    Err(std::fmt::Error)?
+}
+
+#[errors]
+impl Parser {
+    #[errors(OnlyWhenEnabled)]
+    #[cfg(any())]
+    fn gated_method(&self) -> Result<(), _> {
+        Err(OnlyWhenEnabled)?
+    }
+}
+
+#[cfg(any())]
+struct OnlyWhenEnabled;
+
+#[cfg(feature = "http")]
+type FeatureOnly = std::fmt::Error;
+
+struct Parser(&'static str);
+
+#[test]
+fn enabled_attribute() {
+    assert_eq!(enabled("1").unwrap(), 1);
+    assert!(matches!(enabled("x"), Err(EnabledError::ParseInt(_))));
+    assert_eq!(enabled_passthrough("2").unwrap(), 2);
+    assert!(matches!(cfg_parameters(" 3 "), Ok(3)));
+    assert!(matches!(
+        cfg_parameters("x"),
+        Err(CfgParametersError::ParseInt(_))
+    ));
+    assert!(matches!(Parser("x").parse(), Err(ParseError::ParseInt(_))));
+    let err = Parser("x").parse_cloned().unwrap_err();
+    assert!(matches!(err.clone(), ParseClonedError::ParseInt(_)));
+}
+
+#[test]
+fn disabled_attribute() {
+    assert_eq!(disabled("1").unwrap(), 1);
+    assert!(disabled("x").is_err());
+    assert!(disabled_passthrough("/this/file/does/not/exist").is_err());
+    assert_eq!(Parser("x").parse_or_zero().unwrap(), 0);
+    assert_eq!(Parser("4").missing_type().unwrap(), 4);
+}
+
+#[test]
+#[cfg(feature = "http")]
+fn feature_enabled() {
+    assert!(matches!(
+        feature_gated(),
+        Err(FeatureGatedError::FeatureOnly(std::fmt::Error))
+    ));
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "clap")]
+
+use std::ffi::OsStr;
+use std::num::ParseIntError;
+
+use clap::builder::TypedValueParser;
+use clap::error::ErrorKind;
+use clap::{Arg, Command};
+use error_mancer::prelude::*;
+
+#[derive(Debug)]
+struct Reserved;
+
+impl std::fmt::Display for Reserved {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("port is reserved")
+    }
+}
+
+#[errors(ParseIntError, #[clap_kind = InvalidValue] Reserved)]
+fn parse_port(value: &str) -> Result<u16, _> {
+    let port = value.parse()?;
+    if port < 1024 {
+        Err(Reserved)?;
+    }
+    Ok(port)
+}
+
+#[derive(Clone)]
+struct PortParser;
+
+impl TypedValueParser for PortParser {
+    type Value = u16;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        _arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<u16, clap::Error> {
+        parse_port(&value.to_string_lossy()).into_clap(cmd)
+    }
+}
+
+fn command() -> Command {
+    Command::new("server").arg(Arg::new("port").long("port").value_parser(PortParser))
+}
+
+#[test]
+fn valid_value() {
+    let matches = command()
+        .try_get_matches_from(["server", "--port", "8080"])
+        .unwrap();
+    assert_eq!(matches.get_one::<u16>("port"), Some(&8080));
+}
+
+#[test]
+fn default_kind() {
+    let err = command()
+        .try_get_matches_from(["server", "--port", "x"])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ValueValidation);
+    assert!(err.to_string().contains("invalid digit found in string"));
+}
+
+#[test]
+fn explicit_kind() {
+    let err = command()
+        .try_get_matches_from(["server", "--port", "80"])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidValue);
+    let message = err.to_string();
+    assert!(message.contains("port is reserved"));
+    assert!(message.contains("Usage: server"));
+}
+
+#[test]
+fn from_error() {
+    let err = clap::Error::from(parse_port("x").unwrap_err());
+    assert_eq!(err.kind(), ErrorKind::ValueValidation);
+}
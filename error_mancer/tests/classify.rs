@@ -0,0 +1,89 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::num::{ParseIntError, TryFromIntError};
+
+use error_mancer::prelude::*;
+
+#[derive(Debug)]
+struct Timeout;
+
+impl Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("timed out")
+    }
+}
+
+impl Error for Timeout {}
+
+#[derive(Debug)]
+struct Unlisted;
+
+impl Display for Unlisted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unlisted")
+    }
+}
+
+impl Error for Unlisted {}
+
+#[errors(
+    std::io::Error,
+    Timeout as Slow,
+    group Number { ParseIntError, TryFromIntError },
+    &'static str as Message,
+    classify,
+)]
+fn fetch(input: &str) -> Result<u8, _> {
+    if input.is_empty() {
+        Err("empty")?;
+    }
+    Ok(u8::try_from(input.parse::<i32>()?)?)
+}
+
+#[errors(ParseIntError, allow_extra = Other, classify)]
+fn with_extra(input: &str) -> Result<i32, _> {
+    Ok(input.parse()?)
+}
+
+fn classify(err: &(dyn Error + 'static)) -> Option<&'static str> {
+    FetchError::classify(err)
+}
+
+#[test]
+fn classifies_listed_errors() {
+    assert_eq!(classify(&std::io::Error::other("disk")), Some("StdIo"));
+    assert_eq!(classify(&Timeout), Some("Slow"));
+    assert_eq!(classify(&Unlisted), None);
+}
+
+#[test]
+fn classifies_grouped_errors() {
+    let err = "x".parse::<i32>().unwrap_err();
+    assert_eq!(classify(&err), Some("Number"));
+    let err = u8::try_from(300).unwrap_err();
+    assert_eq!(classify(&err), Some("Number"));
+
+    let inner = FetchNumberError::from(err);
+    assert_eq!(classify(&inner), Some("Number"));
+    assert_eq!(FetchNumberError::classify(&inner), Some("TryFromInt"));
+}
+
+#[test]
+fn classifies_the_enum_itself() {
+    assert_eq!(classify(&fetch("").unwrap_err()), Some("Message"));
+    assert_eq!(classify(&fetch("x").unwrap_err()), Some("Number"));
+
+    let boxed: Box<dyn Error + Send + Sync> = fetch("999").unwrap_err().into();
+    assert_eq!(classify(&*boxed), Some("Number"));
+}
+
+#[test]
+fn extra_variant_is_the_fallback() {
+    assert_eq!(WithExtraError::classify(&Unlisted), Some("Other"));
+    let err = "x".parse::<i32>().unwrap_err();
+    assert_eq!(WithExtraError::classify(&err), Some("ParseInt"));
+    assert_eq!(
+        WithExtraError::classify(&with_extra("x").unwrap_err()),
+        Some("ParseInt")
+    );
+}
@@ -0,0 +1,51 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[errors(ParseIntError, std::io::Error)]
+fn parse_all(values: &[&str]) -> Result<Vec<i32>, _> {
+    let parsed = values
+        .iter()
+        .map(errors_closure!(
+            #[errors(ParseIntError)]
+            |value: &&str| -> Result<i32, _> { Ok(value.parse()?) }
+        ))
+        .collect::<Result<Vec<_>, _>>()
+        .into_super_error::<ParseAllError>()?;
+    Ok(parsed)
+}
+
+#[test]
+fn closure_in_map() {
+    assert_eq!(parse_all(&["1", "2"]).unwrap(), [1, 2]);
+    assert!(matches!(
+        parse_all(&["1", "x"]),
+        Err(ParseAllError::ParseInt(_))
+    ));
+}
+
+#[test]
+fn inferred_and_named() {
+    let offset = 10;
+    let add = errors_closure!(
+        #[errors(ParseIntError)]
+        move |value: &str| Ok(value.parse::<i32>()? + offset)
+    );
+    assert_eq!(add("1").unwrap(), 11);
+
+    let check = errors_closure!(
+        #[errors(ParseIntError, std::io::Error)]
+        |value: &str| -> Result<u8, CheckError> {
+            if value.is_empty() {
+                Err(std::io::Error::other("empty"))?;
+            }
+            Ok(value.parse()?)
+        }
+    );
+    assert_eq!(check("3").unwrap(), 3);
+    assert!(check("").is_err());
+    assert!(check("x")
+        .unwrap_err()
+        .to_string()
+        .contains("invalid digit"));
+}
@@ -0,0 +1,49 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[errors(ParseIntError, std::io::Error)]
+fn parse_all(values: &[&str]) -> Result<Vec<i32>, _> {
+    let parsed = values
+        .iter()
+        .map(|value| value.parse::<i32>())
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(parsed)
+}
+
+#[errors(ParseIntError)]
+fn sum(values: &[&str]) -> Result<i32, _> {
+    let values: Vec<i32> = values
+        .iter()
+        .map(|value| value.parse())
+        .collect::<Result<_, _>>()?;
+    Ok(values.iter().sum())
+}
+
+// A closure using `?` itself has to name its error type, the generated enum works.
+#[errors(ParseIntError, std::io::Error)]
+fn doubled(values: &[&str]) -> Result<Vec<i32>, _> {
+    let doubled = values
+        .iter()
+        .map(|value| -> Result<_, DoubledError> {
+            if value.is_empty() {
+                Err(std::io::Error::other("empty"))?;
+            }
+            Ok(value.parse::<i32>()? * 2)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(doubled)
+}
+
+#[test]
+fn collect_converts_error() {
+    assert_eq!(parse_all(&["1", "2"]).unwrap(), [1, 2]);
+    assert!(matches!(
+        parse_all(&["1", "x"]),
+        Err(ParseAllError::ParseInt(_))
+    ));
+    assert_eq!(sum(&["1", "2"]).unwrap(), 3);
+    assert!(sum(&["x"]).is_err());
+    assert_eq!(doubled(&["1", "2"]).unwrap(), [2, 4]);
+    assert!(matches!(doubled(&[""]), Err(DoubledError::StdIo(_))));
+}
@@ -0,0 +1,55 @@
+use std::collections::HashSet;
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[derive(Debug)]
+struct Negative(i32);
+
+impl std::fmt::Display for Negative {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is negative", self.0)
+    }
+}
+
+#[errors(ParseIntError, Negative)]
+fn validate(value: &str) -> Result<i32, _> {
+    let value = value.parse()?;
+    if value < 0 {
+        Err(Negative(value))?;
+    }
+    Ok(value)
+}
+
+#[test]
+fn all_ok() {
+    let values: Vec<i32> = ["1", "2", "3"]
+        .into_iter()
+        .map(validate)
+        .collect_errors()
+        .unwrap();
+    assert_eq!(values, [1, 2, 3]);
+
+    let values: HashSet<i32> = ["1", "1"]
+        .into_iter()
+        .map(validate)
+        .collect_errors()
+        .unwrap();
+    assert_eq!(values, HashSet::from([1]));
+}
+
+#[test]
+fn all_errors() {
+    let errors = ["1", "x", "-2", "3"]
+        .into_iter()
+        .map(validate)
+        .collect_errors::<Vec<_>>()
+        .unwrap_err();
+    assert!(matches!(
+        errors.as_slice(),
+        [
+            ValidateError::ParseInt(_),
+            ValidateError::Negative(Negative(-2))
+        ]
+    ));
+}
@@ -0,0 +1,29 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[derive(Debug, PartialEq)]
+struct Port(u16);
+
+#[errors]
+impl Port {
+    #[errors(ParseIntError)]
+    fn new(value: &str) -> Result<Self, _> {
+        Ok(Self(value.parse()?))
+    }
+
+    #[errors(ParseIntError)]
+    fn many(values: &[&str]) -> Result<Vec<Self>, _> {
+        values
+            .iter()
+            .map(|value| Self::new(value).into_super_error::<ManyError>())
+            .collect()
+    }
+}
+
+#[test]
+fn fallible_constructor() {
+    assert_eq!(Port::new("80").unwrap(), Port(80));
+    assert!(matches!(Port::new("x"), Err(NewError::ParseInt(_))));
+    assert_eq!(Port::many(&["1", "2"]).unwrap(), [Port(1), Port(2)]);
+}
@@ -0,0 +1,44 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[derive(Debug, thiserror::Error)]
+#[error("type error")]
+struct Type;
+
+#[errors(std::io::Error, ParseIntError, Type, group Text { std::fmt::Error }, allow_extra = Other)]
+fn foo() -> Result<(), _> {
+    Ok(())
+}
+
+#[errors(ParseIntError, span_trace)]
+fn traced() -> Result<(), _> {
+    Ok(())
+}
+
+#[test]
+fn construct_variants() {
+    assert!(foo().is_ok());
+    assert!(matches!(
+        FooError::std_io(std::io::Error::other("disk")),
+        FooError::StdIo(_)
+    ));
+    let err = "x".parse::<i32>().unwrap_err();
+    assert!(matches!(FooError::parse_int(err), FooError::ParseInt(_)));
+    assert!(matches!(FooError::r#type(Type), FooError::Type(_)));
+    assert!(matches!(
+        FooError::text(FooTextError::std_fmt(std::fmt::Error)),
+        FooError::Text(FooTextError::StdFmt(_))
+    ));
+    assert!(matches!(
+        FooError::other("boxed".into()),
+        FooError::Other(_)
+    ));
+}
+
+#[test]
+fn captures_span_trace() {
+    assert!(traced().is_ok());
+    let err = TracedError::parse_int("x".parse::<i32>().unwrap_err());
+    let _: &error_mancer::SpanTrace = err.span_trace();
+}
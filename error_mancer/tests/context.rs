@@ -0,0 +1,41 @@
+use std::error::Error;
+
+use error_mancer::prelude::*;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("err1")]
+struct Err1;
+
+#[errors(Err1)]
+fn foo() -> Result<(), _> {
+    Err(Err1.into())
+}
+
+#[test]
+fn display() {
+    let err = foo().context("while calling foo").unwrap_err();
+    assert_eq!(err.to_string(), "while calling foo: err1");
+}
+
+#[test]
+fn keeps_inner() {
+    let err = foo().context("while calling foo").unwrap_err();
+    assert!(matches!(err.inner(), FooError::Err1(Err1)));
+    assert!(matches!(err.into_inner(), FooError::Err1(Err1)));
+}
+
+#[test]
+fn source() {
+    let err = foo().context("while calling foo").unwrap_err();
+    let source = err.source().unwrap();
+    assert_eq!(source.to_string(), "err1");
+}
+
+#[test]
+fn alongside_anyhow_context() {
+    let err = ResultExt::context(foo(), "while calling foo").unwrap_err();
+    assert_eq!(err.to_string(), "while calling foo: err1");
+    let err = anyhow::Context::context(foo(), "while calling foo").unwrap_err();
+    assert_eq!(err.to_string(), "while calling foo");
+}
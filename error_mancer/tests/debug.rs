@@ -0,0 +1,101 @@
+use std::fmt;
+
+use error_mancer::prelude::*;
+
+/// Deliberately doesnt implement `Debug`.
+struct Handle(u32);
+
+impl fmt::Display for Handle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid handle {}", self.0)
+    }
+}
+
+fn open(handle: u32) -> Result<(), Handle> {
+    Err(Handle(handle))
+}
+
+#[errors(Handle no_debug, std::num::ParseIntError)]
+fn foo(x: &str) -> Result<i32, _> {
+    if x.is_empty() {
+        open(3)?;
+    }
+    Ok(x.parse()?)
+}
+
+#[errors(Handle, no_debug)]
+fn bar() -> Result<(), _> {
+    open(5)?;
+    Ok(())
+}
+
+#[errors((Handle no_debug, std::fmt::Error) as Inner)]
+fn grouped() -> Result<(), _> {
+    open(7)?;
+    Ok(())
+}
+
+#[test]
+fn redacted_entry() {
+    let err = foo("").unwrap_err();
+    assert_eq!(format!("{err:?}"), "Handle(<redacted>)");
+    assert_eq!(err.to_string(), "invalid handle 3");
+}
+
+#[test]
+fn other_entries_are_debugged() {
+    let err = foo("x").unwrap_err();
+    let parse = "x".parse::<i32>().unwrap_err();
+    assert_eq!(format!("{err:?}"), format!("StdNumParseInt({parse:?})"));
+    assert_eq!(
+        format!("{err:#?}"),
+        "StdNumParseInt(\n    ParseIntError {\n        kind: InvalidDigit,\n    },\n)"
+    );
+}
+
+#[test]
+fn redacted_enum() {
+    assert_eq!(format!("{:?}", bar().unwrap_err()), "Handle(<redacted>)");
+}
+
+#[test]
+fn redacted_in_group() {
+    assert_eq!(
+        format!("{:?}", grouped().unwrap_err()),
+        "Inner(Handle(<redacted>))"
+    );
+}
+
+#[errors(std::num::ParseIntError, custom_debug)]
+fn manual(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+impl fmt::Debug for ManualError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ManualError: {self}")
+    }
+}
+
+#[errors(std::num::ParseIntError)]
+#[derive(Debug)]
+fn derived(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+#[test]
+fn manual_debug_impl() {
+    assert_eq!(
+        format!("{:?}", manual("x").unwrap_err()),
+        "ManualError: invalid digit found in string"
+    );
+}
+
+#[test]
+fn derived_debug() {
+    let parse = "x".parse::<i32>().unwrap_err();
+    assert_eq!(
+        format!("{:?}", derived("x").unwrap_err()),
+        format!("StdNumParseInt({parse:?})")
+    );
+}
@@ -0,0 +1,71 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[derive(Debug)]
+struct DbError;
+
+impl std::fmt::Display for DbError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("connection reset by 10.0.0.5")
+    }
+}
+
+impl std::error::Error for DbError {}
+
+fn query(fail: bool) -> Result<i32, DbError> {
+    if fail {
+        Err(DbError)
+    } else {
+        Ok(1)
+    }
+}
+
+#[errors(deny(DbError))]
+fn load(x: &str, fail: bool) -> anyhow::Result<i32> {
+    let value: i32 = x.parse()?;
+    let values = ["1", "2"]
+        .iter()
+        .map(|value| value.parse::<i32>())
+        .collect::<Result<Vec<_>, _>>()?;
+    let small: u8 = value.try_into()?;
+    let row = query(fail).map_err(|_| anyhow::anyhow!("query failed"))?;
+    Ok(i32::from(small) + values.iter().sum::<i32>() + row)
+}
+
+#[errors(deny(DbError, std::fmt::Error))]
+async fn load_async(x: &str) -> anyhow::Result<i32> {
+    let nested = async { Ok::<_, DbError>(1) };
+    let closure = || -> Result<i32, DbError> { query(true) };
+    assert!(closure().is_err());
+    Ok(x.parse::<i32>()? + nested.await.unwrap())
+}
+
+#[errors(deny(DbError))]
+fn boxed(x: &str) -> Result<i32, Box<dyn std::error::Error>> {
+    if x.is_empty() {
+        return Err("empty".into());
+    }
+    let value = x.parse::<i32>()?;
+    Ok(value)
+}
+
+#[test]
+fn other_errors_pass_through() {
+    assert_eq!(load("3", false).unwrap(), 7);
+    let err = load("x", false).unwrap_err();
+    assert!(err.downcast_ref::<ParseIntError>().is_some());
+    let err = load("300", false).unwrap_err();
+    assert!(err.downcast_ref::<std::num::TryFromIntError>().is_some());
+    assert_eq!(load("3", true).unwrap_err().to_string(), "query failed");
+
+    assert_eq!(boxed("2").unwrap(), 2);
+    assert_eq!(boxed("").unwrap_err().to_string(), "empty");
+    assert!(boxed("x").unwrap_err().is::<ParseIntError>());
+}
+
+#[tokio::test]
+async fn async_functions() {
+    assert_eq!(load_async("1").await.unwrap(), 2);
+    assert!(load_async("x").await.is_err());
+}
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use error_mancer::errors;
 use thiserror::Error;
 
@@ -25,3 +27,20 @@ impl Bar {
         Ok(())
     }
 }
+
+// The enum without variants is uninhabited, so deriving `Copy` needs nothing from the payloads.
+#[errors]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+fn cant_fail() -> Result<i32, _> {
+    Ok(1)
+}
+
+#[test]
+fn empty_enum_derives() {
+    fn assert_copy<T: Copy + Eq + core::hash::Hash>(_: &T) {}
+
+    let result = cant_fail();
+    assert_copy(&result);
+    let copied = result;
+    assert_eq!(result, copied);
+}
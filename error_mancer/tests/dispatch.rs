@@ -0,0 +1,50 @@
+use std::error::Error;
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+use error_mancer::VariantDispatch;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("wrapped")]
+struct Wrapped(#[source] std::fmt::Error);
+
+#[errors(ParseIntError, Wrapped, group Io { std::io::Error }, dispatch)]
+fn foo(x: i32) -> Result<i32, _> {
+    match x {
+        0 => Ok("abc".parse()?),
+        1 => Err(Wrapped(std::fmt::Error))?,
+        _ => Err(std::io::Error::other("disk"))?,
+    }
+}
+
+#[errors(dispatch)]
+fn empty() -> Result<(), _> {
+    Ok(())
+}
+
+#[test]
+fn displays_inner_error() {
+    assert_eq!(
+        foo(0).unwrap_err().to_string(),
+        "invalid digit found in string"
+    );
+    assert_eq!(foo(1).unwrap_err().to_string(), "wrapped");
+    assert_eq!(foo(2).unwrap_err().to_string(), "disk");
+}
+
+#[test]
+fn source_is_transparent() {
+    let err = foo(1).unwrap_err();
+    assert!(err.source().unwrap().is::<std::fmt::Error>());
+    assert!(foo(0).unwrap_err().source().is_none());
+}
+
+#[test]
+fn inner_error() {
+    let err = foo(0).unwrap_err();
+    assert!(err.inner().is::<ParseIntError>());
+    let err = foo(2).unwrap_err();
+    assert!(err.inner().is::<FooIoError>());
+    assert!(empty().is_ok());
+}
@@ -0,0 +1,154 @@
+use std::fmt;
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+/// Displays differently with `{:#}`, and also respects width and fill.
+#[derive(Debug)]
+struct Fancy;
+
+impl fmt::Display for Fancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.pad("fancy (alternate)")
+        } else {
+            f.pad("fancy")
+        }
+    }
+}
+
+impl std::error::Error for Fancy {}
+
+#[errors(Fancy, ParseIntError, std::io::Error)]
+fn foo(kind: u8) -> Result<(), _> {
+    match kind {
+        0 => Err(Fancy)?,
+        1 => Err("abc".parse::<u8>().unwrap_err())?,
+        _ => Err(std::io::Error::other("io failed"))?,
+    }
+    Ok(())
+}
+
+#[errors((Fancy, ParseIntError) as Inner)]
+fn grouped() -> Result<(), _> {
+    Err(Fancy)?;
+    Ok(())
+}
+
+#[errors(Fancy, span_trace)]
+fn traced() -> Result<(), _> {
+    Err(Fancy)?;
+    Ok(())
+}
+
+#[errors(anyhow::Error)]
+fn with_anyhow() -> Result<(), _> {
+    Err(anyhow::Error::new(Fancy).context("outer"))?;
+    Ok(())
+}
+
+#[test]
+fn alternate_is_forwarded() {
+    assert_eq!(format!("{:#}", foo(0).unwrap_err()), format!("{:#}", Fancy));
+    assert_eq!(
+        format!("{:#}", grouped().unwrap_err()),
+        format!("{:#}", Fancy)
+    );
+    assert_eq!(
+        format!("{:#}", traced().unwrap_err()),
+        format!("{:#}", Fancy)
+    );
+}
+
+#[test]
+fn common_inner_types() {
+    let parse = "abc".parse::<u8>().unwrap_err();
+    assert_eq!(format!("{:#}", foo(1).unwrap_err()), format!("{parse:#}"));
+
+    let io = std::io::Error::other("io failed");
+    assert_eq!(format!("{:#}", foo(2).unwrap_err()), format!("{io:#}"));
+
+    let anyhow = anyhow::Error::new(Fancy).context("outer");
+    assert_eq!(
+        format!("{:#}", with_anyhow().unwrap_err()),
+        format!("{anyhow:#}")
+    );
+    assert_eq!(
+        format!("{}", with_anyhow().unwrap_err()),
+        format!("{anyhow}")
+    );
+}
+
+#[test]
+fn padding_is_forwarded() {
+    assert_eq!(format!("{:>8}", foo(0).unwrap_err()), "   fancy");
+    assert_eq!(format!("{:-<8}", grouped().unwrap_err()), "fancy---");
+}
+
+#[errors(ParseIntError, (Fancy, std::io::Error) as Other, display_context = "function: parse")]
+fn parse_with_context(x: &str) -> Result<i32, _> {
+    if x.is_empty() {
+        Err(Fancy)?;
+    }
+    Ok(x.parse()?)
+}
+
+#[test]
+fn display_context() {
+    assert_eq!(
+        parse_with_context("x").unwrap_err().to_string(),
+        "function: parse: invalid digit found in string"
+    );
+    let err = parse_with_context("").unwrap_err();
+    assert_eq!(err.to_string(), "function: parse: fancy");
+    let ParseWithContextError::Other(inner) = err else {
+        panic!("expected `Other`");
+    };
+    assert_eq!(inner.to_string(), "function: parse: fancy");
+}
+
+#[errors(ParseIntError, (Fancy, std::io::Error) as Other, no_display)]
+fn load(x: &str) -> Result<i32, LoadConfigError> {
+    if x.is_empty() {
+        Err(Fancy)?;
+    }
+    Ok(x.parse()?)
+}
+
+impl fmt::Display for LoadConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseInt(err) => write!(f, "config value isnt a number ({err})"),
+            Self::Other(err) => write!(f, "config couldnt be loaded: {err}"),
+        }
+    }
+}
+
+#[test]
+fn manual_display_impl() {
+    assert_eq!(
+        load("x").unwrap_err().to_string(),
+        "config value isnt a number (invalid digit found in string)"
+    );
+    // Groups keep the generated `Display`.
+    assert_eq!(
+        load("").unwrap_err().to_string(),
+        "config couldnt be loaded: fancy"
+    );
+}
+
+#[errors(opaque; ParseIntError, no_display)]
+fn load_opaque(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+impl fmt::Display for LoadOpaqueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} error", self.kind())
+    }
+}
+
+#[test]
+fn manual_display_opaque() {
+    assert_eq!(load_opaque("x").unwrap_err().to_string(), "ParseInt error");
+}
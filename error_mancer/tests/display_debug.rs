@@ -0,0 +1,43 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid code")]
+struct InvalidCode {
+    code: u8,
+}
+
+#[errors(impl_display_debug, InvalidCode, ParseIntError)]
+fn check(value: &str) -> Result<u8, _> {
+    let code = value.parse()?;
+    if code > 9 {
+        Err(InvalidCode { code })?;
+    }
+    Ok(code)
+}
+
+#[errors(InvalidCode, display_context = "checking", impl_display_debug)]
+fn check_with_context() -> Result<(), _> {
+    Err(InvalidCode { code: 1 })?
+}
+
+#[test]
+fn display_uses_debug() {
+    assert_eq!(
+        check("12").unwrap_err().to_string(),
+        "InvalidCode(InvalidCode { code: 12 })"
+    );
+    assert_eq!(
+        check("x").unwrap_err().to_string(),
+        "ParseInt(ParseIntError { kind: InvalidDigit })"
+    );
+    assert_eq!(
+        format!("{:#}", check("12").unwrap_err()),
+        format!("{:#?}", check("12").unwrap_err())
+    );
+    assert_eq!(
+        check_with_context().unwrap_err().to_string(),
+        "checking: InvalidCode(InvalidCode { code: 1 })"
+    );
+}
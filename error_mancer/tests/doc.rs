@@ -0,0 +1,29 @@
+#![deny(clippy::missing_errors_doc)]
+
+use core::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+/// Parse a number.
+#[errors(
+    /// The input wasnt a valid number.
+    ParseIntError
+)]
+pub fn parse(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+/// Parse a number, with handwritten docs.
+///
+/// # Errors
+/// Fails if `x` isnt a number.
+#[errors(ParseIntError)]
+pub fn parse_documented(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+#[test]
+fn attributes_preserved() {
+    assert_eq!(parse("10").unwrap(), 10);
+    assert!(parse_documented("abc").is_err());
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "embedded-io")]
+#![no_std]
+
+// The test harness needs `std`, but the code below only uses `core`.
+extern crate std;
+
+use core::fmt;
+
+use embedded_io::{ErrorKind, ErrorType, Read};
+use error_mancer::prelude::*;
+
+/// Doesnt implement `embedded_io::Error`.
+#[derive(Debug)]
+struct Checksum;
+
+impl fmt::Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("checksum mismatch")
+    }
+}
+
+#[derive(Debug)]
+struct Link;
+
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("link down")
+    }
+}
+
+impl core::error::Error for Link {}
+
+impl embedded_io::Error for Link {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::NotConnected
+    }
+}
+
+/// A transport returning a scripted sequence of frames.
+struct Transport {
+    frames: &'static [&'static [u8]],
+}
+
+#[errors(#[io_kind = InvalidData] Checksum, #[io_kind = TimedOut] Link, core::str::Utf8Error)]
+fn receive(transport: &mut Transport, buf: &mut [u8]) -> Result<usize, ReceiveError> {
+    let Some((frame, rest)) = transport.frames.split_first() else {
+        return Ok(0);
+    };
+    transport.frames = rest;
+    match frame {
+        [] => Err(Link)?,
+        [0xff, ..] => Err(Checksum)?,
+        _ => {}
+    }
+    core::str::from_utf8(frame)?;
+    buf[..frame.len()].copy_from_slice(frame);
+    Ok(frame.len())
+}
+
+impl ErrorType for Transport {
+    type Error = ReceiveError;
+}
+
+impl Read for Transport {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        receive(self, buf)
+    }
+}
+
+#[test]
+fn reads() {
+    let mut transport = Transport {
+        frames: &[b"hi", &[0xff, 0x00], &[], &[0xc3]],
+    };
+    let mut buf = [0; 8];
+    assert_eq!(transport.read(&mut buf).unwrap(), 2);
+
+    // Attribute mapping for payloads without a kind.
+    let err = transport.read(&mut buf).unwrap_err();
+    assert_eq!(embedded_io::Error::kind(&err), ErrorKind::InvalidData);
+
+    // The payload kind takes precedence over the attribute.
+    let err = transport.read(&mut buf).unwrap_err();
+    assert_eq!(embedded_io::Error::kind(&err), ErrorKind::NotConnected);
+
+    // Defaults to `Other`.
+    let err = transport.read(&mut buf).unwrap_err();
+    assert_eq!(embedded_io::Error::kind(&err), ErrorKind::Other);
+
+    assert_eq!(transport.read(&mut buf).unwrap(), 0);
+}
@@ -0,0 +1,53 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("value out of range")]
+struct OutOfRange;
+
+/// Returns early with the error converted into the function error type, like `anyhow::ensure!`.
+macro_rules! ensure {
+    ($cond:expr, $err:expr) => {
+        if !$cond {
+            return Err(::core::convert::From::from($err));
+        }
+    };
+}
+
+#[errors(ParseIntError, OutOfRange)]
+fn parse_small(x: &str) -> Result<u8, _> {
+    let x: i32 = x.parse()?;
+    ensure!(x < 100, OutOfRange);
+    Ok(x as u8)
+}
+
+#[errors(OutOfRange)]
+fn early_ok(x: i32) -> Result<&'static str, _> {
+    if x == 0 {
+        return Ok("zero");
+    }
+    ensure!(x > 0, OutOfRange);
+    Ok("positive")
+}
+
+#[test]
+fn ensure_returns_from_function() {
+    assert_eq!(parse_small("10").unwrap(), 10);
+    assert!(matches!(
+        parse_small("200"),
+        Err(ParseSmallError::OutOfRange(OutOfRange))
+    ));
+    assert!(matches!(
+        parse_small("x"),
+        Err(ParseSmallError::ParseInt(_))
+    ));
+}
+
+#[test]
+fn early_return() {
+    assert_eq!(early_ok(0).unwrap(), "zero");
+    assert_eq!(early_ok(1).unwrap(), "positive");
+    assert!(matches!(early_ok(-1), Err(EarlyOkError::OutOfRange(_))));
+}
@@ -0,0 +1,47 @@
+use std::num::ParseIntError;
+use std::sync::Arc;
+
+use error_mancer::prelude::*;
+
+#[errors(std::io::Error, ParseIntError, enum_only)]
+fn load(value: &str) -> Result<i32, Arc<LoadError>> {
+    if value.is_empty() {
+        return Err(Arc::new(std::io::Error::other("empty").into()));
+    }
+    value.parse().map_err(|err| Arc::new(LoadError::from(err)))
+}
+
+// A named error type picks the enum name, same as without `enum_only`.
+#[errors(ParseIntError, enum_only)]
+fn parse(value: &str) -> Result<i32, ParseFailed> {
+    Ok(value.parse::<i32>()?)
+}
+
+struct Cache;
+
+#[errors]
+impl Cache {
+    #[errors(ParseIntError, enum_only)]
+    fn get(&self, value: &str) -> Result<i32, Arc<GetError>> {
+        value.parse().map_err(|err| Arc::new(GetError::from(err)))
+    }
+}
+
+#[test]
+fn manual_wiring() {
+    assert_eq!(load("3").unwrap(), 3);
+    let err = load("").unwrap_err();
+    assert!(matches!(*err, LoadError::StdIo(_)));
+    assert!(matches!(*load("x").unwrap_err(), LoadError::ParseInt(_)));
+    let shared = Arc::clone(&err);
+    assert_eq!(shared.to_string(), "empty");
+
+    assert_eq!(parse("4").unwrap(), 4);
+    assert!(matches!(parse("x").unwrap_err(), ParseFailed::ParseInt(_)));
+
+    assert_eq!(Cache.get("5").unwrap(), 5);
+    assert!(matches!(
+        *Cache.get("x").unwrap_err(),
+        GetError::ParseInt(_)
+    ));
+}
@@ -0,0 +1,53 @@
+use std::error::Error;
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[derive(Debug, thiserror::Error)]
+#[error("database unavailable")]
+struct DbError;
+
+#[errors(erase; DbError, ParseIntError)]
+pub fn load(value: &str) -> anyhow::Result<i32> {
+    if value.is_empty() {
+        Err(DbError)?;
+    }
+    Ok(value.parse()?)
+}
+
+#[errors(ParseIntError, erase)]
+pub fn boxed(value: &str) -> Result<i32, Box<dyn Error + Send + Sync>> {
+    Ok(value.parse()?)
+}
+
+struct Store;
+
+#[errors]
+impl Store {
+    #[errors(erase; DbError)]
+    pub fn get(&self, key: &str) -> anyhow::Result<usize> {
+        if key.is_empty() {
+            Err(DbError)?;
+        }
+        Ok(key.len())
+    }
+}
+
+#[test]
+fn erased_at_boundary() {
+    assert_eq!(load("3").unwrap(), 3);
+    assert_eq!(load("").unwrap_err().to_string(), "database unavailable");
+    assert_eq!(
+        load("x").unwrap_err().to_string(),
+        "invalid digit found in string"
+    );
+
+    assert_eq!(boxed("4").unwrap(), 4);
+    assert_eq!(
+        boxed("x").unwrap_err().to_string(),
+        "invalid digit found in string"
+    );
+
+    assert_eq!(Store.get("abc").unwrap(), 3);
+    assert!(Store.get("").is_err());
+}
@@ -0,0 +1,27 @@
+use std::collections::HashMap;
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+// Per-key errors are part of the `Ok` value, only a missing input fails the whole function.
+#[errors(ParseIntError, std::io::Error)]
+fn parse_all(values: &[(&str, &str)]) -> Result<HashMap<String, ParseAllError>, _> {
+    if values.is_empty() {
+        Err(std::io::Error::other("no values"))?;
+    }
+    let mut errors = HashMap::new();
+    for (key, value) in values {
+        if let Err(err) = value.parse::<i32>() {
+            errors.insert(key.to_string(), err.into());
+        }
+    }
+    Ok(errors)
+}
+
+#[test]
+fn enum_in_ok_type() {
+    let errors = parse_all(&[("a", "1"), ("b", "x")]).unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors["b"], ParseAllError::ParseInt(_)));
+    assert!(matches!(parse_all(&[]), Err(ParseAllError::StdIo(_))));
+}
@@ -0,0 +1,30 @@
+#![warn(
+    clippy::missing_errors_doc,
+    clippy::missing_panics_doc,
+    clippy::unwrap_used
+)]
+//! `#[expect(..)]` on functions using `#[errors]`, checked by clippy.
+
+use error_mancer::prelude::*;
+
+#[expect(clippy::missing_errors_doc)]
+#[errors(std::io::Error)]
+pub fn documented() -> Result<(), _> {
+    Ok(())
+}
+
+#[expect(
+    clippy::missing_panics_doc,
+    clippy::unwrap_used,
+    reason = "the unwrap is checked by the caller"
+)]
+#[errors(std::num::ParseIntError, no_doc)]
+pub fn unwraps(x: Option<&str>) -> Result<i32, _> {
+    Ok(x.unwrap().parse()?)
+}
+
+#[test]
+fn expectations_are_kept() {
+    assert!(documented().is_ok());
+    assert!(matches!(unwraps(Some("2")), Ok(2)));
+}
@@ -0,0 +1,60 @@
+use core::num::ParseIntError;
+
+use error_mancer::prelude::*;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("something new")]
+struct NewError;
+
+#[errors(allow_extra = "Unexpected", ParseIntError)]
+fn foo(x: &str) -> Result<i32, _> {
+    if x.is_empty() {
+        return Err(FooError::Unexpected(Box::new(NewError)));
+    }
+    Ok(x.parse()?)
+}
+
+#[test]
+fn listed_error() {
+    assert!(matches!(foo("abc"), Err(FooError::ParseInt(_))));
+}
+
+#[test]
+fn extra_error() {
+    let err = foo("").unwrap_err();
+    assert!(matches!(err, FooError::Unexpected(_)));
+    assert_eq!(err.to_string(), "something new");
+}
+
+fn boxed() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    Err(Box::new(NewError))
+}
+
+#[errors(allow_extra = Other)]
+fn bar() -> Result<(), _> {
+    boxed()?;
+    Ok(())
+}
+
+#[test]
+fn question_mark_on_boxed() {
+    assert!(matches!(bar(), Err(BarError::Other(_))));
+}
+
+#[errors(ParseIntError)]
+fn baz() -> Result<(), _> {
+    Ok(())
+}
+
+#[errors(allow_extra = Unexpected, ParseIntError)]
+fn upcast() -> Result<(), _> {
+    foo("").into_super_error::<UpcastError>()?;
+    baz().into_super_error::<UpcastError>()?;
+    Ok(())
+}
+
+#[test]
+fn flatten_keeps_extra() {
+    assert!(matches!(upcast(), Err(UpcastError::Unexpected(_))));
+}
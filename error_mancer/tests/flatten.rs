@@ -1,8 +1,5 @@
-#![feature(assert_matches)]
 #![no_std]
 
-use core::assert_matches::assert_matches;
-
 use error_mancer::prelude::*;
 use thiserror::Error;
 
@@ -47,8 +44,88 @@ fn unwrapped(x: i32) -> Result<i32, _> {
 
 #[test]
 fn test_unwrapped() {
-    assert_matches!(unwrapped(0), Ok(10));
-    assert_matches!(unwrapped(1), Err(UnwrappedError::Err1(Err1)));
-    assert_matches!(unwrapped(2), Err(UnwrappedError::Err2(Err2)));
-    assert_matches!(unwrapped(3), Err(UnwrappedError::Err3(Err3)));
+    assert!(matches!(unwrapped(0), Ok(10)));
+    assert!(matches!(unwrapped(1), Err(UnwrappedError::Err1(Err1))));
+    assert!(matches!(unwrapped(2), Err(UnwrappedError::Err2(Err2))));
+    assert!(matches!(unwrapped(3), Err(UnwrappedError::Err3(Err3))));
+}
+
+#[errors(Err1, no_flatten)]
+fn not_flattenable() -> Result<(), _> {
+    Err(Err1.into())
+}
+
+#[test]
+fn no_flatten() {
+    assert!(matches!(
+        not_flattenable(),
+        Err(NotFlattenableError::Err1(Err1))
+    ));
+}
+
+#[errors(Err1)]
+fn transport(x: i32) -> Result<Result<i32, FooError>, _> {
+    if x < 0 {
+        return Err(Err1.into());
+    }
+    Ok(foo(x).map(|_| x))
+}
+
+#[errors(Err1, Err2, Err3)]
+fn nested(x: i32) -> Result<i32, _> {
+    transport(x).flatten_nested()
+}
+
+#[test]
+fn flatten_nested() {
+    assert!(matches!(nested(0), Ok(0)));
+    assert!(matches!(nested(-1), Err(NestedError::Err1(Err1))));
+    assert!(matches!(nested(1), Err(NestedError::Err1(Err1))));
+    assert!(matches!(nested(2), Err(NestedError::Err2(Err2))));
+    assert!(matches!(nested(3), Err(NestedError::Err3(Err3))));
+}
+
+#[errors(Err1)]
+fn lookup(x: i32) -> Result<Option<i32>, _> {
+    match x {
+        0 => Ok(None),
+        1 => Err(Err1.into()),
+        _ => Ok(Some(x)),
+    }
+}
+
+#[errors(Err1, Err2)]
+fn lookup_required(x: i32) -> Result<i32, _> {
+    lookup(x).flatten_option(|| Err2.into())
+}
+
+#[test]
+fn flatten_option() {
+    assert!(matches!(lookup_required(5), Ok(5)));
+    assert!(matches!(
+        lookup_required(0),
+        Err(LookupRequiredError::Err2(Err2))
+    ));
+    assert!(matches!(
+        lookup_required(1),
+        Err(LookupRequiredError::Err1(Err1))
+    ));
+}
+
+#[derive(Debug)]
+struct Wrapper(FooError);
+
+impl From<FooError> for Wrapper {
+    fn from(err: FooError) -> Self {
+        Wrapper(err)
+    }
+}
+
+#[test]
+fn err_into() {
+    assert!(matches!(foo(0).err_into::<Wrapper>(), Ok(())));
+    assert!(matches!(
+        foo(1).err_into::<Wrapper>(),
+        Err(Wrapper(FooError::Err1(Err1)))
+    ));
 }
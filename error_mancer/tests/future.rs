@@ -0,0 +1,94 @@
+use std::future::Future;
+use std::num::ParseIntError;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use error_mancer::prelude::*;
+
+/// Parses the value, after being polled once without being ready.
+struct ParseLater {
+    value: &'static str,
+    polled: bool,
+}
+
+impl ParseLater {
+    fn new(value: &'static str) -> Self {
+        Self {
+            value,
+            polled: false,
+        }
+    }
+}
+
+#[errors]
+impl Future for ParseLater {
+    type Output = Result<i32, _>;
+
+    #[errors(ParseIntError, std::io::Error)]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if !self.polled {
+            self.polled = true;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        if self.value.is_empty() {
+            Err(std::io::Error::other("empty"))?;
+        }
+        Poll::Ready(Ok(self.value.parse()?))
+    }
+}
+
+struct Doubled(ParseLater);
+
+#[errors]
+impl Future for Doubled {
+    type Output = Result<i32, DoubledError>;
+
+    #[errors(ParseIntError, std::io::Error)]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let value =
+            std::task::ready!(Pin::new(&mut self.0).poll(cx)).into_super_error::<DoubledError>()?;
+        Poll::Ready(Ok(value * 2))
+    }
+}
+
+#[errors(ParseIntError)]
+fn poll_parse(value: &str) -> Poll<Result<i32, _>> {
+    Poll::Ready(Ok(value.parse()?))
+}
+
+#[errors(ParseIntError)]
+fn poll_boxed(value: &str) -> Poll<Result<i32, Box<dyn std::error::Error>>> {
+    Poll::Ready(Ok(value.parse()?))
+}
+
+#[test]
+fn manual_future() {
+    use futures::executor::block_on;
+
+    assert_eq!(block_on(ParseLater::new("4")).unwrap(), 4);
+    assert!(matches!(
+        block_on(ParseLater::new("x")),
+        Err(PollError::ParseInt(_))
+    ));
+    assert!(matches!(
+        block_on(ParseLater::new("")),
+        Err(PollError::StdIo(_))
+    ));
+
+    assert_eq!(block_on(Doubled(ParseLater::new("4"))).unwrap(), 8);
+    assert!(matches!(
+        block_on(Doubled(ParseLater::new("x"))),
+        Err(DoubledError::ParseInt(_))
+    ));
+}
+
+#[test]
+fn poll_function() {
+    assert!(matches!(poll_parse("1"), Poll::Ready(Ok(1))));
+    assert!(matches!(
+        poll_parse("x"),
+        Poll::Ready(Err(PollParseError::ParseInt(_)))
+    ));
+    assert!(matches!(poll_boxed("x"), Poll::Ready(Err(_))));
+}
@@ -0,0 +1,67 @@
+use std::error::Error;
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+use error_mancer::{generate_error_impl, FlattenInto};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("wrapped")]
+struct Wrapped(#[source] std::fmt::Error);
+
+generate_error_impl!(
+    /// Errors of the parsing closures.
+    pub enum ClosureError {
+        Parse(ParseIntError),
+        Wrapped(Wrapped),
+    }
+);
+
+generate_error_impl!(
+    enum Empty {}
+);
+
+#[errors(ParseIntError)]
+fn parse(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+#[errors(ParseIntError, Wrapped, std::io::Error)]
+fn bigger(x: &str) -> Result<i32, _> {
+    let double = |x: &str| -> Result<i32, ClosureError> { Ok(x.parse::<i32>()? * 2) };
+    Ok(double(x).into_super_error::<BiggerError>()?)
+}
+
+#[test]
+fn question_mark_converts() {
+    let check = |x: i32| -> Result<i32, ClosureError> {
+        if x < 0 {
+            Err(Wrapped(std::fmt::Error))?;
+        }
+        Ok(x)
+    };
+    assert_eq!(check(1).unwrap(), 1);
+    assert!(matches!(check(-1), Err(ClosureError::Wrapped(_))));
+}
+
+#[test]
+fn flattening() {
+    let result: Result<i32, ClosureError> = parse("x").into_super_error();
+    assert!(matches!(result, Err(ClosureError::Parse(_))));
+
+    assert_eq!(bigger("2").unwrap(), 4);
+    assert!(matches!(bigger("x"), Err(BiggerError::ParseInt(_))));
+
+    let _: fn(Empty) -> ClosureError = FlattenInto::flatten;
+}
+
+#[test]
+fn display_and_source() {
+    let err = ClosureError::from(Wrapped(std::fmt::Error));
+    assert_eq!(err.to_string(), "wrapped");
+    assert!(err.source().unwrap().is::<std::fmt::Error>());
+    assert_eq!(
+        format!("{:?}", ClosureError::from("x".parse::<i32>().unwrap_err())),
+        "Parse(ParseIntError { kind: InvalidDigit })"
+    );
+}
@@ -0,0 +1,121 @@
+use error_mancer::prelude::*;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("error 1")]
+struct Err1;
+
+#[derive(Error, Debug)]
+#[error("error 2")]
+struct Err2;
+
+#[derive(Error, Debug)]
+#[error("error 3")]
+struct Err3;
+
+#[errors((Err1, Err2) as Combined, Err3)]
+fn foo(x: i32) -> Result<(), _> {
+    match x {
+        0 => Ok(()),
+        1 => Err(Err1)?,
+        2 => Err(Err2)?,
+        _ => Err(Err3)?,
+    }
+}
+
+#[test]
+fn grouped_variant() {
+    assert!(foo(0).is_ok());
+    assert!(matches!(
+        foo(1),
+        Err(FooError::Combined(FooCombinedError::Err1(Err1)))
+    ));
+    assert!(matches!(
+        foo(2),
+        Err(FooError::Combined(FooCombinedError::Err2(Err2)))
+    ));
+    assert!(matches!(foo(3), Err(FooError::Err3(Err3))));
+}
+
+#[test]
+fn display() {
+    assert_eq!(foo(2).unwrap_err().to_string(), "error 2");
+}
+
+#[errors(Err1, Err2, Err3)]
+fn flat(x: i32) -> Result<(), _> {
+    foo(x).into_super_error::<FlatError>()?;
+    Ok(())
+}
+
+#[test]
+fn flatten_leaves() {
+    assert!(matches!(flat(1), Err(FlatError::Err1(Err1))));
+    assert!(matches!(flat(2), Err(FlatError::Err2(Err2))));
+    assert!(matches!(flat(3), Err(FlatError::Err3(Err3))));
+}
+
+#[derive(Error, Debug)]
+#[error("query failed")]
+struct QueryError {
+    #[source]
+    cause: std::io::Error,
+}
+
+#[errors(
+    group Io { std::io::Error, std::str::Utf8Error },
+    group Db { QueryError, Err1 },
+)]
+fn categories(x: i32) -> Result<(), _> {
+    match x {
+        0 => Err(std::io::Error::other("io"))?,
+        1 => Err(String::from_utf8(vec![0xff]).unwrap_err().utf8_error())?,
+        _ => Err(QueryError {
+            cause: std::io::Error::other("connection reset"),
+        })?,
+    }
+    Ok(())
+}
+
+#[test]
+fn group_keyword() {
+    assert!(matches!(
+        categories(0),
+        Err(CategoriesError::Io(CategoriesIoError::StdIo(_)))
+    ));
+    assert!(matches!(
+        categories(1),
+        Err(CategoriesError::Io(CategoriesIoError::StdStrUtf8(_)))
+    ));
+    assert!(matches!(
+        categories(2),
+        Err(CategoriesError::Db(CategoriesDbError::Query(_)))
+    ));
+}
+
+#[test]
+fn group_keyword_is_transparent() {
+    use std::error::Error as _;
+
+    let err = categories(2).unwrap_err();
+    assert_eq!(err.to_string(), "query failed");
+    assert_eq!(err.source().unwrap().to_string(), "connection reset");
+}
+
+#[errors(std::io::Error, std::str::Utf8Error, QueryError, Err1)]
+fn flat_categories(x: i32) -> Result<(), _> {
+    categories(x).into_super_error::<FlatCategoriesError>()?;
+    Ok(())
+}
+
+#[test]
+fn group_keyword_flattens_leaves() {
+    assert!(matches!(
+        flat_categories(0),
+        Err(FlatCategoriesError::StdIo(_))
+    ));
+    assert!(matches!(
+        flat_categories(2),
+        Err(FlatCategoriesError::Query(_))
+    ));
+}
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[derive(Debug)]
+struct MissingKey;
+
+impl std::fmt::Display for MissingKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("missing key")
+    }
+}
+
+impl std::error::Error for MissingKey {}
+
+#[errors(ParseIntError, std::io::Error)]
+fn result(x: &str, fail_io: bool) -> Result<i32, _> {
+    let value = if fail_io {
+        Err(std::io::Error::other("io"))
+    } else {
+        x.parse::<i32>().map_err(std::io::Error::other)
+    };
+    let value = handle!(value => ResultError {
+        Err(err) if err.kind() == std::io::ErrorKind::Other && !fail_io => -1,
+    });
+    Ok(value)
+}
+
+#[errors(MissingKey)]
+fn option(map: &HashMap<&str, i32>, key: &str) -> Result<i32, _> {
+    let value = handle!(map.get(key) => OptionError {
+        None => Err(MissingKey)?,
+    });
+    Ok(*value)
+}
+
+#[errors(MissingKey, ParseIntError)]
+fn option_of_result(values: &[&str], index: usize) -> Result<i32, _> {
+    let value = handle!(values.get(index).map(|value| value.parse::<i32>()) => OptionOfResultError {
+        None => Err(MissingKey)?,
+        Some(Err(_)) if index == 0 => 0,
+    });
+    Ok(value)
+}
+
+#[test]
+fn handles_results() {
+    assert_eq!(result("10", false).unwrap(), 10);
+    assert_eq!(result("x", false).unwrap(), -1);
+    assert!(matches!(result("10", true), Err(ResultError::StdIo(_))));
+}
+
+#[test]
+fn handles_options() {
+    let map = HashMap::from([("a", 1)]);
+    assert_eq!(option(&map, "a").unwrap(), 1);
+    assert!(matches!(
+        option(&map, "b"),
+        Err(OptionError::MissingKey(MissingKey))
+    ));
+}
+
+#[test]
+fn handles_option_of_result() {
+    assert_eq!(option_of_result(&["x", "2"], 1).unwrap(), 2);
+    assert_eq!(option_of_result(&["x", "2"], 0).unwrap(), 0);
+    assert!(matches!(
+        option_of_result(&["x", "y"], 1),
+        Err(OptionOfResultError::ParseInt(_))
+    ));
+    assert!(matches!(
+        option_of_result(&["x"], 5),
+        Err(OptionOfResultError::MissingKey(_))
+    ));
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "anyhow")]
+
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[derive(Debug)]
+struct Timeout;
+
+impl std::fmt::Display for Timeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("timed out")
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+#[errors(Timeout, ParseIntError)]
+fn fetch(value: &str) -> Result<i32, _> {
+    if value.is_empty() {
+        Err(Timeout)?;
+    }
+    Ok(value.parse()?)
+}
+
+fn sync(value: &str) -> anyhow::Result<i32> {
+    let value = handle!(fetch(value) => wrap_rest(format!("while syncing {value:?}")) {
+        Err(FetchError::Timeout(_)) => 0,
+    });
+    Ok(value)
+}
+
+fn lookup(values: &[&str], index: usize) -> anyhow::Result<i32> {
+    let value = handle!(values.get(index).map(|value| fetch(value)) => wrap_rest("while looking up") {
+        None => -1,
+    });
+    Ok(value)
+}
+
+#[test]
+fn handled_and_ok_values() {
+    assert_eq!(sync("").unwrap(), 0);
+    assert_eq!(sync("3").unwrap(), 3);
+    assert_eq!(lookup(&["4"], 0).unwrap(), 4);
+    assert_eq!(lookup(&["4"], 1).unwrap(), -1);
+}
+
+#[test]
+fn rest_is_wrapped() {
+    let err = sync("x").unwrap_err();
+    assert_eq!(err.to_string(), "while syncing \"x\"");
+    assert!(matches!(
+        err.downcast_ref::<FetchError>(),
+        Some(FetchError::ParseInt(_))
+    ));
+
+    let err = lookup(&[""], 0).unwrap_err();
+    assert_eq!(err.to_string(), "while looking up");
+    assert_eq!(err.root_cause().to_string(), "timed out");
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "http")]
+
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[derive(Debug)]
+struct Db;
+
+impl std::fmt::Display for Db {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("database unavailable")
+    }
+}
+
+#[ErrorMancerMeta(http_status = 404)]
+#[derive(Debug)]
+struct NotFound;
+
+impl std::fmt::Display for NotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("not found")
+    }
+}
+
+#[errors(
+    #[http_status = 503] Db,
+    #[http_status = 400] ParseIntError,
+    NotFound,
+    group Io { std::io::Error },
+)]
+fn handler(id: &str) -> Result<i32, _> {
+    match id {
+        "db" => Err(Db)?,
+        "io" => Err(std::io::Error::other("io"))?,
+        "" => Err(NotFound)?,
+        _ => {}
+    }
+    Ok(id.parse()?)
+}
+
+#[errors(std::io::Error, http_status = 502)]
+fn upstream() -> Result<(), _> {
+    Err(std::io::Error::other("upstream"))?;
+    Ok(())
+}
+
+#[test]
+fn attribute_status() {
+    assert_eq!(handler("db").unwrap_err().http_status(), 503);
+    assert_eq!(handler("x").unwrap_err().http_status(), 400);
+}
+
+#[test]
+fn metadata_status() {
+    assert_eq!(handler("").unwrap_err().http_status(), 404);
+}
+
+#[test]
+fn default_status() {
+    assert_eq!(handler("io").unwrap_err().http_status(), 500);
+    assert_eq!(upstream().unwrap_err().http_status(), 502);
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "i18n")]
+
+use error_mancer::prelude::*;
+use error_mancer::LocalizedDisplay;
+use fluent_bundle::{FluentBundle, FluentResource};
+use unic_langid::langid;
+
+#[derive(Debug)]
+struct ParseError;
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("bad syntax")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[errors(std::io::Error msg_id "err-open-config", ParseError)]
+fn load(fail_open: bool) -> Result<(), _> {
+    if fail_open {
+        Err(std::io::Error::other("missing"))?;
+    }
+    Err(ParseError)?;
+    Ok(())
+}
+
+#[errors((std::io::Error msg_id "err-grouped", ParseError) as Combined)]
+fn grouped() -> Result<(), _> {
+    Err(std::io::Error::other("missing"))?;
+    Ok(())
+}
+
+fn bundle(source: &str) -> FluentBundle<FluentResource> {
+    let resource = FluentResource::try_new(source.to_owned()).unwrap();
+    let mut bundle = FluentBundle::new(vec![langid!("de-DE")]);
+    bundle.set_use_isolating(false);
+    bundle.add_resource(resource).unwrap();
+    bundle
+}
+
+#[test]
+fn message_ids() {
+    assert_eq!(load(true).unwrap_err().message_id(), "err-open-config");
+    assert_eq!(load(false).unwrap_err().message_id(), "parse");
+    assert_eq!(grouped().unwrap_err().message_id(), "err-grouped");
+}
+
+#[test]
+fn localized() {
+    let bundle = bundle(
+        "err-open-config = Konfiguration konnte nicht geöffnet werden: { $error }\n\
+         parse = Syntaxfehler",
+    );
+
+    let err = load(true).unwrap_err();
+    assert_eq!(
+        LocalizedDisplay::new(&err, &bundle).to_string(),
+        "Konfiguration konnte nicht geöffnet werden: missing"
+    );
+    let err = load(false).unwrap_err();
+    assert_eq!(
+        LocalizedDisplay::new(&err, &bundle).to_string(),
+        "Syntaxfehler"
+    );
+}
+
+#[test]
+fn missing_message_falls_back() {
+    let bundle = bundle("other = Etwas");
+    let err = load(true).unwrap_err();
+    assert_eq!(LocalizedDisplay::new(&err, &bundle).to_string(), "missing");
+    assert_eq!(err.to_string(), "missing");
+}
@@ -0,0 +1,43 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[errors(ParseIntError, std::io::Error)]
+fn parse(value: &str) -> Result<i32, _> {
+    if value.is_empty() {
+        let err: ParseError = std::io::Error::other("empty").into();
+        Err(err)?;
+    }
+    let parsed: Result<i32, ParseError> = value.parse().map_err(ParseError::from);
+    Ok(parsed?)
+}
+
+// Goes through the struct wrapping the hidden enum instead.
+#[errors(opaque; ParseIntError)]
+fn parse_opaque(value: &str) -> Result<i32, _> {
+    let parsed: Result<i32, ParseOpaqueError> = value.parse().map_err(ParseOpaqueError::from);
+    Ok(parsed?)
+}
+
+#[errors(ParseIntError, span_trace)]
+fn parse_traced(value: &str) -> Result<i32, _> {
+    let err: ParseTracedError = match value.parse::<i32>() {
+        Ok(value) => return Ok(value),
+        Err(err) => err.into(),
+    };
+    Err(err)?
+}
+
+#[test]
+fn question_mark_on_own_enum() {
+    assert_eq!(parse("1").unwrap(), 1);
+    assert!(matches!(parse(""), Err(ParseError::StdIo(_))));
+    assert!(matches!(parse("x"), Err(ParseError::ParseInt(_))));
+    assert_eq!(parse_opaque("2").unwrap(), 2);
+    assert!(parse_opaque("x").is_err());
+    assert_eq!(parse_traced("3").unwrap(), 3);
+    assert!(matches!(
+        parse_traced("x"),
+        Err(ParseTracedError::ParseInt(..))
+    ));
+}
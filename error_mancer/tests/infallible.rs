@@ -0,0 +1,27 @@
+use std::convert::Infallible;
+
+use error_mancer::prelude::*;
+
+#[errors]
+fn always_ok(x: i32) -> Result<i32, _> {
+    Ok(x * 2)
+}
+
+#[errors(group Nothing {})]
+fn empty_group() -> Result<(), _> {
+    Ok(())
+}
+
+#[test]
+fn into_value() {
+    assert_eq!(always_ok(2).into_value(), 4);
+    let value: Result<&str, Infallible> = Ok("ok");
+    assert_eq!(value.into_value(), "ok");
+}
+
+#[test]
+fn empty_group_is_uninhabited() {
+    let inner: Result<(), EmptyGroupNothingError> = Ok(());
+    inner.into_value();
+    assert!(empty_group().is_ok());
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "serde")]
+
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+use serde_json::json;
+
+#[errors(ParseIntError, group Text { std::fmt::Error }, allow_extra = Other, json_error)]
+fn foo(x: &str) -> Result<i32, _> {
+    match x {
+        "fmt" => Err(std::fmt::Error)?,
+        "other" => Err(Box::<dyn std::error::Error + Send + Sync>::from(
+            "something else",
+        ))?,
+        _ => {}
+    }
+    Ok(x.parse()?)
+}
+
+fn handler(x: &str) -> Result<i32, FooJsonError> {
+    Ok(foo(x)? * 2)
+}
+
+#[test]
+fn serialized_body() {
+    let err = FooJsonError(foo("abc").unwrap_err());
+    assert_eq!(
+        serde_json::to_value(&err).unwrap(),
+        json!({"error": {"type": "ParseInt", "message": "invalid digit found in string"}})
+    );
+}
+
+#[test]
+fn groups_and_extra() {
+    assert_eq!(
+        serde_json::to_value(FooJsonError(foo("fmt").unwrap_err())).unwrap(),
+        json!({"error": {"type": "Text", "message": "an error occurred when formatting an argument"}})
+    );
+    assert_eq!(
+        serde_json::to_value(FooJsonError(foo("other").unwrap_err())).unwrap(),
+        json!({"error": {"type": "Other", "message": "something else"}})
+    );
+}
+
+#[test]
+fn converts_from_enum_and_errors() {
+    assert_eq!(handler("2").unwrap(), 4);
+    let err = handler("x").unwrap_err();
+    assert!(matches!(err.0, FooError::ParseInt(_)));
+    assert_eq!(err.to_string(), "invalid digit found in string");
+
+    let err: FooJsonError = "x".parse::<i32>().unwrap_err().into();
+    assert!(matches!(err.0, FooError::ParseInt(_)));
+}
@@ -0,0 +1,88 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[errors(
+    #[discriminant = 1]
+    std::io::Error,
+    #[discriminant = 2]
+    ParseIntError,
+    #[discriminant = -1]
+    group Text { std::fmt::Error, std::str::Utf8Error },
+    kind,
+)]
+fn foo(x: i32) -> Result<(), _> {
+    match x {
+        0 => Err(std::io::Error::other("oh no"))?,
+        1 => Err("abc".parse::<i32>().unwrap_err())?,
+        _ => Err(std::fmt::Error)?,
+    }
+    Ok(())
+}
+
+#[errors(std::io::Error, ParseIntError, allow_extra = Other, kind)]
+fn implicit(x: i32) -> Result<(), _> {
+    match x {
+        0 => Err(std::io::Error::other("oh no"))?,
+        1 => Err("abc".parse::<i32>().unwrap_err())?,
+        _ => Err(Box::<dyn std::error::Error + Send + Sync>::from("other"))?,
+    }
+    Ok(())
+}
+
+#[errors(kind)]
+fn empty() -> Result<(), _> {
+    Ok(())
+}
+
+#[test]
+fn explicit_discriminants() {
+    assert_eq!(foo(0).unwrap_err().kind(), FooErrorKind::StdIo);
+    assert_eq!(foo(1).unwrap_err().kind(), FooErrorKind::ParseInt);
+    assert_eq!(foo(2).unwrap_err().kind(), FooErrorKind::Text);
+
+    assert_eq!(FooErrorKind::StdIo as i32, 1);
+    assert_eq!(FooErrorKind::ParseInt as i32, 2);
+    assert_eq!(FooErrorKind::Text as i32, -1);
+    assert_eq!(size_of::<FooErrorKind>(), size_of::<i32>());
+}
+
+#[test]
+fn groups_have_their_own_kind() {
+    let Err(FooError::Text(inner)) = foo(2) else {
+        panic!("expected text error");
+    };
+    assert_eq!(inner.kind(), FooTextErrorKind::StdFmt);
+}
+
+#[test]
+fn implicit_discriminants() {
+    assert_eq!(implicit(0).unwrap_err().kind() as i32, 0);
+    assert_eq!(implicit(1).unwrap_err().kind() as i32, 1);
+    assert_eq!(implicit(2).unwrap_err().kind(), ImplicitErrorKind::Other);
+    assert_eq!(ImplicitErrorKind::Other as i32, 2);
+}
+
+#[test]
+fn empty_kind() {
+    assert!(empty().is_ok());
+    let _: fn(&EmptyError) -> EmptyErrorKind = EmptyError::kind;
+}
+
+#[errors(std::io::Error, group Text { std::fmt::Error }, allow_extra = Other, kind, iter_kinds)]
+fn listed() -> Result<(), _> {
+    Ok(())
+}
+
+#[test]
+fn iterating_kinds() {
+    assert!(listed().is_ok());
+    assert_eq!(
+        ListedError::kinds().collect::<Vec<_>>(),
+        [
+            ListedErrorKind::StdIo,
+            ListedErrorKind::Text,
+            ListedErrorKind::Other,
+        ]
+    );
+}
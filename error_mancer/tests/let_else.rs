@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[derive(Debug)]
+struct Missing;
+
+impl std::fmt::Display for Missing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("missing")
+    }
+}
+
+#[errors(Missing, ParseIntError)]
+fn lookup(map: &HashMap<&str, &str>, key: &str) -> Result<i32, _> {
+    let Some(value) = map.get(key) else {
+        Err(Missing)?
+    };
+    let Ok(value) = value.parse::<i32>() else {
+        return Err(Missing.into());
+    };
+    Ok(value)
+}
+
+#[errors(ParseIntError)]
+fn parse_first(values: &[&str]) -> Result<Option<i32>, _> {
+    let [first, ..] = values else {
+        return Ok(None);
+    };
+    Ok(Some(first.parse()?))
+}
+
+#[errors(Missing)]
+async fn lookup_async(map: &HashMap<&str, i32>, key: &str) -> Result<i32, _> {
+    let Some(value) = map.get(key) else {
+        Err(Missing)?
+    };
+    Ok(*value)
+}
+
+#[test]
+fn let_else_with_question_mark() {
+    let map = HashMap::from([("a", "1"), ("b", "x")]);
+    assert_eq!(lookup(&map, "a").unwrap(), 1);
+    assert!(matches!(lookup(&map, "c"), Err(LookupError::Missing(_))));
+}
+
+#[test]
+fn let_else_with_return() {
+    let map = HashMap::from([("b", "x")]);
+    assert!(matches!(lookup(&map, "b"), Err(LookupError::Missing(_))));
+}
+
+#[test]
+fn let_else_ok_return() {
+    assert_eq!(parse_first(&[]).unwrap(), None);
+    assert_eq!(parse_first(&["2"]).unwrap(), Some(2));
+    assert!(matches!(
+        parse_first(&["x"]),
+        Err(ParseFirstError::ParseInt(_))
+    ));
+}
+
+#[tokio::test]
+async fn let_else_in_async() {
+    let map = HashMap::from([("a", 1)]);
+    assert_eq!(lookup_async(&map, "a").await.unwrap(), 1);
+    assert!(matches!(
+        lookup_async(&map, "b").await,
+        Err(LookupAsyncError::Missing(_))
+    ));
+}
@@ -0,0 +1,179 @@
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::Debug;
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[errors(ParseIntError)]
+fn elided(x: &str) -> Result<&str, _> {
+    x.parse::<i32>()?;
+    Ok(x)
+}
+
+#[errors(ParseIntError)]
+fn explicit<'a, 'b>(x: &'a str, y: &'b str) -> Result<&'a str, _>
+where
+    'b: 'a,
+{
+    y.parse::<i32>()?;
+    Ok(x)
+}
+
+#[errors(ParseIntError)]
+fn placeholder(x: &'_ str) -> Result<Cow<'_, str>, _> {
+    x.parse::<i32>()?;
+    Ok(Cow::Borrowed(x))
+}
+
+#[errors(ParseIntError)]
+fn generic<'a, T: Debug + ?Sized>(value: &'a T, x: &str) -> Result<&'a T, _> {
+    x.parse::<i32>()?;
+    Ok(value)
+}
+
+#[errors(ParseIntError, allow_extra = Other)]
+fn with_extra<'a>(x: &'a str) -> Result<&'a str, _> {
+    if x.is_empty() {
+        Err(Box::<dyn Error + Send + Sync>::from("empty"))?;
+    }
+    x.parse::<i32>()?;
+    Ok(x)
+}
+
+#[errors(ParseIntError)]
+async fn async_borrow<'a>(x: &'a str) -> Result<&'a str, _> {
+    x.parse::<i32>()?;
+    Ok(x)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+}
+
+#[errors]
+impl<'a> Parser<'a> {
+    #[errors(ParseIntError)]
+    fn number(&self) -> Result<i32, _> {
+        Ok(self.text.parse()?)
+    }
+
+    #[errors(ParseIntError)]
+    fn validated(&self) -> Result<&'a str, _> {
+        self.text.parse::<i32>()?;
+        Ok(self.text)
+    }
+
+    #[errors(ParseIntError)]
+    fn borrowed(&self) -> Result<&str, _> {
+        self.text.parse::<i32>()?;
+        Ok(self.text)
+    }
+}
+
+#[test]
+fn borrowed_ok_values() {
+    assert_eq!(elided("1").unwrap(), "1");
+    assert_eq!(explicit("a", "1").unwrap(), "a");
+    assert_eq!(placeholder("1").unwrap(), "1");
+    assert_eq!(generic("a", "1").unwrap(), "a");
+    assert_eq!(with_extra("1").unwrap(), "1");
+    assert!(matches!(elided("x"), Err(ElidedError::ParseInt(_))));
+    assert!(matches!(
+        explicit("a", "x"),
+        Err(ExplicitError::ParseInt(_))
+    ));
+    assert!(matches!(
+        placeholder("x"),
+        Err(PlaceholderError::ParseInt(_))
+    ));
+    assert!(matches!(generic("a", "x"), Err(GenericError::ParseInt(_))));
+    assert!(matches!(with_extra(""), Err(WithExtraError::Other(_))));
+}
+
+#[tokio::test]
+async fn async_borrowed_ok_value() {
+    let text = String::from("5");
+    assert_eq!(async_borrow(&text).await.unwrap(), "5");
+    assert!(matches!(
+        async_borrow("x").await,
+        Err(AsyncBorrowError::ParseInt(_))
+    ));
+}
+
+#[test]
+fn methods_on_borrowing_type() {
+    let text = String::from("12");
+    let parser = Parser { text: &text };
+    assert_eq!(parser.number().unwrap(), 12);
+    assert_eq!(parser.validated().unwrap(), "12");
+    assert_eq!(parser.borrowed().unwrap(), "12");
+
+    let parser = Parser { text: "x" };
+    assert!(matches!(
+        parser.validated(),
+        Err(ValidatedError::ParseInt(_))
+    ));
+}
+
+#[test]
+fn errors_are_static() {
+    let text = String::from("x");
+    let err = explicit(&text, &text).unwrap_err();
+    drop(text);
+
+    let boxed: Box<dyn Error + Send + Sync + 'static> = Box::new(err);
+    let err = boxed.downcast::<ExplicitError>().unwrap();
+    assert!(matches!(*err, ExplicitError::ParseInt(_)));
+
+    let text = String::from("");
+    let boxed: Box<dyn Error + Send + Sync> = with_extra(&text).unwrap_err().into();
+    drop(text);
+    assert_eq!(boxed.to_string(), "empty");
+    assert!(boxed.downcast_ref::<WithExtraError>().is_some());
+}
+
+#[derive(Debug)]
+struct Borrowing<'a>(&'a str);
+
+impl std::fmt::Display for Borrowing<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "bad input {}", self.0)
+    }
+}
+
+impl Error for Borrowing<'_> {}
+
+fn check(x: &str) -> Result<(), Borrowing<'static>> {
+    if x.is_empty() {
+        return Err(Borrowing("empty"));
+    }
+    Ok(())
+}
+
+#[errors(Borrowing<'static> as Invalid, group Inner { &'static str as Message })]
+fn static_payloads<'a>(x: &'a str) -> Result<&'a str, _> {
+    check(x)?;
+    if x == "?" {
+        Err("question")?;
+    }
+    Ok(x)
+}
+
+#[test]
+fn static_lifetimes_in_payloads() {
+    assert_eq!(static_payloads("a").unwrap(), "a");
+    let err = static_payloads("").unwrap_err();
+    assert!(matches!(
+        err,
+        StaticPayloadsError::Invalid(Borrowing("empty"))
+    ));
+    assert_eq!(err.to_string(), "bad input empty");
+    assert!(err.source().is_none());
+    assert!(matches!(
+        static_payloads("?"),
+        Err(StaticPayloadsError::Inner(
+            StaticPayloadsInnerError::Message("question")
+        ))
+    ));
+}
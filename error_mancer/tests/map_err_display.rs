@@ -0,0 +1,21 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[errors(ParseIntError, std::io::Error)]
+fn parse(x: &str) -> Result<i32, _> {
+    if x.is_empty() {
+        Err(std::io::Error::other("empty input"))?;
+    }
+    Ok(x.parse()?)
+}
+
+#[test]
+fn display_of_inner_error() {
+    assert_eq!(parse("1").map_err_display(), Ok(1));
+    assert_eq!(parse("").map_err_display().unwrap_err(), "empty input");
+    assert_eq!(
+        parse("x").map_err_display().unwrap_err(),
+        "x".parse::<i32>().unwrap_err().to_string()
+    );
+}
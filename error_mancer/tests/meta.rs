@@ -0,0 +1,62 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[ErrorMancerMeta(http_status = 503, retry = true)]
+#[derive(Debug)]
+struct Unavailable;
+
+impl std::fmt::Display for Unavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("service unavailable")
+    }
+}
+
+#[ErrorMancerMeta(http_status = 404)]
+#[derive(Debug)]
+struct NotFound<T>(T);
+
+impl<T> std::fmt::Display for NotFound<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("not found")
+    }
+}
+
+#[errors(Unavailable, NotFound<u32> as NotFound, ParseIntError, group Io { std::io::Error retryable })]
+fn fetch(id: &str) -> Result<u32, _> {
+    match id {
+        "" => Err(Unavailable)?,
+        "io" => Err(std::io::Error::other("io"))?,
+        _ => {}
+    }
+    let id = id.parse()?;
+    if id == 0 {
+        Err(NotFound(id))?;
+    }
+    Ok(id)
+}
+
+#[test]
+fn payload_metadata() {
+    let err = fetch("").unwrap_err();
+    assert_eq!(err.http_status_for(), Some(503));
+    assert!(err.is_retryable());
+
+    let err = fetch("0").unwrap_err();
+    assert_eq!(err.http_status_for(), Some(404));
+    assert!(!err.is_retryable());
+}
+
+#[test]
+fn without_metadata() {
+    let err = fetch("x").unwrap_err();
+    assert_eq!(err.http_status_for(), None);
+    assert!(!err.is_retryable());
+}
+
+#[test]
+fn groups_and_retryable_entries() {
+    let err = fetch("io").unwrap_err();
+    assert_eq!(err.http_status_for(), None);
+    assert!(err.is_retryable());
+}
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use error_mancer::errors;
 use thiserror::Error;
 
@@ -20,3 +22,69 @@ fn test() -> Result<(), _> {
 fn bar() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
+
+#[errors(Err1)]
+fn boxed(fail: bool) -> Result<i32, Box<dyn std::error::Error>> {
+    if fail {
+        Err(Err1)?;
+    }
+    Ok(10)
+}
+
+#[test]
+fn boxed_dyn_error() {
+    assert_eq!(boxed(false).unwrap(), 10);
+
+    let err = boxed(true).unwrap_err();
+    assert_eq!(err.to_string(), "err1");
+}
+
+#[errors(Err1)]
+fn boxed_static(fail: bool) -> Result<i32, Box<dyn std::error::Error + 'static>> {
+    if fail {
+        Err(Err1)?;
+    }
+    Ok(10)
+}
+
+#[test]
+fn boxed_dyn_error_static() {
+    assert_eq!(boxed_static(false).unwrap(), 10);
+    assert_eq!(boxed_static(true).unwrap_err().to_string(), "err1");
+}
+
+mod inner {
+    #[derive(Debug, thiserror::Error)]
+    #[error("parse failed")]
+    pub struct ParseError;
+}
+
+// A path doesnt resolve to the generated enum, and variants live in their own namespace.
+#[errors(inner::ParseError, std::io::Error as ParseError)]
+fn parse(io: bool) -> Result<(), _> {
+    if io {
+        Err(std::io::Error::other("io"))?;
+    }
+    Err(inner::ParseError)?
+}
+
+#[test]
+fn enum_named_like_error() {
+    assert!(matches!(parse(false), Err(ParseError::InnerParse(_))));
+    assert!(matches!(parse(true), Err(ParseError::ParseError(_))));
+}
+
+/// Already taken, `load` names its enum explicitly instead.
+#[derive(Debug)]
+struct LoadError;
+
+#[errors(Err1)]
+fn load() -> Result<(), LoadFailure> {
+    Err(Err1)?
+}
+
+#[test]
+fn explicit_name_avoids_taken_name() {
+    assert!(matches!(load(), Err(LoadFailure::Err1(_))));
+    let _ = LoadError;
+}
@@ -0,0 +1,54 @@
+use core::num::{ParseIntError, TryFromIntError};
+
+use error_mancer::prelude::*;
+
+#[errors(TryFromIntError, ParseIntError)]
+fn outer(x: &str) -> Result<u8, _> {
+    #[errors(ParseIntError)]
+    fn inner(x: &str) -> Result<i32, _> {
+        Ok(x.parse()?)
+    }
+
+    let x = inner(x).into_super_error::<OuterError>()?;
+    Ok(x.try_into()?)
+}
+
+#[test]
+fn nested_function() {
+    assert_eq!(outer("10").unwrap(), 10);
+    assert!(matches!(outer("abc"), Err(OuterError::ParseInt(_))));
+    assert!(matches!(outer("300"), Err(OuterError::TryFromInt(_))));
+}
+
+#[errors(TryFromIntError)]
+fn same_name(x: i32) -> Result<u8, _> {
+    // The inner enum shadows the outer one inside the body.
+    #[errors(ParseIntError)]
+    fn same_name(x: &str) -> Result<i32, _> {
+        Ok(x.parse()?)
+    }
+
+    assert!(matches!(same_name("abc"), Err(SameNameError::ParseInt(_))));
+    Ok(x.try_into()?)
+}
+
+#[test]
+fn shadowed_name() {
+    assert_eq!(same_name(10).unwrap(), 10);
+    assert!(matches!(same_name(300), Err(SameNameError::TryFromInt(_))));
+}
+
+#[errors]
+fn anyhow_outer() -> anyhow::Result<i32> {
+    #[errors]
+    fn anyhow_inner() -> anyhow::Result<i32> {
+        Ok(10)
+    }
+
+    Ok(anyhow_inner().unwrap())
+}
+
+#[test]
+fn nested_inside_body() {
+    assert_eq!(anyhow_outer().unwrap(), 10);
+}
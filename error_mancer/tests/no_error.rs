@@ -0,0 +1,39 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+use error_mancer::NoError;
+
+#[errors(none)]
+fn double(x: i32) -> Result<i32, _> {
+    Ok(x * 2)
+}
+
+#[errors(none)]
+fn greeting() -> Result<&'static str, _> {
+    Ok("hello")
+}
+
+#[errors(std::io::Error, ParseIntError)]
+fn parse(x: &str) -> Result<i32, _> {
+    let doubled = double(x.parse()?).into_super_error::<ParseError>()?;
+    Ok(doubled)
+}
+
+#[test]
+fn shared_error_type() {
+    let errors: [fn() -> Result<(), NoError>; 2] =
+        [|| double(1).map(drop), || greeting().map(drop)];
+    for error in errors {
+        assert!(error().is_ok());
+    }
+}
+
+#[test]
+fn flattens_into_any_error() {
+    assert_eq!(parse("4").unwrap(), 8);
+    assert!(matches!(parse("x"), Err(ParseError::ParseInt(_))));
+
+    let result: Result<i32, ParseError> = double(3).into_super_error();
+    assert_eq!(result.unwrap(), 6);
+    assert_eq!(size_of::<Result<i32, NoError>>(), size_of::<i32>());
+}
@@ -0,0 +1,31 @@
+#![no_std]
+
+// The test harness needs `std`, but the code below only uses the `core` prelude.
+extern crate std;
+
+use core::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[errors(ParseIntError)]
+fn absolute(x: &str) -> ::core::result::Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+#[errors(ParseIntError)]
+fn relative(x: &str) -> core::result::Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+#[errors(ParseIntError)]
+fn named(x: &str) -> ::core::result::Result<i32, NamedFailure> {
+    Ok(x.parse()?)
+}
+
+#[test]
+fn absolute_path() {
+    assert!(matches!(absolute("1"), Ok(1)));
+    assert!(matches!(absolute("x"), Err(AbsoluteError::ParseInt(_))));
+    assert!(matches!(relative("x"), Err(RelativeError::ParseInt(_))));
+    assert!(matches!(named("x"), Err(NamedFailure::ParseInt(_))));
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "nom")]
+
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+use error_mancer::{NomError, NomFrame};
+use nom::bytes::complete::tag;
+use nom::character::complete::{alpha1, digit1};
+use nom::combinator::{all_consuming, cut, map_res};
+use nom::error::{context, ContextError, ErrorKind, FromExternalError, ParseError};
+use nom::multi::separated_list1;
+use nom::sequence::separated_pair;
+use nom::{IResult, Needed, Parser};
+
+#[derive(Debug, thiserror::Error)]
+#[error("`{0}` is set twice")]
+struct DuplicateKey(String);
+
+/// `key=value` entries separated by `;`, i.e `width=3;height=4`. A entry fails once its `=` is
+/// parsed.
+fn entries<'a, E>(input: &'a str) -> IResult<&'a str, Vec<(&'a str, u32)>, E>
+where
+    E: ParseError<&'a str> + ContextError<&'a str> + FromExternalError<&'a str, ParseIntError>,
+{
+    let value = context("value", map_res(digit1, str::parse));
+    let entry = context("entry", separated_pair(alpha1, tag("="), cut(value)));
+    all_consuming(separated_list1(tag(";"), entry)).parse(input)
+}
+
+#[errors(nom, ParseIntError, DuplicateKey)]
+fn parse_config(input: &str) -> Result<Vec<(String, u32)>, _> {
+    let (_, entries) = entries(input)?;
+    let mut config = Vec::<(String, u32)>::new();
+    for (key, value) in entries {
+        if config.iter().any(|(existing, _)| existing == key) {
+            return Err(DuplicateKey(key.to_owned()))?;
+        }
+        config.push((key.to_owned(), value));
+    }
+    Ok(config)
+}
+
+#[errors(nom_trace, ParseIntError)]
+fn parse_traced(input: &str) -> Result<Vec<(&str, u32)>, _> {
+    Ok(entries(input)?.1)
+}
+
+#[errors(nom)]
+fn parse_header(input: &[u8]) -> Result<usize, _> {
+    let (rest, _) = nom::bytes::streaming::tag(&b"MAGIC"[..]).parse(input)?;
+    Ok(rest.len())
+}
+
+#[test]
+fn parses() {
+    let config = parse_config("width=3;height=4").unwrap();
+    assert_eq!(config, [("width".to_owned(), 3), ("height".to_owned(), 4)]);
+}
+
+#[test]
+fn nom_error() {
+    let input = "width=3;height";
+    let Err(ParseConfigError::Nom(err)) = parse_config(input) else {
+        panic!("expected a nom error");
+    };
+    assert_eq!(err.kind, ErrorKind::Eof);
+    assert_eq!(err.input_offset(&input), 7);
+    assert!(err.trace.is_empty());
+    assert_eq!(err.to_string(), "End of file");
+}
+
+#[test]
+fn external_error() {
+    assert!(matches!(
+        parse_config("width=99999999999"),
+        Err(ParseConfigError::ParseInt(_))
+    ));
+}
+
+#[test]
+fn domain_error() {
+    let Err(ParseConfigError::DuplicateKey(err)) = parse_config("width=3;width=4") else {
+        panic!("expected a duplicate key");
+    };
+    assert_eq!(err.0, "width");
+}
+
+#[test]
+fn trace() {
+    let input = "width=x";
+    let Err(ParseTracedError::Nom(err)) = parse_traced(input) else {
+        panic!("expected a nom error");
+    };
+    assert_eq!(err.kind, ErrorKind::Digit);
+    assert_eq!(err.input_offset(&input), 6);
+    assert_eq!(err.contexts().collect::<Vec<_>>(), ["value", "entry"]);
+    assert_eq!(
+        err.trace[0],
+        NomFrame::Context {
+            remaining: 1,
+            context: "value",
+        }
+    );
+    assert_eq!(err.to_string(), "entry: value: Digit");
+}
+
+#[test]
+fn directly_as_parser_error() {
+    let result = entries::<ParseTracedError>("=3");
+    let Err(nom::Err::Error(ParseTracedError::Nom(NomError { kind, .. }))) = result else {
+        panic!("expected a nom error");
+    };
+    assert_eq!(kind, ErrorKind::Alpha);
+}
+
+#[test]
+fn incomplete() {
+    assert_eq!(parse_header(b"MAGIC!").unwrap(), 1);
+    let Err(ParseHeaderError::Nom(err)) = parse_header(b"MAG") else {
+        panic!("expected a nom error");
+    };
+    assert_eq!(err.needed, Some(Needed::new(2)));
+    assert_eq!(err.to_string(), "2 more bytes of input needed");
+}
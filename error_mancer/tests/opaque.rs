@@ -0,0 +1,79 @@
+use std::error::Error;
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+mod library {
+    use super::*;
+
+    #[errors(opaque; ParseIntError, std::io::Error, try_flatten)]
+    pub fn parse(x: &str) -> Result<i32, _> {
+        if x.is_empty() {
+            Err(std::io::Error::other("empty input"))?;
+        }
+        Ok(x.parse()?)
+    }
+
+    #[errors(std::io::Error, ParseIntError, opaque, iter_kinds)]
+    pub fn flag_form(x: &str) -> Result<i32, _> {
+        Ok(parse(x).into_super_error::<FlagFormError>()?)
+    }
+
+    #[errors(ParseIntError, try_flatten)]
+    pub fn inner(x: &str) -> Result<i32, _> {
+        Ok(x.parse()?)
+    }
+
+    #[errors(std::io::Error, ParseIntError, opaque)]
+    pub fn flattened(x: &str) -> Result<i32, _> {
+        Ok(inner(x).into_super_error::<FlattenedError>()?)
+    }
+}
+
+use library::{FlagFormError, FlagFormErrorKind, ParseErrorKind};
+
+#[test]
+fn only_kinds_and_messages() {
+    let err = library::parse("x").unwrap_err();
+    assert_eq!(err.kind(), ParseErrorKind::ParseInt);
+    assert_eq!(err.to_string(), "invalid digit found in string");
+    assert!(err.source().is_none());
+
+    let err = library::parse("").unwrap_err();
+    assert_eq!(err.kind(), ParseErrorKind::StdIo);
+    assert_eq!(err.to_string(), "empty input");
+    assert_eq!(
+        format!("{err:?}"),
+        r#"StdIo(Custom { kind: Other, error: "empty input" })"#
+    );
+}
+
+#[test]
+fn converting_between_opaque_errors() {
+    assert_eq!(library::flag_form("4").unwrap(), 4);
+    let err: FlagFormError = library::flag_form("").unwrap_err();
+    assert_eq!(err.kind(), FlagFormErrorKind::StdIo);
+    assert_eq!(
+        FlagFormError::kinds().collect::<Vec<_>>(),
+        [FlagFormErrorKind::StdIo, FlagFormErrorKind::ParseInt]
+    );
+}
+
+#[test]
+fn flattening() {
+    assert_eq!(library::flattened("3").unwrap(), 3);
+    assert_eq!(
+        library::flattened("x").unwrap_err().kind(),
+        library::FlattenedErrorKind::ParseInt
+    );
+
+    let result: Result<i32, Result<library::InnerError, library::ParseError>> =
+        library::parse("x").try_flatten();
+    assert!(matches!(result, Err(Ok(library::InnerError::ParseInt(_)))));
+    let result: Result<i32, Result<library::InnerError, library::ParseError>> =
+        library::parse("").try_flatten();
+    assert_eq!(
+        result.unwrap_err().unwrap_err().kind(),
+        ParseErrorKind::StdIo
+    );
+}
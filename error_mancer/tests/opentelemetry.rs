@@ -0,0 +1,81 @@
+#![cfg(feature = "opentelemetry")]
+
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+use opentelemetry::trace::{Status, Tracer, TracerProvider};
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider, SpanData};
+
+#[errors(
+    #[otel_status = Unset] std::fmt::Error,
+    ParseIntError,
+    group Lookup { #[otel_status = Ok] &'static str as Missing },
+    opentelemetry
+)]
+fn parse(x: &str) -> Result<i32, _> {
+    match x {
+        "fmt" => Err(std::fmt::Error)?,
+        "missing" => Err("missing")?,
+        _ => Ok(x.parse()?),
+    }
+}
+
+/// Run `f` in a span and return the finished span.
+fn in_span(f: impl FnOnce()) -> SpanData {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    provider.tracer("test").in_span("parse", |_| f());
+    let mut spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    spans.remove(0)
+}
+
+#[test]
+fn records_errors_on_the_active_span() {
+    let span = in_span(|| {
+        parse("x").unwrap_err();
+    });
+    assert_eq!(span.status, Status::error("invalid digit found in string"));
+    let event = &span.events.events[0];
+    assert_eq!(event.name, "exception");
+    assert!(event
+        .attributes
+        .iter()
+        .any(|attribute| attribute.value.as_str() == "invalid digit found in string"));
+}
+
+#[test]
+fn status_per_variant() {
+    let span = in_span(|| {
+        parse("fmt").unwrap_err();
+    });
+    assert_eq!(span.status, Status::Unset);
+    assert_eq!(span.events.len(), 1);
+
+    let span = in_span(|| {
+        parse("missing").unwrap_err();
+    });
+    assert_eq!(span.status, Status::Ok);
+    assert_eq!(span.events.len(), 1);
+}
+
+#[test]
+fn nothing_recorded_on_success() {
+    let span = in_span(|| {
+        parse("1").unwrap();
+    });
+    assert_eq!(span.status, Status::Unset);
+    assert!(span.events.is_empty());
+}
+
+#[test]
+fn otel_status() {
+    assert_eq!(parse("fmt").unwrap_err().otel_status(), Status::Unset);
+    assert_eq!(parse("missing").unwrap_err().otel_status(), Status::Ok);
+    assert_eq!(
+        parse("x").unwrap_err().otel_status(),
+        Status::error("invalid digit found in string")
+    );
+}
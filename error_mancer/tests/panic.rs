@@ -0,0 +1,30 @@
+#![cfg(feature = "prototyping")]
+
+use error_mancer::prelude::*;
+
+#[errors(std::num::ParseIntError, panic)]
+fn parse(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+#[errors(std::num::ParseIntError, panic)]
+async fn parse_async(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+#[test]
+fn ok_is_returned() {
+    assert_eq!(parse("42").unwrap(), 42);
+}
+
+#[test]
+#[should_panic(expected = "`parse` returned an error: StdNumParseInt(ParseIntError")]
+fn error_panics() {
+    let _: Result<i32, ParseError> = parse("abc");
+}
+
+#[tokio::test]
+#[should_panic(expected = "`parse_async` returned an error")]
+async fn async_error_panics() {
+    let _ = parse_async("abc").await;
+}
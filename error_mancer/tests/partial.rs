@@ -0,0 +1,76 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[derive(Debug, thiserror::Error)]
+#[error("input ended early")]
+struct TruncatedError;
+
+// Bad lines are skipped, a missing terminator fails the whole import.
+#[errors(ParseIntError, TruncatedError)]
+fn import(lines: &[&str]) -> Result<Partial<Vec<i32>, _>, _> {
+    let Some((&"end", lines)) = lines.split_last() else {
+        Err(TruncatedError)?
+    };
+
+    let mut imported = Partial::new(Vec::new());
+    for line in lines {
+        match line.parse() {
+            Ok(value) => imported.value.push(value),
+            Err(err) => imported.push_err(err),
+        }
+    }
+    Ok(imported)
+}
+
+#[errors(ParseIntError)]
+fn import_all(lines: &[&str]) -> Result<Partial<Vec<i32>, _>, _> {
+    let first = lines.first().map_or(Ok(0), |line| line.parse::<i32>())?;
+    let mut imported: Partial<Vec<i32>, _> = lines
+        .iter()
+        .skip(1)
+        .map(|line| line.parse::<i32>())
+        .collect_partial();
+    imported.value.insert(0, first);
+    Ok(imported)
+}
+
+#[test]
+fn non_fatal_errors_are_collected() {
+    let imported = import(&["1", "x", "3", "end"]).unwrap();
+    assert_eq!(imported.value, [1, 3]);
+    assert!(matches!(imported.errors[..], [ImportError::ParseInt(_)]));
+    assert!(!imported.is_complete());
+    assert!(import(&["1", "x", "3", "end"])
+        .unwrap()
+        .into_result(0)
+        .is_err());
+    assert_eq!(
+        import(&["1", "x", "3", "end"])
+            .unwrap()
+            .into_result(1)
+            .unwrap(),
+        [1, 3]
+    );
+}
+
+#[test]
+fn fatal_errors_are_returned() {
+    assert!(matches!(
+        import(&["1", "x"]),
+        Err(ImportError::Truncated(_))
+    ));
+    assert!(matches!(import(&[]), Err(ImportError::Truncated(_))));
+    assert!(import(&["1", "end"]).unwrap().is_complete());
+}
+
+#[test]
+fn collect_partial_converts_errors() {
+    let imported = import_all(&["1", "2", "y", "4"]).unwrap();
+    assert_eq!(imported.value, [1, 2, 4]);
+    assert!(matches!(imported.errors[..], [ImportAllError::ParseInt(_)]));
+    assert!(matches!(
+        import_all(&["x", "2"]),
+        Err(ImportAllError::ParseInt(_))
+    ));
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "std")]
+
+use std::num::ParseIntError;
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+
+use error_mancer::prelude::*;
+use error_mancer::Poisoned;
+
+#[errors(poison, ParseIntError)]
+fn add(total: &Mutex<i32>, value: &str) -> Result<i32, _> {
+    let mut total = total.lock()?;
+    *total += value.parse::<i32>()?;
+    Ok(*total)
+}
+
+#[errors(poison)]
+fn read(value: &RwLock<String>) -> Result<usize, _> {
+    Ok(value.read()?.len())
+}
+
+#[errors(group Lock { poison, std::io::Error })]
+fn grouped(total: &Mutex<i32>) -> Result<i32, _> {
+    Ok(*total.lock()?)
+}
+
+#[test]
+fn poisoned_lock() {
+    let total = Arc::new(Mutex::new(0));
+    assert_eq!(add(&total, "2").unwrap(), 2);
+    assert!(matches!(add(&total, "x"), Err(AddError::ParseInt(_))));
+
+    let shared = Arc::clone(&total);
+    let result = thread::spawn(move || {
+        let _guard = shared.lock().unwrap();
+        panic!("poisoning the mutex");
+    })
+    .join();
+    assert!(result.is_err());
+    assert!(matches!(
+        add(&total, "1"),
+        Err(AddError::Poisoned(Poisoned))
+    ));
+    assert!(matches!(
+        grouped(&total),
+        Err(GroupedError::Lock(GroupedLockError::Poisoned(_)))
+    ));
+    assert_eq!(
+        add(&total, "1").unwrap_err().to_string(),
+        "poisoned lock: another task failed inside"
+    );
+
+    let text = Arc::new(RwLock::new(String::from("abc")));
+    assert_eq!(read(&text).unwrap(), 3);
+    let shared = Arc::clone(&text);
+    let result = thread::spawn(move || {
+        let _guard = shared.write().unwrap();
+        panic!("poisoning the lock");
+    })
+    .join();
+    assert!(result.is_err());
+    assert!(matches!(read(&text), Err(ReadError::Poisoned(_))));
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "provide")]
+#![feature(error_generic_member_access)]
+
+use std::backtrace::Backtrace;
+use std::error::{request_ref, Error, Request};
+use std::fmt;
+
+use error_mancer::prelude::*;
+
+#[derive(Debug)]
+struct WithBacktrace {
+    backtrace: Backtrace,
+}
+
+impl fmt::Display for WithBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("with backtrace")
+    }
+}
+
+impl Error for WithBacktrace {
+    fn provide<'a>(&'a self, request: &mut Request<'a>) {
+        request.provide_ref::<Backtrace>(&self.backtrace);
+    }
+}
+
+fn fail() -> Result<(), WithBacktrace> {
+    Err(WithBacktrace {
+        backtrace: Backtrace::force_capture(),
+    })
+}
+
+#[errors(WithBacktrace, anyhow::Error)]
+fn foo() -> Result<(), _> {
+    fail()?;
+    Ok(())
+}
+
+#[errors((WithBacktrace, std::io::Error) as Inner)]
+fn grouped() -> Result<(), _> {
+    fail()?;
+    Ok(())
+}
+
+#[errors(WithBacktrace, anyhow::Error)]
+fn non_error_payload() -> Result<(), _> {
+    Err(anyhow::anyhow!("no backtrace here"))?;
+    Ok(())
+}
+
+#[test]
+fn backtrace_is_forwarded() {
+    let err = foo().unwrap_err();
+    assert!(request_ref::<Backtrace>(&err).is_some());
+}
+
+#[test]
+fn backtrace_is_forwarded_through_groups() {
+    let err = grouped().unwrap_err();
+    assert!(request_ref::<Backtrace>(&err).is_some());
+}
+
+#[test]
+fn payload_without_error_impl_provides_nothing() {
+    let err = non_error_payload().unwrap_err();
+    assert!(request_ref::<Backtrace>(&err).is_none());
+}
@@ -0,0 +1,23 @@
+use core::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+struct Input(String);
+
+#[errors]
+impl Input {
+    #[errors(ParseIntError)]
+    fn consume(self: Box<Self>) -> Result<i32, _> {
+        let Input(text) = *self;
+        Ok(text.parse()?)
+    }
+}
+
+#[test]
+fn boxed_self() {
+    let input = Box::new(Input(String::from("10")));
+    assert_eq!(input.consume().unwrap(), 10);
+
+    let input = Box::new(Input(String::from("abc")));
+    assert!(matches!(input.consume(), Err(ConsumeError::ParseInt(_))));
+}
@@ -0,0 +1,51 @@
+use std::error::Error;
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("cant connect to postgres://admin:hunter2@db")]
+struct DbError(#[source] std::io::Error);
+
+#[errors(redact DbError, ParseIntError, std::io::Error redact)]
+fn connect(x: i32) -> Result<(), _> {
+    match x {
+        0 => Err(DbError(std::io::Error::other("token=abc")))?,
+        1 => Err("x".parse::<i32>().unwrap_err())?,
+        _ => Err(std::io::Error::other("token=abc"))?,
+    }
+}
+
+#[errors(#[display = "database error"] redact DbError)]
+fn described() -> Result<(), _> {
+    Err(DbError(std::io::Error::other("token=abc")))?
+}
+
+#[test]
+fn display_is_redacted() {
+    assert_eq!(connect(0).unwrap_err().to_string(), "Db (details redacted)");
+    assert_eq!(
+        connect(2).unwrap_err().to_string(),
+        "StdIo (details redacted)"
+    );
+    assert_eq!(described().unwrap_err().to_string(), "database error");
+}
+
+#[test]
+fn debug_and_source_are_redacted() {
+    let err = connect(0).unwrap_err();
+    assert_eq!(format!("{err:?}"), "Db(<redacted>)");
+    assert!(err.source().is_none());
+    assert!(described().unwrap_err().source().is_none());
+}
+
+#[test]
+fn other_variants_unchanged() {
+    let err = connect(1).unwrap_err();
+    assert_eq!(err.to_string(), "invalid digit found in string");
+    assert_eq!(
+        format!("{err:?}"),
+        "ParseInt(ParseIntError { kind: InvalidDigit })"
+    );
+}
@@ -0,0 +1,29 @@
+mod errors {
+    pub use super::parsers::{ParseError, ReadError};
+}
+
+mod parsers {
+    use error_mancer::prelude::*;
+
+    #[errors(std::num::ParseIntError, reexport = crate::errors)]
+    pub fn parse(x: &str) -> Result<i32, _> {
+        Ok(x.parse()?)
+    }
+
+    #[errors(std::io::Error, reexport = crate::errors)]
+    pub fn read(path: &str) -> Result<String, ReadError> {
+        Ok(std::fs::read_to_string(path)?)
+    }
+}
+
+#[test]
+fn reexported() {
+    assert!(matches!(
+        parsers::parse("x"),
+        Err(errors::ParseError::StdNumParseInt(_))
+    ));
+    assert!(matches!(
+        parsers::read("/does/not/exist"),
+        Err(errors::ReadError::StdIo(_))
+    ));
+}
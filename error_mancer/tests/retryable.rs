@@ -0,0 +1,40 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[errors(std::io::Error retryable, ParseIntError)]
+fn foo(x: &str) -> Result<i32, _> {
+    if x.is_empty() {
+        Err(std::io::Error::other("timed out"))?;
+    }
+    Ok(x.parse()?)
+}
+
+#[errors(
+    (std::io::Error retryable, std::fmt::Error) as Output,
+    ParseIntError as Parse retryable,
+    std::str::Utf8Error,
+)]
+fn bar(x: i32) -> Result<(), _> {
+    match x {
+        0 => Err(std::io::Error::other("timed out"))?,
+        1 => Err(std::fmt::Error)?,
+        2 => Err("abc".parse::<i32>().unwrap_err())?,
+        _ => Err(String::from_utf8(vec![0xff]).unwrap_err().utf8_error())?,
+    }
+    Ok(())
+}
+
+#[test]
+fn classification() {
+    assert!(foo("").unwrap_err().retryable());
+    assert!(!foo("abc").unwrap_err().retryable());
+}
+
+#[test]
+fn classification_with_groups() {
+    assert!(bar(0).unwrap_err().retryable());
+    assert!(!bar(1).unwrap_err().retryable());
+    assert!(bar(2).unwrap_err().retryable());
+    assert!(!bar(3).unwrap_err().retryable());
+}
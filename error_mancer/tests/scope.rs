@@ -0,0 +1,51 @@
+use std::num::ParseIntError;
+use std::thread;
+
+use error_mancer::prelude::*;
+
+#[errors(ParseIntError, std::io::Error)]
+fn sum(values: &[&str]) -> Result<i32, _> {
+    thread::scope(|scope| {
+        let handles = values
+            .iter()
+            .map(|value| {
+                errors_in_scope!(
+                    scope,
+                    #[errors(ParseIntError)]
+                    move || -> Result<i32, _> { Ok(value.parse()?) }
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut sum = 0;
+        for handle in handles {
+            sum += handle.join().unwrap().into_super_error::<SumError>()?;
+        }
+        Ok(sum)
+    })
+}
+
+#[test]
+fn spawned_in_scope() {
+    assert_eq!(sum(&["1", "2", "3"]).unwrap(), 6);
+    assert!(matches!(sum(&["1", "x"]), Err(SumError::ParseInt(_))));
+}
+
+#[test]
+fn borrows_from_scope() {
+    let mut values = Vec::new();
+    let result = thread::scope(|scope| {
+        errors_in_scope!(
+            scope,
+            #[errors(std::io::Error)]
+            || {
+                values.push(1);
+                Ok(())
+            }
+        )
+        .join()
+        .unwrap()
+    });
+    assert!(result.is_ok());
+    assert_eq!(values, [1]);
+}
@@ -0,0 +1,145 @@
+#![cfg(feature = "serde")]
+
+use error_mancer::prelude::*;
+use serde::ser::{self, Impossible, Serialize, Serializer};
+
+#[derive(Debug, thiserror::Error)]
+#[error("{0} isnt a single digit")]
+struct NotADigit(u64);
+
+#[errors(NotADigit, String, serde_error)]
+fn write_digit(out: &mut String, value: u64) -> Result<(), DigitError> {
+    let digit = char::from_digit(value.try_into().unwrap_or(u32::MAX), 10);
+    out.push(digit.ok_or(NotADigit(value))?);
+    Ok(())
+}
+
+/// A toy format which writes numbers as single digits.
+struct DigitSerializer<'a>(&'a mut String);
+
+macro_rules! unsupported {
+    ($($method:ident($($ty:ty),*) -> $ok:ty;)*) => {
+        $(
+            fn $method(self, $(_: $ty),*) -> Result<$ok, DigitError> {
+                Err(ser::Error::custom(format_args!("`{}` isnt supported", stringify!($method))))
+            }
+        )*
+    };
+}
+
+impl Serializer for DigitSerializer<'_> {
+    type Ok = ();
+    type Error = DigitError;
+    type SerializeSeq = Impossible<(), DigitError>;
+    type SerializeTuple = Impossible<(), DigitError>;
+    type SerializeTupleStruct = Impossible<(), DigitError>;
+    type SerializeTupleVariant = Impossible<(), DigitError>;
+    type SerializeMap = Impossible<(), DigitError>;
+    type SerializeStruct = Impossible<(), DigitError>;
+    type SerializeStructVariant = Impossible<(), DigitError>;
+
+    fn serialize_u8(self, v: u8) -> Result<(), DigitError> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), DigitError> {
+        write_digit(self.0, v)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), DigitError> {
+        value.serialize(self)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, _: &T) -> Result<(), DigitError> {
+        Err(ser::Error::custom("options arent supported"))
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), DigitError> {
+        Err(ser::Error::custom("enums arent supported"))
+    }
+
+    unsupported! {
+        serialize_bool(bool) -> ();
+        serialize_i8(i8) -> ();
+        serialize_i16(i16) -> ();
+        serialize_i32(i32) -> ();
+        serialize_i64(i64) -> ();
+        serialize_u16(u16) -> ();
+        serialize_u32(u32) -> ();
+        serialize_f32(f32) -> ();
+        serialize_f64(f64) -> ();
+        serialize_char(char) -> ();
+        serialize_str(&str) -> ();
+        serialize_bytes(&[u8]) -> ();
+        serialize_none() -> ();
+        serialize_unit() -> ();
+        serialize_unit_struct(&'static str) -> ();
+        serialize_unit_variant(&'static str, u32, &'static str) -> ();
+        serialize_seq(Option<usize>) -> Self::SerializeSeq;
+        serialize_tuple(usize) -> Self::SerializeTuple;
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant;
+        serialize_map(Option<usize>) -> Self::SerializeMap;
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct;
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant;
+    }
+}
+
+/// Only serializable when even, failing with a `custom` message otherwise.
+struct Even(u64);
+
+impl Serialize for Even {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0 % 2 != 0 {
+            return Err(ser::Error::custom(format_args!("{} is odd", self.0)));
+        }
+        serializer.serialize_u64(self.0)
+    }
+}
+
+fn to_digit(value: &impl Serialize) -> Result<String, DigitError> {
+    let mut out = String::new();
+    value.serialize(DigitSerializer(&mut out))?;
+    Ok(out)
+}
+
+#[test]
+fn serializer_errors() {
+    assert_eq!(to_digit(&7u8).unwrap(), "7");
+    assert_eq!(to_digit(&Even(4)).unwrap(), "4");
+
+    assert!(matches!(
+        to_digit(&12u8),
+        Err(DigitError::NotADigit(NotADigit(12)))
+    ));
+    assert!(matches!(
+        to_digit(&Even(3)),
+        Err(DigitError::String(message)) if message == "3 is odd"
+    ));
+    assert!(matches!(
+        to_digit(&"text"),
+        Err(DigitError::String(message)) if message == "`serialize_str` isnt supported"
+    ));
+}
+
+#[test]
+fn deserializer_error() {
+    use serde::de::{Error, Unexpected};
+
+    let err = DigitError::invalid_type(Unexpected::Bool(true), &"a digit");
+    assert_eq!(
+        err.to_string(),
+        "invalid type: boolean `true`, expected a digit"
+    );
+    assert!(matches!(err, DigitError::String(_)));
+}
@@ -0,0 +1,37 @@
+use core::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[errors(ParseIntError, span_trace)]
+fn parse(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+#[test]
+fn variant_holds_trace() {
+    let err = parse("abc").unwrap_err();
+    assert!(matches!(err, ParseError::ParseInt(_, _)));
+    let _ = err.span_trace();
+    assert_eq!(err.to_string(), "invalid digit found in string");
+}
+
+#[cfg(not(feature = "tracing-error"))]
+#[test]
+fn zero_sized_without_feature() {
+    assert_eq!(size_of::<ParseError>(), size_of::<ParseIntError>());
+}
+
+#[cfg(feature = "tracing-error")]
+#[test]
+fn captures_span() {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let subscriber = tracing_subscriber::registry().with(tracing_error::ErrorLayer::default());
+    tracing::subscriber::with_default(subscriber, || {
+        let span = tracing::info_span!("loading_config");
+        let _guard = span.enter();
+
+        let err = parse("abc").unwrap_err();
+        assert!(err.span_trace().to_string().contains("loading_config"));
+    });
+}
@@ -0,0 +1,135 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[derive(Debug)]
+struct Timeout;
+
+impl Display for Timeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("timed out")
+    }
+}
+
+impl Error for Timeout {}
+
+#[errors(std::io::Error, Timeout, ParseIntError, group Validation { &'static str as Message })]
+fn fetch(input: &str) -> Result<i32, _> {
+    match input {
+        "io" => Err(std::io::Error::other("disk"))?,
+        "timeout" => Err(Timeout)?,
+        "" => Err("empty")?,
+        _ => {}
+    }
+    Ok(input.parse()?)
+}
+
+split_variants!(FetchError; pub Recoverable = (StdIo, Timeout), pub Fatal = (ParseInt, Validation));
+
+#[test]
+fn splits_into_sub_enums() {
+    let split: Result<Recoverable, Fatal> = fetch("io").unwrap_err().into();
+    assert!(matches!(split, Ok(Recoverable::StdIo(_))));
+
+    let split: Result<Recoverable, Fatal> = fetch("timeout").unwrap_err().into();
+    assert!(matches!(split, Ok(Recoverable::Timeout(Timeout))));
+
+    let split: Result<Recoverable, Fatal> = fetch("x").unwrap_err().into();
+    assert!(matches!(split, Err(Fatal::ParseInt(_))));
+
+    let split: Result<Recoverable, Fatal> = fetch("").unwrap_err().into();
+    assert!(matches!(
+        split,
+        Err(Fatal::Validation(FetchValidationError::Message("empty")))
+    ));
+}
+
+#[test]
+fn sub_enums_are_errors() {
+    let Ok(err) = Result::<Recoverable, Fatal>::from(fetch("timeout").unwrap_err()) else {
+        panic!("expected a recoverable error");
+    };
+    assert_eq!(err.to_string(), "timed out");
+    assert_eq!(format!("{err:?}"), "Timeout(Timeout)");
+
+    let Err(err) = Result::<Recoverable, Fatal>::from(fetch("x").unwrap_err()) else {
+        panic!("expected a fatal error");
+    };
+    let boxed: Box<dyn Error> = Box::new(err);
+    assert_eq!(boxed.to_string(), "invalid digit found in string");
+}
+
+/// Recoverable errors fall back to `0`.
+#[errors(Fatal)]
+fn fetch_or_default(input: &str) -> Result<i32, _> {
+    match fetch(input) {
+        Ok(value) => Ok(value),
+        Err(err) => match Result::<Recoverable, Fatal>::from(err) {
+            Ok(_) => Ok(0),
+            Err(fatal) => Err(fatal)?,
+        },
+    }
+}
+
+#[test]
+fn merges_back() {
+    let Err(fatal) = Result::<Recoverable, Fatal>::from(fetch("x").unwrap_err()) else {
+        panic!("expected a fatal error");
+    };
+    assert!(matches!(FetchError::from(fatal), FetchError::ParseInt(_)));
+
+    let Ok(recoverable) = Result::<Recoverable, Fatal>::from(fetch("io").unwrap_err()) else {
+        panic!("expected a recoverable error");
+    };
+    assert!(matches!(
+        FetchError::from(recoverable),
+        FetchError::StdIo(_)
+    ));
+
+    assert_eq!(fetch_or_default("timeout").unwrap(), 0);
+    assert!(matches!(
+        fetch_or_default("x"),
+        Err(FetchOrDefaultError::Fatal(Fatal::ParseInt(_)))
+    ));
+}
+
+#[errors(ParseIntError, allow_extra = Other)]
+fn with_extra(input: &str) -> Result<i32, _> {
+    Ok(input.parse()?)
+}
+
+split_variants!(WithExtraError; Parsing = (ParseInt), Unknown = (Other));
+
+#[test]
+fn extra_variant() {
+    let split = Result::<Parsing, Unknown>::from(WithExtraError::Other("boom".into()));
+    let Err(Unknown::Other(err)) = split else {
+        panic!("expected the extra variant");
+    };
+    assert_eq!(err.to_string(), "boom");
+    assert!(matches!(
+        Result::<Parsing, Unknown>::from(with_extra("x").unwrap_err()),
+        Ok(Parsing::ParseInt(_))
+    ));
+}
+
+#[errors(std::fmt::Error, std::num::ParseIntError)]
+fn nested() -> Result<(), _> {
+    Ok(())
+}
+
+mod split {
+    use error_mancer::prelude::*;
+
+    split_variants!(super::NestedError; pub Formatting = (StdFmt), pub Parsing = (StdNumParseInt,));
+}
+
+#[test]
+fn split_in_child_module() {
+    assert!(nested().is_ok());
+    let split =
+        Result::<split::Formatting, split::Parsing>::from(NestedError::StdFmt(std::fmt::Error));
+    assert!(matches!(split, Ok(split::Formatting::StdFmt(_))));
+}
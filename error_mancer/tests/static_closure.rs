@@ -0,0 +1,35 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+/// Parses a number.
+#[errors(ParseIntError)]
+static PARSE_NUMBER: fn(&str) -> Result<i32, _> = |x| Ok(x.parse()?);
+
+#[errors(ParseIntError, std::fmt::Error)]
+pub static DOUBLE: fn(&str) -> Result<i32, _> = |x| -> Result<i32, _> {
+    let x = PARSE_NUMBER(x).into_super_error::<DoubleError>()?;
+    Ok(x * 2)
+};
+
+#[errors(ParseIntError)]
+static NAMED: fn() -> Result<(), NamedParseError> = || {
+    "x".parse::<i32>()?;
+    Ok(())
+};
+
+#[test]
+fn static_closure() {
+    assert_eq!(PARSE_NUMBER("10").unwrap(), 10);
+    assert!(matches!(
+        PARSE_NUMBER("x"),
+        Err(ParseNumberError::ParseInt(_))
+    ));
+    assert_eq!(DOUBLE("10").unwrap(), 20);
+    assert!(matches!(DOUBLE("x"), Err(DoubleError::ParseInt(_))));
+}
+
+#[test]
+fn explicit_name() {
+    assert!(matches!(NAMED(), Err(NamedParseError::ParseInt(_))));
+}
@@ -0,0 +1,25 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[errors(ParseIntError, use_std_error)]
+fn foo(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+#[errors((ParseIntError, std::fmt::Error) as Inner, allow_extra = Other, use_std_error)]
+fn bar(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+fn assert_std_error<E: std::error::Error>(_: &E) {}
+
+#[test]
+fn implements_std_error() {
+    let err = foo("x").unwrap_err();
+    assert_std_error(&err);
+    assert_eq!(err.to_string(), "invalid digit found in string");
+
+    let err: Box<dyn std::error::Error> = Box::new(bar("x").unwrap_err());
+    assert_eq!(err.to_string(), "invalid digit found in string");
+}
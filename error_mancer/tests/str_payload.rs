@@ -0,0 +1,34 @@
+use std::error::Error;
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[errors(&'static str, ParseIntError)]
+fn parse(x: &str) -> Result<i32, _> {
+    if x.is_empty() {
+        Err("empty input")?;
+    }
+    Ok(x.parse()?)
+}
+
+#[errors(&'static str)]
+fn only_str() -> Result<(), _> {
+    Err("always fails")?
+}
+
+#[test]
+fn str_variant() {
+    let err = parse("").unwrap_err();
+    assert!(matches!(err, ParseError::Str("empty input")));
+    assert_eq!(err.to_string(), "empty input");
+    assert_eq!(format!("{err:?}"), r#"Str("empty input")"#);
+    assert!(err.source().is_none());
+    assert!(matches!(parse("x"), Err(ParseError::ParseInt(_))));
+}
+
+#[test]
+fn from_str() {
+    let err = ParseError::from("manual");
+    assert!(matches!(err, ParseError::Str("manual")));
+    assert!(matches!(only_str(), Err(OnlyStrError::Str("always fails"))));
+}
@@ -0,0 +1,68 @@
+use std::error::Error;
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[derive(Debug, thiserror::Error)]
+#[error("config couldnt be read")]
+struct ConfigError(#[source] std::io::Error);
+
+#[errors(ConfigError, ParseIntError, #[display = "not a number"] std::num::ParseFloatError)]
+fn load(kind: u8) -> Result<f32, _> {
+    match kind {
+        0 => Err(ConfigError(std::io::Error::other("disk on fire")))?,
+        1 => Ok(f32::from("x".parse::<u8>()?)),
+        _ => Ok("y".parse()?),
+    }
+}
+
+#[errors((ConfigError, ParseIntError) as Inner)]
+fn grouped() -> Result<(), _> {
+    Err(ConfigError(std::io::Error::other("disk on fire")))?
+}
+
+/// Every message in the chain, starting with `err`.
+fn chain(err: &(dyn Error + 'static)) -> Vec<String> {
+    std::iter::successors(Some(err), |&err| err.source())
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn boxed() {
+    let err: Box<dyn Error + Send + Sync> = Box::new(load(0).unwrap_err());
+    assert_eq!(err.to_string(), "config couldnt be read");
+    assert_eq!(chain(&*err), ["config couldnt be read", "disk on fire"]);
+    assert!(err.downcast_ref::<LoadError>().is_some());
+}
+
+#[test]
+fn borrowed() {
+    let err = load(1).unwrap_err();
+    let err: &dyn Error = &err;
+    assert_eq!(err.to_string(), "invalid digit found in string");
+    assert!(err.source().is_none());
+}
+
+#[test]
+fn custom_display_sources_the_payload() {
+    let err = load(2).unwrap_err();
+    let err: &(dyn Error + 'static) = &err;
+    assert_eq!(chain(err), ["not a number", "invalid float literal"]);
+    assert!(err.source().unwrap().is::<std::num::ParseFloatError>());
+}
+
+#[test]
+fn question_mark_into_box() {
+    fn run() -> Result<(), Box<dyn Error>> {
+        grouped()?;
+        Ok(())
+    }
+    let err = run().unwrap_err();
+    assert_eq!(chain(&*err), ["config couldnt be read", "disk on fire"]);
+    let Some(GroupedError::Inner(inner)) = err.downcast_ref::<GroupedError>() else {
+        panic!("expected `GroupedError::Inner`");
+    };
+    let inner: &dyn Error = inner;
+    assert_eq!(inner.to_string(), "config couldnt be read");
+}
@@ -0,0 +1,81 @@
+use core::fmt;
+
+use error_mancer::prelude::*;
+
+trait DiagnosticInfo: fmt::Debug {
+    fn code(&self) -> u32;
+}
+
+#[derive(Debug)]
+struct Timeout;
+
+impl DiagnosticInfo for Timeout {
+    fn code(&self) -> u32 {
+        504
+    }
+}
+
+#[allow(clippy::borrowed_box)]
+fn format_diagnostic(
+    err: &Box<dyn DiagnosticInfo + Send>,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    write!(f, "diagnostic {}", err.code())
+}
+
+fn diagnose() -> Result<(), Box<dyn DiagnosticInfo + Send>> {
+    Err(Box::new(Timeout))
+}
+
+#[errors(Box<dyn DiagnosticInfo + Send> as Diagnostic)]
+fn foo() -> Result<(), _> {
+    diagnose()?;
+    Ok(())
+}
+
+#[errors(
+    #[display_with = format_diagnostic]
+    Box<dyn DiagnosticInfo + Send> as Diagnostic
+)]
+fn custom_display() -> Result<(), _> {
+    diagnose()?;
+    Ok(())
+}
+
+#[errors(
+    #[display = "something went wrong"]
+    Box<dyn DiagnosticInfo + Send> as Diagnostic
+)]
+fn static_display() -> Result<(), _> {
+    diagnose()?;
+    Ok(())
+}
+
+#[test]
+fn propagate_and_match() {
+    let Err(FooError::Diagnostic(diagnostic)) = foo() else {
+        panic!("expected diagnostic");
+    };
+    assert_eq!(diagnostic.code(), 504);
+}
+
+#[test]
+fn display() {
+    assert_eq!(foo().unwrap_err().to_string(), "Diagnostic");
+    assert_eq!(custom_display().unwrap_err().to_string(), "diagnostic 504");
+    assert_eq!(
+        static_display().unwrap_err().to_string(),
+        "something went wrong"
+    );
+}
+
+#[errors(Box<dyn std::error::Error + Send + Sync> as Boxed)]
+fn boxed_error() -> Result<(), _> {
+    Err(Box::<dyn std::error::Error + Send + Sync>::from("boxed"))?;
+    Ok(())
+}
+
+#[test]
+fn dyn_error_delegates() {
+    assert_eq!(boxed_error().unwrap_err().to_string(), "boxed");
+}
@@ -0,0 +1,27 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[derive(Debug)]
+struct Missing;
+
+impl std::fmt::Display for Missing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("missing value")
+    }
+}
+
+#[errors(ParseIntError, Missing)]
+fn first(values: &[&str]) -> Result<i32, _> {
+    values
+        .first()
+        .map(|value| value.parse::<i32>().map_err(FirstError::from))
+        .transpose_with(|| Missing.into())
+}
+
+#[test]
+fn transpose_with_none_error() {
+    assert_eq!(first(&["3"]).unwrap(), 3);
+    assert!(matches!(first(&["x"]), Err(FirstError::ParseInt(_))));
+    assert!(matches!(first(&[]), Err(FirstError::Missing(_))));
+}
@@ -0,0 +1,81 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("error 1")]
+struct Err1;
+
+#[derive(Error, Debug)]
+#[error("error 2")]
+struct Err2;
+
+#[errors(Err1, Err2, ParseIntError, try_flatten)]
+fn big(x: &str) -> Result<i32, _> {
+    match x {
+        "1" => Err(Err1)?,
+        "2" => Err(Err2)?,
+        _ => Ok(x.parse()?),
+    }
+}
+
+#[errors(Err1, try_flatten)]
+fn small() -> Result<(), _> {
+    Err(Err1)?
+}
+
+#[errors(Err1, ParseIntError, try_flatten)]
+fn partial() -> Result<(), _> {
+    Ok(())
+}
+
+#[errors((Err1, Err2) as Custom, ParseIntError, try_flatten)]
+fn grouped(x: &str) -> Result<i32, _> {
+    match x {
+        "1" => Err(Err1)?,
+        "2" => Err(Err2)?,
+        _ => Ok(x.parse()?),
+    }
+}
+
+#[test]
+fn big_into_small() {
+    assert!(matches!(big("10").try_flatten::<PartialError>(), Ok(10)));
+    assert!(matches!(
+        big("1").try_flatten::<PartialError>(),
+        Err(Ok(PartialError::Err1(Err1)))
+    ));
+    assert!(matches!(
+        big("x").try_flatten::<PartialError>(),
+        Err(Ok(PartialError::ParseInt(_)))
+    ));
+    assert!(matches!(
+        big("2").try_flatten::<PartialError>(),
+        Err(Err(BigError::Err2(Err2)))
+    ));
+}
+
+#[test]
+fn small_into_big() {
+    assert!(matches!(
+        small().try_flatten::<BigError>(),
+        Err(Ok(BigError::Err1(Err1)))
+    ));
+}
+
+#[test]
+fn groups() {
+    assert!(matches!(
+        grouped("1").try_flatten::<SmallError>(),
+        Err(Ok(SmallError::Err1(Err1)))
+    ));
+    assert!(matches!(
+        grouped("2").try_flatten::<SmallError>(),
+        Err(Err(GroupedError::Custom(GroupedCustomError::Err2(Err2))))
+    ));
+    assert!(matches!(
+        big("2").try_flatten::<GroupedError>(),
+        Err(Ok(GroupedError::Custom(GroupedCustomError::Err2(Err2))))
+    ));
+}
@@ -2,4 +2,21 @@
 fn ui() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/ui/*.rs");
+    t.pass("tests/ui/pass/*.rs");
+    #[cfg(not(feature = "const_closures"))]
+    t.compile_fail("tests/ui/no_const_closures/*.rs");
+    #[cfg(not(feature = "prototyping"))]
+    t.compile_fail("tests/ui/no_prototyping/*.rs");
+    #[cfg(not(feature = "serde"))]
+    t.compile_fail("tests/ui/no_serde/*.rs");
+    #[cfg(not(feature = "opentelemetry"))]
+    t.compile_fail("tests/ui/no_opentelemetry/*.rs");
+    #[cfg(not(feature = "yew"))]
+    t.compile_fail("tests/ui/no_yew/*.rs");
+    #[cfg(not(feature = "zbus"))]
+    t.compile_fail("tests/ui/no_zbus/*.rs");
+    #[cfg(not(feature = "nom"))]
+    t.compile_fail("tests/ui/no_nom/*.rs");
+    #[cfg(not(feature = "std"))]
+    t.compile_fail("tests/ui/no_std_feature/*.rs");
 }
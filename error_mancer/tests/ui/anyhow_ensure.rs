@@ -0,0 +1,12 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[errors(ParseIntError)]
+fn parse(x: &str) -> anyhow::Result<i32> {
+    let x: i32 = x.parse()?;
+    anyhow::ensure!(x > 0, "x must be positive");
+    Ok(x)
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use core::num::ParseIntError;
+
+use error_mancer::assert_not_allowed;
+use error_mancer::prelude::*;
+
+#[errors(ParseIntError)]
+fn foo() -> Result<i32, _> {
+    Ok("10".parse()?)
+}
+
+assert_not_allowed!(FooError, ParseIntError);
+
+fn main() {}
@@ -0,0 +1,25 @@
+use error_mancer::prelude::*;
+
+trait Load {
+    type Error;
+
+    fn load(&self) -> Result<i32, Self::Error>;
+    fn reload(&self) -> Result<i32, Self::Error>;
+}
+
+struct Number(&'static str);
+
+#[errors]
+impl Load for Number {
+    #[errors(std::num::ParseIntError)]
+    fn load(&self) -> Result<i32, Self::Error> {
+        Ok(self.0.parse()?)
+    }
+
+    #[errors(std::num::ParseIntError)]
+    fn reload(&self) -> Result<i32, Self::Error> {
+        Ok(self.0.parse()?)
+    }
+}
+
+fn main() {}
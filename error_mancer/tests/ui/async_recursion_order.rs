@@ -0,0 +1,13 @@
+use async_recursion::async_recursion;
+use error_mancer::prelude::*;
+
+#[async_recursion]
+#[errors(std::num::ParseIntError)]
+async fn sum(values: &[&str]) -> Result<i32, _> {
+    let Some((first, rest)) = values.split_first() else {
+        return Ok(0);
+    };
+    Ok(first.parse::<i32>()? + sum(rest).await?)
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use error_mancer::prelude::*;
+
+#[errors(boxed_self; std::num::ParseIntError)]
+fn plain_name(x: &str) -> Result<i32, ParseFailure> {
+    Ok(x.parse()?)
+}
+
+#[errors(boxed_self; std::num::ParseIntError)]
+fn passthrough(x: &str) -> anyhow::Result<i32> {
+    Ok(x.parse()?)
+}
+
+#[errors(boxed_self; none)]
+fn infallible() -> Result<i32, _> {
+    Ok(1)
+}
+
+#[errors(boxed_self; std::num::ParseIntError, enum_only)]
+fn by_hand(x: &str) -> Result<i32, Box<ByHandError>> {
+    Ok(x.parse().map_err(ByHandError::from)?)
+}
+
+fn main() {}
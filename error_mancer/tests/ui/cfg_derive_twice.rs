@@ -0,0 +1,8 @@
+use error_mancer::prelude::*;
+
+#[errors(std::fmt::Error, cfg_derive(test, Clone, PartialEq), cfg_derive(debug_assertions, PartialEq))]
+fn foo() -> Result<(), _> {
+    Ok(())
+}
+
+fn main() {}
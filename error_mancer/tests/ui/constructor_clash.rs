@@ -0,0 +1,12 @@
+use error_mancer::prelude::*;
+
+#[derive(Debug, thiserror::Error)]
+#[error("bad kind")]
+struct Kind;
+
+#[errors(Kind, kind)]
+fn foo() -> Result<(), _> {
+    Err(Kind.into())
+}
+
+fn main() {}
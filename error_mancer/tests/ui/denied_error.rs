@@ -0,0 +1,32 @@
+use error_mancer::prelude::*;
+
+#[derive(Debug)]
+struct DbError;
+
+impl std::fmt::Display for DbError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("db error")
+    }
+}
+
+impl std::error::Error for DbError {}
+
+fn query() -> Result<i32, DbError> {
+    Err(DbError)
+}
+
+#[errors(deny(DbError))]
+fn load(x: &str) -> anyhow::Result<i32> {
+    let value: i32 = x.parse()?;
+    Ok(value + query()?)
+}
+
+#[errors(deny(std::fmt::Error, DbError))]
+async fn load_async() -> anyhow::Result<()> {
+    if true {
+        Err(std::fmt::Error)?;
+    }
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use error_mancer::prelude::*;
+
+#[errors(std::io::Error, deny(std::fmt::Error))]
+fn listed() -> anyhow::Result<()> {
+    Ok(())
+}
+
+#[errors(deny(std::fmt::Error))]
+fn generated() -> Result<(), _> {
+    Ok(())
+}
+
+fn main() {}
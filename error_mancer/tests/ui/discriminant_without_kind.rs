@@ -0,0 +1,8 @@
+use error_mancer::prelude::*;
+
+#[errors(#[discriminant = 1] std::io::Error)]
+fn foo() -> Result<(), _> {
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use error_mancer::prelude::*;
+
+#[errors(#[display = "io"] std::io::Error, dispatch)]
+fn foo() -> Result<(), _> {
+    Ok(())
+}
+
+#[errors(std::io::Error, dispatch, display_context = "bar")]
+fn bar() -> Result<(), _> {
+    Ok(())
+}
+
+fn main() {}
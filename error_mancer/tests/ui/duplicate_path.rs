@@ -0,0 +1,28 @@
+#![deny(deprecated)]
+
+use error_mancer::prelude::*;
+
+mod io {
+    #[derive(Debug)]
+    pub struct Error;
+
+    impl std::fmt::Display for Error {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("io")
+        }
+    }
+
+    impl std::error::Error for Error {}
+}
+
+#[errors(std::io::Error, io::Error)]
+fn foo() -> Result<(), _> {
+    Ok(())
+}
+
+#[errors(std::fmt::Error, group Inner { std::fmt::Error })]
+fn bar() -> Result<(), _> {
+    Ok(())
+}
+
+fn main() {}
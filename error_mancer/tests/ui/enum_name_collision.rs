@@ -0,0 +1,24 @@
+use error_mancer::prelude::*;
+
+mod inner {
+    #[derive(Debug, thiserror::Error)]
+    #[error("parse failed")]
+    pub struct ParseError;
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("parse input failed")]
+    pub struct ParseInputError;
+}
+use inner::{ParseError, ParseInputError};
+
+#[errors(ParseError)]
+fn parse() -> Result<(), _> {
+    Ok(())
+}
+
+#[errors(group Input { ParseInputError }, std::io::Error)]
+fn parse_input() -> Result<(), _> {
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use error_mancer::prelude::*;
+
+#[derive(Debug)]
+struct FooError;
+
+#[errors(std::num::ParseIntError)]
+fn foo() -> Result<i32, _> {
+    Ok("1".parse()?)
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use error_mancer::prelude::*;
+
+#[errors(std::io::Error, enum_only, none)]
+fn foo() -> Result<(), FooError> {
+    Ok(())
+}
+
+#[errors(enum_only, deny(std::io::Error))]
+fn bar() -> anyhow::Result<()> {
+    Ok(())
+}
+
+fn main() {}
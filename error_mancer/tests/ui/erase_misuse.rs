@@ -0,0 +1,13 @@
+use error_mancer::prelude::*;
+
+#[errors(erase; std::io::Error)]
+fn named() -> Result<(), _> {
+    Ok(())
+}
+
+#[errors(erase; std::io::Error)]
+fn unlisted(x: &str) -> anyhow::Result<i32> {
+    Ok(x.parse()?)
+}
+
+fn main() {}
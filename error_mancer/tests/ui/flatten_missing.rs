@@ -0,0 +1,27 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[errors(std::io::Error, ParseIntError)]
+fn foo() -> Result<(), _> {
+    Ok(())
+}
+
+#[errors(std::io::Error)]
+fn one_missing() -> Result<(), _> {
+    foo().into_super_error::<OneMissingError>()?;
+    Ok(())
+}
+
+#[errors(std::io::Error, std::fmt::Error, core::str::Utf8Error)]
+fn bar() -> Result<(), _> {
+    Ok(())
+}
+
+#[errors(std::fmt::Error)]
+fn two_missing() -> Result<(), _> {
+    bar().into_super_error::<TwoMissingError>()?;
+    Ok(())
+}
+
+fn main() {}
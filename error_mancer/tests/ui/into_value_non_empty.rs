@@ -0,0 +1,10 @@
+use error_mancer::prelude::*;
+
+#[errors(std::num::ParseIntError)]
+fn parse(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+fn main() {
+    let _ = InfallibleResultExt::into_value(parse("1"));
+}
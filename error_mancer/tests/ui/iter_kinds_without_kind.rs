@@ -0,0 +1,8 @@
+use error_mancer::prelude::*;
+
+#[errors(std::io::Error, iter_kinds)]
+fn foo() -> Result<(), _> {
+    Ok(())
+}
+
+fn main() {}
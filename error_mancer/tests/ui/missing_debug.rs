@@ -0,0 +1,17 @@
+use error_mancer::prelude::*;
+
+struct Handle;
+
+impl std::fmt::Display for Handle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("handle")
+    }
+}
+
+#[errors(Handle)]
+fn foo() -> Result<(), _> {
+    Err(Handle)?;
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use error_mancer::prelude::*;
+
+#[derive(Debug)]
+struct Handle;
+
+#[errors(Handle)]
+fn foo() -> Result<(), _> {
+    Err(Handle)?;
+    Ok(())
+}
+
+fn main() {}
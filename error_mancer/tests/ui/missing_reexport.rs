@@ -0,0 +1,12 @@
+mod errors {}
+
+mod parsers {
+    use error_mancer::prelude::*;
+
+    #[errors(std::num::ParseIntError, reexport = crate::errors)]
+    pub fn read_count(x: &str) -> Result<i32, _> {
+        Ok(x.parse()?)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use core::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[errors]
+fn outer() -> Result<(), _> {
+    #[errors(ParseIntError)]
+    fn inner(x: &str) -> Result<i32, _> {
+        Ok(x.parse()?)
+    }
+
+    let _ = inner("10");
+    Ok(())
+}
+
+fn main() {
+    let _: Option<InnerError> = None;
+}
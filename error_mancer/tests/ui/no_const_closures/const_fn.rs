@@ -0,0 +1,8 @@
+use error_mancer::prelude::*;
+
+#[errors]
+const fn foo() -> Result<i32, _> {
+    Ok(10)
+}
+
+fn main() {}
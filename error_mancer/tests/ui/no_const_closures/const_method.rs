@@ -0,0 +1,13 @@
+use error_mancer::prelude::*;
+
+struct Byte;
+
+#[errors]
+impl Byte {
+    #[errors(std::num::TryFromIntError)]
+    const fn validate(x: u8) -> Result<u8, _> {
+        Ok(x)
+    }
+}
+
+fn main() {}
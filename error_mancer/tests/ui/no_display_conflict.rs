@@ -0,0 +1,13 @@
+use error_mancer::prelude::*;
+
+#[errors(std::num::ParseIntError, no_display, dispatch)]
+fn dispatched(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+#[errors(std::num::ParseIntError, no_display, impl_display_debug)]
+fn debug(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use error_mancer::prelude::*;
+
+#[derive(Debug)]
+struct Err1;
+
+impl core::fmt::Display for Err1 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("err1")
+    }
+}
+
+impl core::error::Error for Err1 {}
+
+#[errors(Err1, no_flatten)]
+fn foo() -> Result<(), _> {
+    Ok(())
+}
+
+#[errors(Err1)]
+fn bar() -> Result<(), _> {
+    foo().into_super_error::<BarError>()?;
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use error_mancer::prelude::*;
+
+#[errors(std::num::ParseIntError, panic)]
+fn foo(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+fn main() {}
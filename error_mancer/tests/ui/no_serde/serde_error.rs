@@ -0,0 +1,8 @@
+use error_mancer::prelude::*;
+
+#[errors(String, serde_error)]
+fn foo() -> Result<i32, _> {
+    Ok(1)
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use error_mancer::prelude::*;
+
+#[errors(poison)]
+fn foo(value: &std::sync::Mutex<i32>) -> Result<i32, _> {
+    Ok(*value.lock()?)
+}
+
+fn main() {}
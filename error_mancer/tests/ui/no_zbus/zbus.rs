@@ -0,0 +1,8 @@
+use error_mancer::prelude::*;
+
+#[errors(std::num::ParseIntError, zbus)]
+fn foo() -> Result<i32, _> {
+    Ok(1)
+}
+
+fn main() {}
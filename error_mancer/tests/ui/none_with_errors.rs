@@ -0,0 +1,13 @@
+use error_mancer::prelude::*;
+
+#[errors(std::io::Error, none)]
+fn foo() -> Result<(), _> {
+    Ok(())
+}
+
+#[errors(none)]
+fn bar() -> Result<(), BarError> {
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use error_mancer::prelude::*;
+
+#[errors(std::num::ParseIntError)]
+fn foo() -> Result<i32, _> {
+    std::fs::read_to_string("number.txt")?;
+    Ok("10".parse()?)
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+mod library {
+    use error_mancer::prelude::*;
+
+    #[errors(opaque; std::num::ParseIntError)]
+    pub fn parse(x: &str) -> Result<i32, _> {
+        Ok(x.parse()?)
+    }
+}
+
+fn main() {
+    let err = library::parse("x").unwrap_err();
+    let library::ParseError(_repr) = err;
+}
@@ -0,0 +1,47 @@
+#![deny(warnings)]
+
+use error_mancer::prelude::*;
+
+#[derive(Debug)]
+struct ParseError;
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("parse")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+mod chrono {
+    #[derive(Debug)]
+    pub struct ParseError;
+
+    impl std::fmt::Display for ParseError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("chrono")
+        }
+    }
+
+    impl std::error::Error for ParseError {}
+}
+
+#[derive(Debug)]
+struct Error;
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("error")
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[errors(ParseError, chrono::ParseError, Error, serde_json::Error)]
+fn read() -> Result<(), _> {
+    Ok(())
+}
+
+fn main() {
+    let _ = read();
+}
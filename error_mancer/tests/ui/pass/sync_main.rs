@@ -0,0 +1,9 @@
+use std::process::ExitCode;
+
+use error_mancer::prelude::*;
+
+#[errors(std::num::ParseIntError)]
+fn main() -> Result<ExitCode, _> {
+    let value: u8 = "0".parse()?;
+    Ok(ExitCode::from(value))
+}
@@ -0,0 +1,13 @@
+use error_mancer::prelude::*;
+
+async fn read() -> Result<String, std::io::Error> {
+    Ok(String::from("10"))
+}
+
+#[tokio::main(flavor = "current_thread")]
+#[errors(std::io::Error, std::num::ParseIntError)]
+async fn main() -> Result<(), _> {
+    let value: i32 = read().await?.parse()?;
+    assert_eq!(value, 10);
+    Ok(())
+}
@@ -0,0 +1,9 @@
+use error_mancer::prelude::*;
+
+#[errors(std::num::ParseIntError)]
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), _> {
+    let value: i32 = "10".parse()?;
+    assert_eq!(value, 10);
+    Ok(())
+}
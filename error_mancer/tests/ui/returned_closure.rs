@@ -0,0 +1,13 @@
+use error_mancer::prelude::*;
+
+#[errors(std::io::Error)]
+fn make_reader(path: String) -> impl FnOnce() -> Result<Vec<u8>, _> {
+    move || Ok(std::fs::read(path)?)
+}
+
+#[errors(std::io::Error)]
+fn boxed_reader(path: String) -> Box<dyn Fn() -> Result<Vec<u8>, _>> {
+    Box::new(move || Ok(std::fs::read(&path)?))
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use error_mancer::prelude::*;
+
+#[errors(std::fmt::Error, std::num::ParseIntError, std::num::TryFromIntError)]
+fn foo() -> Result<(), _> {
+    Ok(())
+}
+
+split_variants!(FooError; Formatting = (StdFmt), Parsing = (StdNumParseInt));
+
+split_variants!(FooError; Numbers = (StdNumParseInt, StdNumTryFromInt), Other = (StdFmt, StdNumParseInt));
+
+fn main() {}
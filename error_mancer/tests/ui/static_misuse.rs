@@ -0,0 +1,15 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+fn parse(x: &str) -> Result<i32, ParseIntError> {
+    x.parse()
+}
+
+#[errors(ParseIntError)]
+static NOT_A_CLOSURE: fn(&str) -> Result<i32, _> = parse;
+
+#[errors(ParseIntError)]
+static NOT_A_FN: &dyn Fn(&str) -> Result<i32, _> = &|x| Ok(x.parse()?);
+
+fn main() {}
@@ -0,0 +1,52 @@
+use std::error::Error;
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+use error_mancer::Union;
+
+#[errors(ParseIntError)]
+fn foo(x: &str) -> Result<i32, _> {
+    Ok(x.parse()?)
+}
+
+#[errors(std::fmt::Error)]
+fn bar(fail: bool) -> Result<(), _> {
+    if fail {
+        Err(std::fmt::Error)?;
+    }
+    Ok(())
+}
+
+fn glue(x: &str, fail: bool) -> Result<i32, Union<FooError, BarError>> {
+    let value = foo(x)?;
+    bar(fail).map_err(Union::Right)?;
+    Ok(value)
+}
+
+#[errors(ParseIntError, std::fmt::Error)]
+fn upcast(x: &str, fail: bool) -> Result<i32, _> {
+    glue(x, fail).into_super_error::<UpcastError>()
+}
+
+#[test]
+fn left() {
+    assert_eq!(glue("1", false).unwrap(), 1);
+    let err = glue("x", false).unwrap_err();
+    assert!(matches!(err, Union::Left(FooError::ParseInt(_))));
+    assert_eq!(err.to_string(), "invalid digit found in string");
+    assert!(err.source().is_none());
+}
+
+#[test]
+fn right() {
+    let err = glue("1", true).unwrap_err();
+    assert!(matches!(err, Union::Right(BarError::StdFmt(_))));
+    assert_eq!(err.to_string(), std::fmt::Error.to_string());
+}
+
+#[test]
+fn upcasting() {
+    assert_eq!(upcast("2", false).unwrap(), 2);
+    assert!(matches!(upcast("x", false), Err(UpcastError::ParseInt(_))));
+    assert!(matches!(upcast("2", true), Err(UpcastError::StdFmt(_))));
+}
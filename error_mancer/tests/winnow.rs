@@ -0,0 +1,72 @@
+#![cfg(feature = "winnow")]
+
+use error_mancer::prelude::*;
+use winnow::ascii::{alpha1, dec_uint};
+use winnow::error::{ContextError, ErrMode, Needed};
+use winnow::prelude::*;
+use winnow::stream::Partial;
+use winnow::token::literal;
+
+fn key_value(input: &mut &str) -> ModalResult<(String, u32)> {
+    let key = alpha1.parse_next(input)?;
+    '='.parse_next(input)?;
+    let value = dec_uint.parse_next(input)?;
+    Ok((key.to_owned(), value))
+}
+
+#[errors(winnow::error::ErrMode<winnow::error::ContextError>)]
+fn parse(mut input: &str) -> Result<(String, u32), _> {
+    Ok(key_value(&mut input)?)
+}
+
+#[errors(ErrMode<ContextError> as Syntax, std::io::Error)]
+fn parse_renamed(mut input: &str) -> Result<(String, u32), _> {
+    Ok(key_value(&mut input)?)
+}
+
+#[errors((ErrMode<ContextError>, std::num::ParseIntError) as Input)]
+fn parse_grouped(mut input: &str) -> Result<(String, u32), _> {
+    Ok(key_value(&mut input)?)
+}
+
+#[errors(ErrMode<ContextError>)]
+fn parse_header(input: &[u8]) -> Result<usize, _> {
+    let mut input = Partial::new(input);
+    literal::<_, _, ErrMode<ContextError>>("MAGIC").parse_next(&mut input)?;
+    Ok(input.len())
+}
+
+#[test]
+fn parses() {
+    assert_eq!(parse("a=1").unwrap(), (String::from("a"), 1));
+}
+
+#[test]
+fn unwraps_err_mode() {
+    let Err(ParseError::WinnowErrorContext(err)) = parse("a=") else {
+        panic!("expected context error");
+    };
+    let _: ContextError = err;
+
+    let Err(ParseRenamedError::Syntax(_)) = parse_renamed("=1") else {
+        panic!("expected syntax error");
+    };
+}
+
+#[test]
+fn unwraps_err_mode_in_groups() {
+    let Err(ParseGroupedError::Input(ParseGroupedInputError::Context(_))) = parse_grouped("1")
+    else {
+        panic!("expected context error");
+    };
+}
+
+#[test]
+fn incomplete() {
+    assert_eq!(parse_header(b"MAGIC!").unwrap(), 1);
+    let Err(ParseHeaderError::Incomplete(err)) = parse_header(b"MAG") else {
+        panic!("expected incomplete error");
+    };
+    assert_eq!(err.needed, Needed::Unknown);
+    assert_eq!(err.to_string(), "more input needed");
+}
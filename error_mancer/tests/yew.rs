@@ -0,0 +1,43 @@
+#![cfg(feature = "yew")]
+
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+use yew::html::{Classes, IntoPropValue};
+use yew::{props, Properties};
+
+#[errors(ParseIntError, (std::fmt::Error, std::io::Error) as Output, yew)]
+fn parse(x: &str) -> Result<i32, _> {
+    if x.is_empty() {
+        Err(std::fmt::Error)?;
+    }
+    Ok(x.parse()?)
+}
+
+#[derive(Properties, PartialEq)]
+struct ErrorProps {
+    message: String,
+    #[prop_or_default]
+    class: Classes,
+}
+
+#[test]
+fn prop_value() {
+    let message: String = parse("x").unwrap_err().into_prop_value();
+    assert_eq!(message, "invalid digit found in string");
+
+    let props = props!(ErrorProps {
+        message: parse("x").unwrap_err(),
+        class: Classes::from(parse("x").unwrap_err()),
+    });
+    assert_eq!(props.message, "invalid digit found in string");
+    assert_eq!(props.class, Classes::from("parse-int"));
+}
+
+#[test]
+fn classes() {
+    assert_eq!(
+        Classes::from(parse("").unwrap_err()),
+        Classes::from("output std-fmt")
+    );
+}
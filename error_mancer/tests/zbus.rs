@@ -0,0 +1,64 @@
+#![cfg(feature = "zbus")]
+
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+use zbus::{fdo, interface};
+
+#[derive(Debug)]
+struct Forbidden;
+
+impl std::fmt::Display for Forbidden {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("forbidden")
+    }
+}
+
+#[errors(ParseIntError, #[fdo = AccessDenied] Forbidden, group Io { std::io::Error }, zbus)]
+fn parse(value: &str) -> Result<u32, _> {
+    if value == "root" {
+        Err(Forbidden)?;
+    }
+    if value.is_empty() {
+        Err(std::io::Error::other("empty"))?;
+    }
+    Ok(value.parse()?)
+}
+
+struct Service;
+
+#[interface(name = "org.example.Service")]
+impl Service {
+    fn parse(&self, value: &str) -> fdo::Result<u32> {
+        Ok(parse(value)?)
+    }
+}
+
+#[test]
+fn ok_value() {
+    assert_eq!(Service.parse("3").unwrap(), 3);
+}
+
+#[test]
+fn default_kind() {
+    let fdo::Error::Failed(message) = Service.parse("x").unwrap_err() else {
+        panic!("expected `Failed`");
+    };
+    assert_eq!(message, "invalid digit found in string");
+}
+
+#[test]
+fn explicit_kind() {
+    assert_eq!(
+        Service.parse("root").unwrap_err(),
+        fdo::Error::AccessDenied(String::from("forbidden"))
+    );
+}
+
+#[test]
+fn group_kind() {
+    assert_eq!(
+        Service.parse("").unwrap_err(),
+        fdo::Error::Failed(String::from("empty"))
+    );
+}
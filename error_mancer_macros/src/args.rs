@@ -0,0 +1,930 @@
+use convert_case::{Case, Casing};
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::format_ident;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    braced,
+    parenthesized,
+    token,
+    Arm,
+    Attribute,
+    Block,
+    Expr,
+    ExprClosure,
+    GenericArgument,
+    Ident,
+    LitStr,
+    Meta,
+    Path,
+    PathArguments,
+    Token,
+    Type,
+    TypePath,
+};
+
+use crate::generate::same_trait;
+
+/// The parsed arguments of a `#[errors(...)]` attribute.
+#[derive(Default)]
+pub(crate) struct ErrorsArgs {
+    pub(crate) entries: Vec<ErrorEntry>,
+    /// Errors `?` cant be used on, for functions with a passthrough error type.
+    pub(crate) deny: Vec<Type>,
+    /// Dont generate a `FlattenInto` implementation.
+    pub(crate) no_flatten: bool,
+    /// Dont add a `# Errors` section to the function docs.
+    pub(crate) no_doc: bool,
+    /// Print all payloads as `<redacted>` in the `Debug` implementation.
+    pub(crate) no_debug: bool,
+    /// Implement `std::error::Error` instead of `core::error::Error`.
+    pub(crate) use_std_error: bool,
+    /// Capture a `SpanTrace` in each variant.
+    pub(crate) span_trace: bool,
+    /// Dont generate a `Debug` implementation, so it can be implemented by hand.
+    pub(crate) custom_debug: bool,
+    /// Dont generate a `Display` implementation, so it can be implemented by hand.
+    pub(crate) no_display: bool,
+    /// Implement `Display` with the `Debug` output, for payloads without useful messages yet.
+    pub(crate) impl_display_debug: bool,
+    /// Panic on errors instead of returning them, only available with the `prototyping` feature.
+    pub(crate) panic: bool,
+    /// Generate a fieldless `Kind` enum and a `kind()` method returning it.
+    pub(crate) kind: bool,
+    /// Generate a `kinds()` function iterating over every variant of the `Kind` enum.
+    pub(crate) iter_kinds: bool,
+    /// Use the shared `error_mancer::NoError` instead of generating a enum.
+    pub(crate) none: bool,
+    /// Implement `Display` and `Error` through `VariantDispatch`, so the enum only generates the
+    /// `match` finding the payload.
+    pub(crate) dispatch: bool,
+    /// Generate a `classify` function, returning the variant a `&dyn Error` would be converted
+    /// into.
+    pub(crate) classify: bool,
+    /// Implement `TryFlattenInto` and `FromPayload`, for converting into a enum covering only some
+    /// of the errors.
+    pub(crate) try_flatten: bool,
+    /// Generate a struct wrapping a hidden enum, only exposing the kind and message.
+    pub(crate) opaque: bool,
+    /// Check the body against the listed errors, but keep the passthrough error type in the
+    /// signature, such as `anyhow::Result<T>`.
+    pub(crate) erase: bool,
+    /// Return `Box<FooError>` instead of the enum, keeping the `Result` small on the `Ok` path.
+    pub(crate) boxed_self: bool,
+    /// Only generate the enum, the function is left as written so the enum can be wired up by
+    /// hand, such as in `Result<T, Arc<FooError>>`.
+    pub(crate) enum_only: bool,
+    /// Generate a `JsonError` wrapper implementing `Serialize`, only available with the `serde`
+    /// feature.
+    pub(crate) json_error: bool,
+    /// Implement `serde::ser::Error` and `serde::de::Error`, storing the messages in the `String`
+    /// entry, only available with the `serde` feature.
+    pub(crate) serde_error: bool,
+    /// Add a `Nom` variant and implement nom's error traits, only available with the `nom`
+    /// feature.
+    pub(crate) nom: bool,
+    /// Like `nom`, but record the combinators and contexts the error passed through.
+    pub(crate) nom_trace: bool,
+    /// Implement `From<Enum> for zbus::fdo::Error`, only available with the `zbus` feature.
+    pub(crate) zbus: bool,
+    /// Implement `IntoPropValue<String>` and `From<Enum> for Classes`, only available with the
+    /// `yew` feature.
+    pub(crate) yew: bool,
+    /// Record errors on the active OpenTelemetry span when they are created, only available with
+    /// the `opentelemetry` feature.
+    pub(crate) opentelemetry: bool,
+    /// Name of a catch-all variant holding a boxed error.
+    pub(crate) allow_extra: Option<Ident>,
+    /// Module the enum is expected to be re-exported from.
+    pub(crate) reexport: Option<Path>,
+    /// Flags set explicitly with their value, these arent overridden by defaults.
+    pub(crate) explicit: Vec<(String, bool)>,
+    /// Suffix of the generated enum name, `Error` unless set.
+    pub(crate) enum_suffix: Option<String>,
+    /// Status code for variants without `#[http_status = ..]`, `500` unless set.
+    pub(crate) http_status: Option<syn::LitInt>,
+    /// Context written before the message of every variant, i.e `"function: foo: {err}"`.
+    pub(crate) display_context: Option<LitStr>,
+    /// Traits derived for the generated enums, from `error_mancer.toml`.
+    pub(crate) derives: Vec<Path>,
+    /// Lints allowed on the generated enums, from `error_mancer.toml`.
+    pub(crate) allow: Vec<Path>,
+    /// Traits derived only when a `cfg` predicate holds, from `cfg_derive(predicate, ..)`.
+    pub(crate) cfg_derives: Vec<CfgDerive>,
+    /// The `error_mancer.toml` the defaults were read from.
+    pub(crate) config_file: Option<String>,
+}
+
+/// `cfg_derive(test, PartialEq, Eq)`, emitted as `#[cfg_attr(test, derive(PartialEq, Eq))]`.
+pub(crate) struct CfgDerive {
+    pub(crate) predicate: Meta,
+    pub(crate) traits: Vec<Path>,
+}
+
+impl Parse for CfgDerive {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let predicate = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let traits = Punctuated::<Path, Token![,]>::parse_terminated(input)?;
+        Ok(Self {
+            predicate,
+            traits: traits.into_iter().collect(),
+        })
+    }
+}
+
+/// A single entry listed in the attribute.
+pub(crate) struct ErrorEntry {
+    /// Attributes written before the entry, i.e doc comments.
+    pub(crate) attrs: Vec<Attribute>,
+    pub(crate) kind: EntryKind,
+}
+
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum EntryKind {
+    /// A plain error type, i.e `std::io::Error`.
+    Type(ErrorType),
+    /// Multiple errors stored in a single variant, i.e `(Err1, Err2) as Combined` or
+    /// `group Combined { Err1, Err2 }`.
+    Group(ErrorGroup),
+}
+
+pub(crate) struct ErrorType {
+    pub(crate) ty: Type,
+    /// Explicit variant name given with `as Name`.
+    pub(crate) rename: Option<Ident>,
+    /// Fluent message id given with `msg_id "..."`.
+    pub(crate) msg_id: Option<LitStr>,
+    /// Marked with `retryable`.
+    pub(crate) retryable: bool,
+    /// Marked with `no_debug`, printing the payload as `<redacted>`.
+    pub(crate) no_debug: bool,
+    /// Marked with `redact`, hiding the payload from `Display`, `Debug` and `Error::source`.
+    pub(crate) redact: bool,
+    /// Written as `poison`, the payload is `error_mancer::Poisoned` and any
+    /// `std::sync::PoisonError<G>` converts into it.
+    pub(crate) poison: bool,
+}
+
+impl ErrorType {
+    /// The variant name, either the explicit one or derived from the path segments, i.e
+    /// `std::io::Error` becomes `StdIo`.
+    pub(crate) fn variant_name(&self) -> Ident {
+        if let Some(rename) = &self.rename {
+            return rename.clone();
+        }
+
+        if self.poison {
+            return format_ident!("Poisoned");
+        }
+        if is_static_str(&self.ty) {
+            return format_ident!("Str");
+        }
+
+        let ty = match self.payload() {
+            payload @ Type::Path(_) => payload,
+            _ => &self.ty,
+        };
+        let Type::Path(TypePath { path, .. }) = ty else {
+            unreachable!("non-path types are required to have a name when parsing");
+        };
+        let name = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string() + "_")
+            .collect::<String>()
+            .to_case(Case::Pascal);
+        // A bare `Error` keeps its name.
+        let name = match name.trim_end_matches("Error") {
+            "" => "Error",
+            name => name,
+        };
+        format_ident!("{name}")
+    }
+
+    /// With the `winnow` feature `winnow::error::ErrMode<E>` is unwrapped, storing `E` in the
+    /// variant. Returns the path to `ErrMode` (without generics) and `E`.
+    pub(crate) fn err_mode(&self) -> Option<(Path, &Type)> {
+        if !cfg!(feature = "winnow") {
+            return None;
+        }
+        let Type::Path(TypePath { path, qself: None }) = &self.ty else {
+            return None;
+        };
+        let segment = path.segments.last()?;
+        if segment.ident != "ErrMode" {
+            return None;
+        }
+        let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+            return None;
+        };
+        let [GenericArgument::Type(inner)] = arguments.args.iter().collect::<Vec<_>>()[..] else {
+            return None;
+        };
+
+        let mut path = path.clone();
+        if let Some(segment) = path.segments.last_mut() {
+            segment.arguments = PathArguments::None;
+        }
+        Some((path, inner))
+    }
+
+    /// The type stored in the variant.
+    pub(crate) fn payload(&self) -> &Type {
+        self.err_mode().map_or(&self.ty, |(_, inner)| inner)
+    }
+}
+
+/// The input to `errors_block!`, i.e `async Err1, Err2 => { ... }`.
+pub(crate) struct ErrorsBlock {
+    pub(crate) asyncness: Option<Token![async]>,
+    pub(crate) args: ErrorsArgs,
+    pub(crate) block: Block,
+}
+
+/// The input to `handle!`, i.e `value => FooError { Err(Err1) => 0 }`.
+pub(crate) struct Handle {
+    pub(crate) value: Expr,
+    pub(crate) rest: HandleRest,
+    pub(crate) arms: Vec<Arm>,
+}
+
+/// What `handle!` does with the cases which arent handled.
+pub(crate) enum HandleRest {
+    /// Convert the errors into the given enum.
+    Propagate(Path),
+    /// `wrap_rest(context)`, convert the errors into an `anyhow::Error` with the given context.
+    Wrap(Expr),
+}
+
+impl Parse for Handle {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let value = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let rest = if input.peek(Ident) && input.peek2(token::Paren) {
+            let ident: Ident = input.parse()?;
+            if ident != "wrap_rest" {
+                return Err(syn::Error::new(ident.span(), "expected `wrap_rest`"));
+            }
+            if !cfg!(feature = "anyhow") {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "`wrap_rest` requires the `anyhow` feature",
+                ));
+            }
+            let content;
+            parenthesized!(content in input);
+            HandleRest::Wrap(content.parse()?)
+        } else {
+            HandleRest::Propagate(input.parse()?)
+        };
+
+        let content;
+        braced!(content in input);
+        let mut arms = Vec::new();
+        while !content.is_empty() {
+            arms.push(content.parse()?);
+        }
+
+        Ok(Self { value, rest, arms })
+    }
+}
+
+/// The input to `split_variants!`, i.e `FooError; Io = (StdIo, Timeout), Other = (Parse)`.
+pub(crate) struct SplitVariants {
+    pub(crate) enum_path: Path,
+    pub(crate) first: SplitHalf,
+    pub(crate) second: SplitHalf,
+}
+
+/// One of the enums generated by `split_variants!`, i.e `pub Io = (StdIo, Timeout)`.
+pub(crate) struct SplitHalf {
+    pub(crate) attrs: Vec<Attribute>,
+    pub(crate) vis: syn::Visibility,
+    pub(crate) name: Ident,
+    pub(crate) variants: Vec<Ident>,
+}
+
+impl Parse for SplitVariants {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let enum_path = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let first = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let second = input.parse()?;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(Self {
+            enum_path,
+            first,
+            second,
+        })
+    }
+}
+
+impl Parse for SplitHalf {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let content;
+        parenthesized!(content in input);
+        let variants = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+        Ok(Self {
+            attrs,
+            vis,
+            name,
+            variants: variants.into_iter().collect(),
+        })
+    }
+}
+
+/// The arguments of `#[ErrorMancerMeta(...)]`, i.e `http_status = 503, retry = true`.
+#[derive(Default)]
+pub(crate) struct MetaArgs {
+    pub(crate) http_status: Option<syn::LitInt>,
+    pub(crate) retry: Option<syn::LitBool>,
+}
+
+impl Parse for MetaArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Self::default();
+        let values = Punctuated::<syn::MetaNameValue, Token![,]>::parse_terminated(input)?;
+        for value in values {
+            let Expr::Lit(syn::ExprLit { lit, .. }) = &value.value else {
+                return Err(syn::Error::new(value.value.span(), "Expected a literal"));
+            };
+            if value.path.is_ident("http_status") {
+                let syn::Lit::Int(status) = lit else {
+                    return Err(syn::Error::new(lit.span(), "Expected a status code"));
+                };
+                status.base10_parse::<u16>()?;
+                args.http_status = Some(status.clone());
+            } else if value.path.is_ident("retry") {
+                let syn::Lit::Bool(retry) = lit else {
+                    return Err(syn::Error::new(lit.span(), "Expected `true` or `false`"));
+                };
+                args.retry = Some(retry.clone());
+            } else {
+                return Err(syn::Error::new(
+                    value.path.span(),
+                    "Unknown metadata, expected `http_status` or `retry`",
+                ));
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// The input to `errors_in_scope!`, i.e `scope, #[errors(Err1)] || { ... }`.
+pub(crate) struct ErrorsInScope {
+    pub(crate) scope: Expr,
+    pub(crate) args: ErrorsArgs,
+    pub(crate) closure: ExprClosure,
+}
+
+/// The input to `errors_closure!`, i.e `#[errors(Err1)] |x| { ... }`.
+pub(crate) struct ErrorsClosure {
+    pub(crate) args: ErrorsArgs,
+    pub(crate) closure: ExprClosure,
+}
+
+pub(crate) struct ErrorGroup {
+    pub(crate) entries: Vec<ErrorEntry>,
+    /// The variant name.
+    pub(crate) name: Ident,
+}
+
+impl ErrorsArgs {
+    /// Try to parse a boolean flag, returns false if the next token isnt a known flag on its own
+    /// (i.e its the start of a path).
+    fn parse_flag(&mut self, input: ParseStream) -> syn::Result<bool> {
+        let fork = input.fork();
+        let Ok(ident) = fork.parse::<Ident>() else {
+            return Ok(false);
+        };
+        if !(fork.is_empty() || fork.peek(Token![,])) {
+            return Ok(false);
+        }
+
+        if !self.set_flag(&ident.to_string(), ident.span(), true)? {
+            return Ok(false);
+        }
+        input.parse::<Ident>()?;
+        Ok(true)
+    }
+
+    /// Parse `deny(Err1, Err2)`, returns false if the input doesnt start with `deny(`.
+    fn parse_deny(&mut self, input: ParseStream) -> syn::Result<bool> {
+        if !peek_call(input, "deny")? {
+            return Ok(false);
+        }
+        input.parse::<Ident>()?;
+
+        let content;
+        parenthesized!(content in input);
+        let denied = Punctuated::<Type, Token![,]>::parse_terminated(&content)?;
+        self.deny.extend(denied);
+        Ok(true)
+    }
+
+    /// Parse `cfg_derive(predicate, Trait1, Trait2)`, returns false if the input doesnt start
+    /// with `cfg_derive(`.
+    fn parse_cfg_derive(&mut self, input: ParseStream) -> syn::Result<bool> {
+        if !peek_call(input, "cfg_derive")? {
+            return Ok(false);
+        }
+        input.parse::<Ident>()?;
+
+        let content;
+        parenthesized!(content in input);
+        self.cfg_derives.push(content.parse()?);
+        Ok(true)
+    }
+
+    /// Set the flag called `name`, returns false if there is no such flag.
+    pub(crate) fn set_flag(&mut self, name: &str, span: Span, value: bool) -> syn::Result<bool> {
+        let flag = match name {
+            "no_flatten" => &mut self.no_flatten,
+            "no_doc" => &mut self.no_doc,
+            "no_debug" => &mut self.no_debug,
+            "custom_debug" => &mut self.custom_debug,
+            "no_display" => &mut self.no_display,
+            "impl_display_debug" => &mut self.impl_display_debug,
+            "use_std_error" => &mut self.use_std_error,
+            "span_trace" => &mut self.span_trace,
+            "kind" => &mut self.kind,
+            "iter_kinds" => &mut self.iter_kinds,
+            "none" => &mut self.none,
+            "dispatch" => &mut self.dispatch,
+            "classify" => &mut self.classify,
+            "try_flatten" => &mut self.try_flatten,
+            "opaque" => &mut self.opaque,
+            "enum_only" => &mut self.enum_only,
+            "erase" => &mut self.erase,
+            "boxed_self" => &mut self.boxed_self,
+            "json_error" => {
+                if !cfg!(feature = "serde") {
+                    return Err(syn::Error::new(
+                        span,
+                        "`json_error` requires the `serde` feature",
+                    ));
+                }
+                &mut self.json_error
+            }
+            "serde_error" => {
+                if !cfg!(feature = "serde") {
+                    return Err(syn::Error::new(
+                        span,
+                        "`serde_error` requires the `serde` feature",
+                    ));
+                }
+                &mut self.serde_error
+            }
+            "nom" | "nom_trace" => {
+                if !cfg!(feature = "nom") {
+                    return Err(syn::Error::new(
+                        span,
+                        format!("`{name}` requires the `nom` feature"),
+                    ));
+                }
+                if name == "nom" {
+                    &mut self.nom
+                } else {
+                    &mut self.nom_trace
+                }
+            }
+            "zbus" => {
+                if !cfg!(feature = "zbus") {
+                    return Err(syn::Error::new(span, "`zbus` requires the `zbus` feature"));
+                }
+                &mut self.zbus
+            }
+            "yew" => {
+                if !cfg!(feature = "yew") {
+                    return Err(syn::Error::new(span, "`yew` requires the `yew` feature"));
+                }
+                &mut self.yew
+            }
+            "opentelemetry" => {
+                if !cfg!(feature = "opentelemetry") {
+                    return Err(syn::Error::new(
+                        span,
+                        "`opentelemetry` requires the `opentelemetry` feature",
+                    ));
+                }
+                &mut self.opentelemetry
+            }
+            "panic" => {
+                if !cfg!(feature = "prototyping") {
+                    return Err(syn::Error::new(
+                        span,
+                        "`panic` requires the `prototyping` feature",
+                    ));
+                }
+                &mut self.panic
+            }
+            _ => return Ok(false),
+        };
+        *flag = value;
+        self.explicit.push((name.to_owned(), value));
+        Ok(true)
+    }
+
+    /// Whether `path` is one of the traits in a `cfg_derive(..)`.
+    pub(crate) fn derives_conditionally(&self, path: &Path) -> bool {
+        self.cfg_derives
+            .iter()
+            .flat_map(|cfg_derive| &cfg_derive.traits)
+            .any(|derived| same_trait(derived, path))
+    }
+
+    /// Whether `nom` or `nom_trace` was passed.
+    pub(crate) fn uses_nom(&self) -> bool {
+        self.nom || self.nom_trace
+    }
+
+    /// Add the variants implied by other arguments once the defaults are applied, `Nom` for
+    /// `nom` and `nom_trace`, and `Incomplete` for `winnow::error::ErrMode<E>` entries.
+    pub(crate) fn add_implied_entries(&mut self) {
+        add_incomplete_entry(&mut self.entries);
+        if self.uses_nom() {
+            self.entries.push(implied_entry(
+                " One of nom's parsers failed.",
+                syn::parse_quote!(::error_mancer::NomError),
+                "Nom",
+            ));
+        }
+    }
+
+    /// Suffix of the generated enum names.
+    pub(crate) fn suffix(&self) -> &str {
+        self.enum_suffix.as_deref().unwrap_or("Error")
+    }
+
+    /// Fill in everything not set explicitly from `defaults`, i.e the arguments of `#[errors]`
+    /// on a impl block or `error_mancer.toml`.
+    pub(crate) fn apply_defaults(&mut self, defaults: &ErrorsArgs) -> syn::Result<()> {
+        for (name, value) in &defaults.explicit {
+            if !self.explicit.iter().any(|(explicit, _)| explicit == name) {
+                self.set_flag(name, Span::call_site(), *value)?;
+            }
+        }
+        if self.enum_suffix.is_none() {
+            self.enum_suffix.clone_from(&defaults.enum_suffix);
+        }
+        if self.http_status.is_none() {
+            self.http_status.clone_from(&defaults.http_status);
+        }
+        if self.display_context.is_none() {
+            self.display_context.clone_from(&defaults.display_context);
+        }
+        self.derives.extend(defaults.derives.iter().cloned());
+        self.allow.extend(defaults.allow.iter().cloned());
+        // Traits the function derives conditionally itself arent taken from the defaults.
+        for default in &defaults.cfg_derives {
+            let traits = default
+                .traits
+                .iter()
+                .filter(|path| !self.derives_conditionally(path))
+                .cloned()
+                .collect::<Vec<_>>();
+            if !traits.is_empty() {
+                self.cfg_derives.push(CfgDerive {
+                    predicate: default.predicate.clone(),
+                    traits,
+                });
+            }
+        }
+        if self.config_file.is_none() {
+            self.config_file.clone_from(&defaults.config_file);
+        }
+        Ok(())
+    }
+
+    /// Parse a `key = value` option.
+    fn parse_option(&mut self, input: ParseStream) -> syn::Result<()> {
+        let key = input.parse::<Ident>()?;
+        input.parse::<Token![=]>()?;
+
+        if input.peek(syn::LitBool) {
+            let value = input.parse::<syn::LitBool>()?;
+            if !self.set_flag(&key.to_string(), key.span(), value.value)? {
+                return Err(syn::Error::new(key.span(), format!("Unknown flag `{key}`")));
+            }
+            return Ok(());
+        }
+
+        match key.to_string().as_str() {
+            "allow_extra" => self.allow_extra = Some(parse_ident_or_str(input)?),
+            "reexport" => self.reexport = Some(input.call(Path::parse_mod_style)?),
+            "enum_suffix" => self.enum_suffix = Some(parse_ident_or_str(input)?.to_string()),
+            "display_context" => self.display_context = Some(input.parse()?),
+            "http_status" => {
+                let status = input.parse::<syn::LitInt>()?;
+                status.base10_parse::<u16>()?;
+                self.http_status = Some(status);
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("Unknown option `{key}`"),
+                ))
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether `ty` is `&'static str`, which is named `Str` by default.
+fn is_static_str(ty: &Type) -> bool {
+    let Type::Reference(reference) = ty else {
+        return false;
+    };
+    reference.mutability.is_none()
+        && reference
+            .lifetime
+            .as_ref()
+            .is_some_and(|lifetime| lifetime.ident == "static")
+        && matches!(&*reference.elem, Type::Path(path) if path.path.is_ident("str"))
+}
+
+/// Whether the input starts with `name(`.
+fn peek_call(input: ParseStream, name: &str) -> syn::Result<bool> {
+    if !(input.peek(Ident) && input.peek2(token::Paren)) {
+        return Ok(false);
+    }
+    Ok(input.fork().parse::<Ident>()? == name)
+}
+
+/// Parse either `Name` or `"Name"`.
+fn parse_ident_or_str(input: ParseStream) -> syn::Result<Ident> {
+    if input.peek(LitStr) {
+        input.parse::<LitStr>()?.parse()
+    } else {
+        input.parse()
+    }
+}
+
+impl Parse for ErrorsArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Self::default();
+
+        // The mode can start the arguments, i.e `#[errors(opaque; std::io::Error)]`,
+        // `#[errors(erase; std::io::Error)]` or `#[errors(boxed_self; std::io::Error)]`.
+        if input.peek(Ident) && input.peek2(Token![;]) {
+            let mode = input.parse::<Ident>()?;
+            if mode != "opaque" && mode != "erase" && mode != "boxed_self" {
+                return Err(syn::Error::new(
+                    mode.span(),
+                    "Expected `opaque`, `erase` or `boxed_self`",
+                ));
+            }
+            args.set_flag(&mode.to_string(), mode.span(), true)?;
+            input.parse::<Token![;]>()?;
+        }
+
+        while !input.is_empty() {
+            if input.peek(Ident) && input.peek2(Token![=]) {
+                args.parse_option(input)?;
+            } else if !args.parse_deny(input)?
+                && !args.parse_cfg_derive(input)?
+                && !args.parse_flag(input)?
+            {
+                args.entries.push(input.parse()?);
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(args)
+    }
+}
+
+impl Parse for ErrorEntry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+
+        let kind = if input.peek(Ident) && input.peek2(Ident) && input.peek3(token::Brace) {
+            let keyword = input.parse::<Ident>()?;
+            if keyword != "group" {
+                return Err(syn::Error::new(keyword.span(), "Expected `group`"));
+            }
+            let name = input.parse()?;
+            let content;
+            braced!(content in input);
+            let entries = Punctuated::<ErrorEntry, Token![,]>::parse_terminated(&content)?;
+            EntryKind::Group(ErrorGroup {
+                entries: entries.into_iter().collect(),
+                name,
+            })
+        } else if input.peek(token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            let entries = Punctuated::<ErrorEntry, Token![,]>::parse_terminated(&content)?;
+            input.parse::<Token![as]>()?;
+            let name = input.parse()?;
+            EntryKind::Group(ErrorGroup {
+                entries: entries.into_iter().collect(),
+                name,
+            })
+        } else {
+            // `redact` can also be written before the type, i.e `redact sqlx::Error`.
+            let mut redact = input.peek(Ident)
+                && input.peek2(Ident)
+                && input.fork().parse::<Ident>()? == "redact";
+            if redact {
+                input.parse::<Ident>()?;
+            }
+            let poison = input.peek(Ident)
+                && !input.peek2(Token![::])
+                && input.fork().parse::<Ident>()? == "poison";
+            let ty = if poison {
+                let keyword = input.parse::<Ident>()?;
+                if !cfg!(feature = "std") {
+                    return Err(syn::Error::new(
+                        keyword.span(),
+                        "`poison` requires the `std` feature",
+                    ));
+                }
+                syn::parse_quote!(::error_mancer::Poisoned)
+            } else {
+                input.parse()?
+            };
+            let rename = if input.peek(Token![as]) {
+                input.parse::<Token![as]>()?;
+                Some(input.parse()?)
+            } else {
+                None
+            };
+
+            if rename.is_none() && !matches!(ty, Type::Path(_)) && !is_static_str(&ty) {
+                return Err(syn::Error::new(
+                    ty.span(),
+                    "Expected `as Name` to name the variant for this error type",
+                ));
+            }
+
+            let mut msg_id = None;
+            let mut retryable = false;
+            let mut no_debug = false;
+            while input.peek(Ident) {
+                let key = input.parse::<Ident>()?;
+                match key.to_string().as_str() {
+                    "msg_id" => {
+                        if !cfg!(feature = "i18n") {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "`msg_id` requires the `i18n` feature",
+                            ));
+                        }
+                        msg_id = Some(input.parse()?);
+                    }
+                    "retryable" => retryable = true,
+                    "no_debug" => no_debug = true,
+                    "redact" => redact = true,
+                    _ => {
+                        return Err(syn::Error::new(
+                            key.span(),
+                            format!(
+                                "Unknown option `{key}`, expected `msg_id`, `retryable`, \
+                                 `no_debug` or `redact`"
+                            ),
+                        ))
+                    }
+                }
+            }
+            EntryKind::Type(ErrorType {
+                ty,
+                rename,
+                msg_id,
+                retryable,
+                no_debug,
+                redact,
+                poison,
+            })
+        };
+
+        Ok(Self { attrs, kind })
+    }
+}
+
+/// Add a `Incomplete` variant next to `ErrMode<E>` entries, so groups holding them can store it
+/// as well.
+fn add_incomplete_entry(entries: &mut Vec<ErrorEntry>) {
+    let mut incomplete = false;
+    for entry in entries.iter_mut() {
+        match &mut entry.kind {
+            EntryKind::Type(error_type) => incomplete |= error_type.err_mode().is_some(),
+            EntryKind::Group(group) => add_incomplete_entry(&mut group.entries),
+        }
+    }
+    if incomplete {
+        entries.push(implied_entry(
+            " A partial parser needed more input.",
+            syn::parse_quote!(::error_mancer::Incomplete),
+            "Incomplete",
+        ));
+    }
+}
+
+/// A entry added by the macro, such as the `Nom` variant for `nom`.
+fn implied_entry(doc: &str, ty: Type, name: &str) -> ErrorEntry {
+    ErrorEntry {
+        attrs: vec![syn::parse_quote!(#[doc = #doc])],
+        kind: EntryKind::Type(ErrorType {
+            ty,
+            rename: Some(format_ident!("{name}")),
+            msg_id: None,
+            retryable: false,
+            no_debug: false,
+            redact: false,
+            poison: false,
+        }),
+    }
+}
+
+/// Parse the `#[errors(...)]` written before a closure.
+fn parse_closure_args(input: ParseStream) -> syn::Result<ErrorsArgs> {
+    let mut args = ErrorsArgs::default();
+    for attr in input.call(Attribute::parse_outer)? {
+        if !attr.path().is_ident("errors") {
+            return Err(syn::Error::new(
+                attr.span(),
+                "Only `#[errors]` is supported on the closure",
+            ));
+        }
+        if let Meta::List(list) = &attr.meta {
+            args = list.parse_args()?;
+        }
+    }
+    Ok(args)
+}
+
+impl Parse for ErrorsInScope {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let scope = input.parse()?;
+        input.parse::<Token![,]>()?;
+
+        let args = parse_closure_args(input)?;
+        let closure = input.parse()?;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(Self {
+            scope,
+            args,
+            closure,
+        })
+    }
+}
+
+impl Parse for ErrorsClosure {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let args = parse_closure_args(input)?;
+        let closure = input.parse()?;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(Self { args, closure })
+    }
+}
+
+impl Parse for ErrorsBlock {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let asyncness = input.parse()?;
+
+        let args = input.step(|cursor| {
+            let mut tokens = TokenStream::new();
+            let mut rest = *cursor;
+            while let Some((token, next)) = rest.token_tree() {
+                if let TokenTree::Punct(punct) = &token {
+                    if punct.as_char() == '='
+                        && next.punct().is_some_and(|(p, _)| p.as_char() == '>')
+                    {
+                        return Ok((tokens, rest));
+                    }
+                }
+                tokens.extend([token]);
+                rest = next;
+            }
+            Err(cursor.error("Expected `=>` followed by a block"))
+        })?;
+        let args = syn::parse2(args)?;
+
+        input.parse::<Token![=>]>()?;
+        let block = input.parse()?;
+
+        Ok(Self {
+            asyncness,
+            args,
+            block,
+        })
+    }
+}
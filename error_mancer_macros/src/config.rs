@@ -0,0 +1,107 @@
+//! Defaults read from a `error_mancer.toml`, searched for from `CARGO_MANIFEST_DIR` upwards up to
+//! the workspace root.
+//!
+//! ```toml
+//! no_doc = true
+//! derive = ["Clone", "PartialEq"]
+//! allow = ["clippy::large_enum_variant"]
+//! enum_suffix = "Failure"
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use proc_macro2::Span;
+
+use crate::args::ErrorsArgs;
+
+const FILE_NAME: &str = "error_mancer.toml";
+
+/// Find the closest `error_mancer.toml`. The search stops at the workspace root, the first
+/// directory whose `Cargo.toml` has a `[workspace]` table, so a file outside of the project isnt
+/// picked up.
+fn find() -> Option<PathBuf> {
+    let manifest_dir = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR")?);
+    for dir in manifest_dir.ancestors() {
+        let path = dir.join(FILE_NAME);
+        if path.is_file() {
+            return Some(path);
+        }
+        if is_workspace_root(dir) {
+            return None;
+        }
+    }
+    None
+}
+
+/// Whether `dir` has a `Cargo.toml` with a `[workspace]` table.
+fn is_workspace_root(dir: &Path) -> bool {
+    std::fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|manifest| manifest.parse::<toml::Table>().ok())
+        .is_some_and(|manifest| manifest.contains_key("workspace"))
+}
+
+/// Load the defaults from the closest `error_mancer.toml`, if there is one.
+pub(crate) fn load() -> syn::Result<Option<ErrorsArgs>> {
+    let Some(path) = find() else {
+        return Ok(None);
+    };
+    let error = |message: String| {
+        syn::Error::new(Span::call_site(), format!("{}: {message}", path.display()))
+    };
+
+    let content = std::fs::read_to_string(&path).map_err(|err| error(err.to_string()))?;
+    let table = content
+        .parse::<toml::Table>()
+        .map_err(|err| error(err.to_string()))?;
+
+    let mut defaults = ErrorsArgs {
+        config_file: Some(path.display().to_string()),
+        ..ErrorsArgs::default()
+    };
+    for (key, value) in table {
+        match key.as_str() {
+            "derive" | "allow" => {
+                let paths = value
+                    .as_array()
+                    .ok_or_else(|| error(format!("`{key}` should be a list of paths")))?
+                    .iter()
+                    .map(|path| {
+                        let path = path
+                            .as_str()
+                            .ok_or_else(|| error(format!("`{key}` should be a list of paths")))?;
+                        syn::parse_str(path).map_err(|err| error(format!("`{path}`: {err}")))
+                    })
+                    .collect::<syn::Result<Vec<_>>>()?;
+                if key == "derive" {
+                    defaults.derives = paths;
+                } else {
+                    defaults.allow = paths;
+                }
+            }
+            "enum_suffix" => {
+                let suffix = value
+                    .as_str()
+                    .ok_or_else(|| error(String::from("`enum_suffix` should be a string")))?;
+                defaults.enum_suffix = Some(suffix.to_owned());
+            }
+            "http_status" => {
+                let status = value
+                    .as_integer()
+                    .and_then(|status| u16::try_from(status).ok())
+                    .ok_or_else(|| error(String::from("`http_status` should be a status code")))?;
+                defaults.http_status =
+                    Some(syn::LitInt::new(&status.to_string(), Span::call_site()));
+            }
+            flag => {
+                let value = value
+                    .as_bool()
+                    .ok_or_else(|| error(format!("`{flag}` should be a boolean")))?;
+                if !defaults.set_flag(flag, Span::call_site(), value)? {
+                    return Err(error(format!("Unknown option `{flag}`")));
+                }
+            }
+        }
+    }
+    Ok(Some(defaults))
+}
@@ -0,0 +1,67 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::visit_mut::VisitMut;
+use syn::{parse_quote, Expr, Type};
+
+/// The body of a function using `deny(..)`. The body is kept as is, except for `?` which checks
+/// the error isnt one of the denied errors.
+pub(crate) fn deny_body(denied: &[Type], mut body: syn::Block) -> TokenStream {
+    let marker = syn::Ident::new("__ErrorMancerDeny", Span::mixed_site());
+    RewriteTry { marker: &marker }.visit_block_mut(&mut body);
+
+    let stmts = &body.stmts;
+    quote! {
+        {
+            #[doc(hidden)]
+            struct #marker;
+            #(
+                impl ::error_mancer::__private::DeniedBy<#marker> for #denied {}
+            )*
+
+            #(#stmts)*
+        }
+    }
+}
+
+/// Rewrites `expr?` into a match checking the error, `?` inside closures, async blocks and
+/// items is left alone as it doesnt return from the function.
+struct RewriteTry<'a> {
+    marker: &'a syn::Ident,
+}
+
+impl VisitMut for RewriteTry<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Closure(_) | Expr::Async(_) | Expr::Const(_) | Expr::TryBlock(_) => {}
+            Expr::Try(try_expr) => {
+                self.visit_expr_mut(&mut try_expr.expr);
+
+                let inner = &try_expr.expr;
+                let marker = self.marker;
+                let value = syn::Ident::new("value", Span::mixed_site());
+                let err = syn::Ident::new("err", Span::mixed_site());
+                let check = quote_spanned! {try_expr.question_token.span()=>
+                    ::error_mancer::__private::assert_allowed(
+                        &#err,
+                        (&::error_mancer::__private::DenyProbe::<_, #marker>::new(&#err)).check(),
+                    )
+                };
+                *expr = parse_quote! {
+                    match #inner {
+                        ::core::result::Result::Ok(#value) => #value,
+                        ::core::result::Result::Err(#err) => {
+                            #[allow(unused_imports)]
+                            use ::error_mancer::__private::{CheckAllowed as _, CheckDenied as _};
+                            #check;
+                            return ::core::result::Result::Err(::core::convert::From::from(#err));
+                        }
+                    }
+                };
+            }
+            _ => syn::visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_item_mut(&mut self, _item: &mut syn::Item) {}
+}
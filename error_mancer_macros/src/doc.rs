@@ -0,0 +1,173 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Attribute, Expr, ExprLit, Lit, Meta, Type};
+
+use crate::args::{EntryKind, ErrorsArgs};
+
+/// Get the string value of `#[doc = "..."]` attributes, ignoring computed docs like
+/// `#[doc = include_str!(...)]`.
+pub(crate) fn doc_lines(attrs: &[Attribute]) -> impl Iterator<Item = String> + '_ {
+    attrs.iter().filter_map(|attr| match &attr.meta {
+        Meta::NameValue(meta) if meta.path.is_ident("doc") => match &meta.value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(doc), ..
+            }) => Some(doc.value()),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Returns true if the user already wrote a `# Errors` section.
+pub(crate) fn has_errors_section(attrs: &[Attribute]) -> bool {
+    doc_lines(attrs).any(|line| line.trim() == "# Errors")
+}
+
+/// Pretty print a type for use in docs, `quote` puts spaces around every token.
+pub(crate) fn type_to_string(ty: impl ToTokens) -> String {
+    ty.to_token_stream()
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(":: ", "::")
+        .replace(" < ", "<")
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace("& ", "&")
+}
+
+/// Generate a `# Errors` doc section listing each error type.
+pub(crate) fn errors_section(args: &ErrorsArgs, enum_name: Option<&Type>) -> TokenStream {
+    let mut lines = vec![String::new(), String::from("# Errors"), String::new()];
+
+    if args.entries.is_empty() && args.allow_extra.is_none() {
+        lines.push(String::from("This function never returns an error."));
+    } else {
+        match enum_name {
+            Some(enum_name) => lines.push(format!(
+                "Returns [`{}`] if any of the following errors occur:",
+                type_to_string(enum_name)
+            )),
+            None => lines.push(String::from(
+                "Returns an error if any of the following errors occur:",
+            )),
+        }
+        lines.push(String::new());
+
+        for entry in &args.entries {
+            let mut line = match &entry.kind {
+                EntryKind::Type(error_type) => format!("* [`{}`]", type_to_string(&error_type.ty)),
+                EntryKind::Group(group) => {
+                    let leaves = crate::generate::leaf_errors(&group.entries)
+                        .into_iter()
+                        .map(|error_type| format!("[`{}`]", type_to_string(&error_type.ty)))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("* `{}` ({leaves})", group.name)
+                }
+            };
+            let docs = doc_lines(&entry.attrs)
+                .map(|doc| doc.trim().to_owned())
+                .collect::<Vec<_>>()
+                .join(" ");
+            if !docs.is_empty() {
+                line.push_str(": ");
+                line.push_str(&docs);
+            }
+            lines.push(line);
+        }
+
+        if let Some(extra) = &args.allow_extra {
+            lines.push(format!("* `{extra}`: Any other error."));
+        }
+    }
+
+    let lines = lines.into_iter().map(|line| format!(" {line}"));
+    quote! {
+        #(#[doc = #lines])*
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use crate::errors_impl;
+
+    fn expand(attr: proc_macro2::TokenStream, item: proc_macro2::TokenStream) -> String {
+        let file: syn::File = syn::parse2(errors_impl(attr, item).unwrap()).unwrap();
+        let function = file
+            .items
+            .iter()
+            .find_map(|item| match item {
+                syn::Item::Fn(function) => Some(function),
+                _ => None,
+            })
+            .unwrap();
+        super::doc_lines(&function.attrs)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn lists_errors() {
+        let docs = expand(
+            quote!(
+                std::io::Error,
+                #[doc = "When parsing fails."]
+                core::num::ParseIntError
+            ),
+            quote! {
+                /// Does a thing.
+                pub fn foo() -> Result<(), _> { Ok(()) }
+            },
+        );
+        assert_eq!(
+            docs,
+            " Does a thing.\n \n # Errors\n \n \
+             Returns [`FooError`] if any of the following errors occur:\n \n \
+             * [`std::io::Error`]\n \
+             * [`core::num::ParseIntError`]: When parsing fails."
+        );
+    }
+
+    #[test]
+    fn no_errors() {
+        let docs = expand(
+            quote!(),
+            quote!(
+                fn foo() -> Result<(), _> {
+                    Ok(())
+                }
+            ),
+        );
+        assert!(docs.contains("This function never returns an error."));
+    }
+
+    #[test]
+    fn existing_section() {
+        let docs = expand(
+            quote!(std::io::Error),
+            quote! {
+                /// # Errors
+                /// Custom docs.
+                fn foo() -> Result<(), _> { Ok(()) }
+            },
+        );
+        assert_eq!(docs, " # Errors\n Custom docs.");
+    }
+
+    #[test]
+    fn no_doc() {
+        let docs = expand(
+            quote!(std::io::Error, no_doc),
+            quote!(
+                fn foo() -> Result<(), _> {
+                    Ok(())
+                }
+            ),
+        );
+        assert_eq!(docs, "");
+    }
+}
@@ -0,0 +1,2138 @@
+use convert_case::{Case, Casing};
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{parse_quote, Attribute, Expr, ExprLit, Lit, Meta, Token, Type, TypeParamBound};
+
+use crate::args::{EntryKind, ErrorEntry, ErrorGroup, ErrorType, ErrorsArgs};
+
+pub(crate) fn generate_error_type(
+    args: &ErrorsArgs,
+    function_name: String,
+    vis: syn::Visibility,
+    derives: TokenStream,
+    enum_name: Option<syn::Ident>,
+) -> syn::Result<(TokenStream, syn::Ident)> {
+    let duplicate_warnings = check_duplicates(&args.entries)?;
+    check_cfg_derives(args)?;
+
+    // A explicit name is left to the compiler, the enum might be disabled by a `cfg_attr`.
+    let enum_name = if let Some(enum_name) = enum_name {
+        enum_name
+    } else {
+        let enum_name = function_name.to_case(Case::Pascal);
+        let enum_name = format_ident!("{enum_name}{}", args.suffix());
+        check_name_collision(&args.entries, &enum_name)?;
+        enum_name
+    };
+
+    let enum_stream = if args.opaque {
+        opaque_struct(args, &enum_name, &vis, &derives)?
+    } else {
+        generate_enum(
+            args,
+            &args.entries,
+            &enum_name,
+            &vis,
+            &derives,
+            args.allow_extra.as_ref(),
+            true,
+        )?
+    };
+    let json_error = json_error(args, &enum_name, &vis);
+    let serde_error = serde_error(args, &enum_name)?;
+    let nom_error = nom_error(args, &enum_name)?;
+    let boxed_impls = boxed_impls(args, &enum_name);
+    // Rebuild when `error_mancer.toml` changes.
+    let config_dependency = args.config_file.as_ref().map(|path| {
+        quote!(
+            const _: &[u8] = ::core::include_bytes!(#path);
+        )
+    });
+
+    Ok((
+        quote! {
+            #enum_stream #json_error #serde_error #nom_error #boxed_impls #config_dependency
+            #duplicate_warnings
+        },
+        enum_name,
+    ))
+}
+
+/// Suffix of the hidden enum wrapped by a `opaque` struct, i.e `FooErrorRepr`.
+const OPAQUE_REPR_SUFFIX: &str = "Repr";
+
+/// The public struct generated by `opaque`, wrapping the generated enum so its variants cant be
+/// matched on. Only the kind, the message and the source are exposed.
+fn opaque_struct(
+    args: &ErrorsArgs,
+    struct_name: &syn::Ident,
+    vis: &syn::Visibility,
+    derives: &TokenStream,
+) -> syn::Result<TokenStream> {
+    if args.json_error {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`json_error` cant be combined with `opaque`",
+        ));
+    }
+    // The variant names `classify` returns arent public.
+    if args.classify {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`classify` cant be combined with `opaque`",
+        ));
+    }
+
+    let repr_name = format_ident!("{struct_name}{OPAQUE_REPR_SUFFIX}");
+    let kind_name = format_ident!("{struct_name}Kind");
+    // The enum has to be as visible as the struct for the bounds below, it is hidden instead.
+    // Values of it can only be reached through the private field.
+    let repr = generate_enum(
+        args,
+        &args.entries,
+        &repr_name,
+        vis,
+        &quote!(#[doc(hidden)] #derives),
+        args.allow_extra.as_ref(),
+        false,
+    )?;
+    let error_trait = error_trait(args);
+
+    let derives_debug = derived_traits(derives)
+        .iter()
+        .any(|path| same_trait(path, &parse_quote!(Debug)));
+    let display_impl = if args.no_display {
+        quote!()
+    } else {
+        quote! {
+            impl ::core::fmt::Display for #struct_name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::core::fmt::Display::fmt(&self.0, f)
+                }
+            }
+        }
+    };
+    let debug_impl = if args.custom_debug || derives_debug {
+        quote!()
+    } else {
+        quote! {
+            impl ::core::fmt::Debug for #struct_name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::core::fmt::Debug::fmt(&self.0, f)
+                }
+            }
+        }
+    };
+    let flatten_impl = if args.no_flatten {
+        quote!()
+    } else {
+        quote! {
+            impl<T> ::error_mancer::FlattenInto<T> for #struct_name
+            where
+                #repr_name: ::error_mancer::FlattenInto<T>,
+            {
+                fn flatten(self) -> T {
+                    ::error_mancer::FlattenInto::flatten(self.0)
+                }
+            }
+        }
+    };
+    let kinds = if args.iter_kinds {
+        quote! {
+            /// Every kind this error can have, in declaration order.
+            pub fn kinds() -> impl ::core::iter::Iterator<Item = #kind_name> {
+                #repr_name::kinds()
+            }
+        }
+    } else {
+        quote!()
+    };
+    let span_trace = if args.span_trace {
+        quote! {
+            /// The span trace captured when this error was created.
+            pub fn span_trace(&self) -> &::error_mancer::SpanTrace {
+                self.0.span_trace()
+            }
+        }
+    } else {
+        quote!()
+    };
+    let try_flatten_impl = if args.try_flatten {
+        quote! {
+            #[doc(hidden)]
+            impl ::error_mancer::__private::FromPayload for #struct_name {
+                fn from_payload<P: 'static>(payload: P) -> ::core::result::Result<Self, P> {
+                    <#repr_name as ::error_mancer::__private::FromPayload>::from_payload(payload).map(Self)
+                }
+            }
+
+            impl<T: ::error_mancer::__private::FromPayload> ::error_mancer::TryFlattenInto<T> for #struct_name {
+                fn try_flatten(self) -> ::core::result::Result<T, Self> {
+                    ::error_mancer::TryFlattenInto::try_flatten(self.0).map_err(Self)
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+    let http_status_impl = if cfg!(feature = "http") {
+        quote! {
+            impl ::error_mancer::HttpStatusCode for #struct_name {
+                fn http_status(&self) -> u16 {
+                    ::error_mancer::HttpStatusCode::http_status(&self.0)
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    Ok(quote! {
+        #repr
+
+        #derives
+        #vis struct #struct_name(#repr_name);
+
+        #debug_impl
+
+        impl #struct_name {
+            /// Which kind of error this is.
+            pub fn kind(&self) -> #kind_name {
+                self.0.kind()
+            }
+
+            #kinds
+
+            #span_trace
+        }
+
+        #[doc(hidden)]
+        #[diagnostic::do_not_recommend]
+        impl<T> ::error_mancer::ErrorMancerFrom<T> for #struct_name
+        where
+            #repr_name: ::error_mancer::ErrorMancerFrom<T>,
+        {
+            fn from(value: T) -> Self {
+                Self(::error_mancer::ErrorMancerFrom::from(value))
+            }
+        }
+
+        impl<T> ::core::convert::From<T> for #struct_name where Self: ::error_mancer::ErrorMancerFrom<T> {
+            fn from(value: T) -> Self {
+                ::error_mancer::ErrorMancerFrom::from(value)
+            }
+        }
+
+        #[doc(hidden)]
+        impl<T, S> ::error_mancer::__private::Represents<T, S> for #struct_name
+        where
+            #repr_name: ::error_mancer::__private::Represents<T, S>,
+        {
+        }
+
+        #flatten_impl
+
+        #try_flatten_impl
+
+        #display_impl
+
+        impl #error_trait for #struct_name {
+            fn source(&self) -> ::core::option::Option<&(dyn #error_trait + 'static)> {
+                #error_trait::source(&self.0)
+            }
+        }
+
+        impl ::error_mancer::ErrorMeta for #struct_name {
+            fn http_status_for(&self) -> ::core::option::Option<u16> {
+                ::error_mancer::ErrorMeta::http_status_for(&self.0)
+            }
+
+            fn is_retryable(&self) -> bool {
+                ::error_mancer::ErrorMeta::is_retryable(&self.0)
+            }
+        }
+
+        #http_status_impl
+    })
+}
+
+/// The `serde::ser::Error` and `serde::de::Error` implementations generated by `serde_error`,
+/// the messages passed to `custom` are stored in the `String` entry.
+fn serde_error(args: &ErrorsArgs, enum_name: &syn::Ident) -> syn::Result<TokenStream> {
+    if !args.serde_error {
+        return Ok(quote!());
+    }
+    let message = leaf_errors(&args.entries)
+        .into_iter()
+        .map(|error_type| &error_type.ty)
+        .find(|ty| match ty {
+            Type::Path(syn::TypePath { qself: None, path }) => path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "String" && segment.arguments.is_empty()),
+            _ => false,
+        });
+    let Some(message) = message else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`serde_error` requires a `String` entry to hold the messages passed to `custom`",
+        ));
+    };
+
+    let custom = quote! {
+        fn custom<T: ::core::fmt::Display>(msg: T) -> Self {
+            let mut message = <#message>::new();
+            let _ = ::core::fmt::Write::write_fmt(&mut message, ::core::format_args!("{msg}"));
+            <Self as ::error_mancer::ErrorMancerFrom<#message>>::from(message)
+        }
+    };
+    Ok(quote! {
+        impl ::error_mancer::__private::serde::ser::Error for #enum_name {
+            #custom
+        }
+
+        impl ::error_mancer::__private::serde::de::Error for #enum_name {
+            #custom
+        }
+    })
+}
+
+/// The conversions for `boxed_self`, `Box<FooError>` accepts the same errors as the enum and
+/// flattens into the same enums.
+fn boxed_impls(args: &ErrorsArgs, enum_name: &syn::Ident) -> TokenStream {
+    if !args.boxed_self {
+        return quote!();
+    }
+    let boxed = quote!(::error_mancer::__private::Box<#enum_name>);
+    let try_flatten_impl = if args.try_flatten {
+        quote! {
+            #[doc(hidden)]
+            impl ::error_mancer::__private::FromPayload for #boxed {
+                fn from_payload<P: 'static>(payload: P) -> ::core::result::Result<Self, P> {
+                    <#enum_name as ::error_mancer::__private::FromPayload>::from_payload(payload)
+                        .map(::error_mancer::__private::Box::new)
+                }
+            }
+
+            impl<T: ::error_mancer::__private::FromPayload> ::error_mancer::TryFlattenInto<T> for #boxed {
+                fn try_flatten(self) -> ::core::result::Result<T, Self> {
+                    ::error_mancer::TryFlattenInto::try_flatten(*self)
+                        .map_err(::error_mancer::__private::Box::new)
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+    quote! {
+        #[doc(hidden)]
+        #[diagnostic::do_not_recommend]
+        impl<T> ::error_mancer::ErrorMancerFrom<T> for #boxed
+        where
+            #enum_name: ::error_mancer::ErrorMancerFrom<T>,
+        {
+            fn from(value: T) -> Self {
+                ::error_mancer::__private::Box::new(
+                    <#enum_name as ::error_mancer::ErrorMancerFrom<T>>::from(value),
+                )
+            }
+        }
+
+        impl<T> ::core::convert::From<T> for #boxed where Self: ::error_mancer::ErrorMancerFrom<T> {
+            fn from(value: T) -> Self {
+                ::error_mancer::ErrorMancerFrom::from(value)
+            }
+        }
+
+        impl<T, S> ::error_mancer::__private::Represents<T, S> for #boxed
+        where
+            #enum_name: ::error_mancer::__private::Represents<T, S>,
+        {
+        }
+
+        impl<T> ::error_mancer::FlattenInto<T> for #boxed
+        where
+            #enum_name: ::error_mancer::FlattenInto<T>,
+        {
+            fn flatten(self) -> T {
+                ::error_mancer::FlattenInto::flatten(*self)
+            }
+        }
+
+        #try_flatten_impl
+    }
+}
+
+/// nom's `ParseError`, `ContextError` and `FromExternalError` implementations generated by `nom`
+/// and `nom_trace`, nom's errors are stored in the `Nom` variant. With `nom_trace` the
+/// combinators and contexts are pushed onto its trace, otherwise the innermost error is kept.
+fn nom_error(args: &ErrorsArgs, enum_name: &syn::Ident) -> syn::Result<TokenStream> {
+    if !args.uses_nom() {
+        return Ok(quote!());
+    }
+    if args.opaque {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`nom` cant be combined with `opaque`",
+        ));
+    }
+
+    let nom = quote!(::error_mancer::__private::nom);
+    let push_frame = |frame: TokenStream| {
+        if args.nom_trace {
+            quote! {
+                let mut other = other;
+                if let Self::Nom(ref mut err, ..) = other {
+                    err.trace.push(::error_mancer::NomFrame::#frame);
+                }
+                other
+            }
+        } else {
+            quote!(other)
+        }
+    };
+    let append = push_frame(quote! {
+        Kind {
+            remaining: #nom::Input::input_len(&input),
+            kind,
+        }
+    });
+    let add_context = push_frame(quote! {
+        Context {
+            remaining: #nom::Input::input_len(&input),
+            context,
+        }
+    });
+    let unused_input = (!args.nom_trace).then(|| quote!(let _ = input;));
+
+    Ok(quote! {
+        impl<I: #nom::Input> #nom::error::ParseError<I> for #enum_name {
+            fn from_error_kind(input: I, kind: #nom::error::ErrorKind) -> Self {
+                <Self as ::error_mancer::ErrorMancerFrom<::error_mancer::NomError>>::from(
+                    ::error_mancer::NomError::new(&input, kind),
+                )
+            }
+
+            fn append(input: I, kind: #nom::error::ErrorKind, other: Self) -> Self {
+                #unused_input
+                #append
+            }
+        }
+
+        impl<I: #nom::Input> #nom::error::ContextError<I> for #enum_name {
+            fn add_context(input: I, context: &'static str, other: Self) -> Self {
+                #unused_input
+                #add_context
+            }
+        }
+
+        /// Errors returned from `map_res` and similar are stored in their own variant.
+        impl<I, E> #nom::error::FromExternalError<I, E> for #enum_name
+        where
+            Self: ::error_mancer::ErrorMancerFrom<E>,
+        {
+            fn from_external_error(_: I, _: #nom::error::ErrorKind, err: E) -> Self {
+                <Self as ::error_mancer::ErrorMancerFrom<E>>::from(err)
+            }
+        }
+
+        #[doc(hidden)]
+        #[diagnostic::do_not_recommend]
+        impl ::error_mancer::ErrorMancerFrom<#nom::Err<#enum_name>> for #enum_name {
+            fn from(value: #nom::Err<#enum_name>) -> Self {
+                match value {
+                    #nom::Err::Error(err) | #nom::Err::Failure(err) => err,
+                    #nom::Err::Incomplete(needed) => {
+                        <Self as ::error_mancer::ErrorMancerFrom<::error_mancer::NomError>>::from(
+                            ::error_mancer::NomError::incomplete(needed),
+                        )
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// The `FooJsonError` wrapper generated by `json_error`, serialized as
+/// `{"error": {"type": "StdIo", "message": "..."}}` with the `Display` text as the message.
+fn json_error(args: &ErrorsArgs, enum_name: &syn::Ident, vis: &syn::Visibility) -> TokenStream {
+    if !args.json_error {
+        return quote!();
+    }
+
+    let json_name = group_enum_name(args, enum_name, &format_ident!("Json"));
+    let mut kind_arms = args
+        .entries
+        .iter()
+        .map(|entry| {
+            let name = match &entry.kind {
+                EntryKind::Type(error_type) => error_type.variant_name(),
+                EntryKind::Group(group) => group.name.clone(),
+            };
+            let kind = name.to_string();
+            quote!(#enum_name::#name(..) => #kind)
+        })
+        .collect::<Vec<_>>();
+    if let Some(extra) = &args.allow_extra {
+        let kind = extra.to_string();
+        kind_arms.push(quote!(#enum_name::#extra(..) => #kind));
+    }
+
+    let into_response = if cfg!(feature = "axum") {
+        quote! {
+            impl ::error_mancer::__private::axum::response::IntoResponse for #json_name {
+                fn into_response(self) -> ::error_mancer::__private::axum::response::Response {
+                    ::error_mancer::__private::json_response(
+                        ::error_mancer::HttpStatusCode::http_status(&self.0),
+                        self,
+                    )
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let doc = format!("[`{enum_name}`] serialized as a JSON error body.");
+    quote! {
+        #[doc = #doc]
+        #vis struct #json_name(pub #enum_name);
+
+        impl<T> ::core::convert::From<T> for #json_name where #enum_name: ::core::convert::From<T> {
+            fn from(value: T) -> Self {
+                Self(#enum_name::from(value))
+            }
+        }
+
+        impl ::core::fmt::Debug for #json_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Debug::fmt(&self.0, f)
+            }
+        }
+
+        impl ::core::fmt::Display for #json_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl ::error_mancer::__private::serde::Serialize for #json_name {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: ::error_mancer::__private::serde::Serializer,
+            {
+                let kind = match &self.0 {
+                    #(#kind_arms,)*
+                    #[allow(unreachable_patterns)]
+                    _ => unreachable!()
+                };
+                ::error_mancer::__private::serialize_json_error(kind, &self.0, serializer)
+            }
+        }
+
+        #into_response
+    }
+}
+
+/// The traits derived by the `#[derive]` attributes in `derives`.
+fn derived_traits(derives: &TokenStream) -> Vec<syn::Path> {
+    let Ok(derives) = Attribute::parse_outer.parse2(derives.clone()) else {
+        return Vec::new();
+    };
+    derives
+        .iter()
+        .filter_map(|derive| {
+            derive
+                .parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .collect()
+}
+
+/// Whether two paths name the same trait, only the last segment is compared.
+pub(crate) fn same_trait(a: &syn::Path, b: &syn::Path) -> bool {
+    a.segments.last().map(|last| &last.ident) == b.segments.last().map(|last| &last.ident)
+}
+
+/// A trait in two `cfg_derive(..)`s would be derived twice when both predicates hold.
+fn check_cfg_derives(args: &ErrorsArgs) -> syn::Result<()> {
+    let mut seen: Vec<&syn::Path> = Vec::new();
+    for path in args
+        .cfg_derives
+        .iter()
+        .flat_map(|cfg_derive| &cfg_derive.traits)
+    {
+        if seen.iter().any(|seen| same_trait(seen, path)) {
+            return Err(syn::Error::new_spanned(
+                path,
+                format!(
+                    "`{}` is already derived conditionally, derive it in a single `cfg_derive`",
+                    path.to_token_stream()
+                ),
+            ));
+        }
+        seen.push(path);
+    }
+    Ok(())
+}
+
+/// A error named like the generated enum would be shadowed by it, so the enum would contain
+/// itself.
+fn check_name_collision(entries: &[ErrorEntry], enum_name: &syn::Ident) -> syn::Result<()> {
+    for leaf in leaf_errors(entries) {
+        let Type::Path(syn::TypePath { qself: None, path }) = leaf.payload() else {
+            continue;
+        };
+        if path.leading_colon.is_none()
+            && path.segments.len() == 1
+            && path.segments[0].ident == *enum_name
+        {
+            return Err(syn::Error::new_spanned(
+                leaf.payload(),
+                format!(
+                    "`{enum_name}` is also the name of the generated enum, write the error as a \
+                     path like `crate::...` or name the enum with `Result<T, Name>`"
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Best-effort check for a error listed twice. Paths cant be resolved, so two paths of at least
+/// two segments are assumed to be the same type when one ends with the other, i.e `io::Error` and
+/// `std::io::Error`. That is only a warning, a exact duplicate is a error.
+fn check_duplicates(entries: &[ErrorEntry]) -> syn::Result<TokenStream> {
+    let leaves = leaf_errors(entries);
+    let mut warnings = Vec::new();
+    for (index, later) in leaves.iter().enumerate() {
+        let Some(later_segments) = path_segments(&later.ty) else {
+            continue;
+        };
+        for earlier in &leaves[..index] {
+            let Some(earlier_segments) = path_segments(&earlier.ty) else {
+                continue;
+            };
+            let earlier = earlier.ty.to_token_stream().to_string().replace(' ', "");
+            let later_name = later.ty.to_token_stream().to_string().replace(' ', "");
+            if earlier == later_name {
+                return Err(syn::Error::new_spanned(
+                    &later.ty,
+                    format!("`{later_name}` is listed more than once"),
+                ));
+            }
+            // A single segment such as `ParseError` is too common to guess from.
+            let len = earlier_segments.len().min(later_segments.len());
+            if len >= 2
+                && earlier_segments[earlier_segments.len() - len..]
+                    == later_segments[later_segments.len() - len..]
+            {
+                let message = format!(
+                    "`{later_name}` and `{earlier}` likely refer to the same type, list it once \
+                     (or use a path like `crate::...` to tell them apart)"
+                );
+                warnings.push(warning(later.ty.span(), &message));
+            }
+        }
+    }
+    Ok(quote!(#(#warnings)*))
+}
+
+/// A warning at `span`, proc macros cant emit one directly so a deprecated item is used.
+fn warning(span: Span, message: &str) -> TokenStream {
+    let item = quote_spanned!(span=> DuplicateError);
+    quote! {
+        const _: () = {
+            #[deprecated(note = #message)]
+            struct DuplicateError;
+            let _ = #item;
+        };
+    }
+}
+
+/// The segments of a path type as strings, without a leading `::`.
+fn path_segments(ty: &Type) -> Option<Vec<String>> {
+    let Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    Some(
+        path.segments
+            .iter()
+            .map(|segment| segment.to_token_stream().to_string())
+            .collect(),
+    )
+}
+
+/// `From<Enum> for zbus::fdo::Error`, mapping each variant to the error kind given with
+/// `#[fdo = Kind]` (`Failed` by default) with the `Display` text as the message.
+pub(crate) fn zbus_impls(
+    args: &ErrorsArgs,
+    entries: &[ErrorEntry],
+    enum_name: &syn::Ident,
+    extra: Option<&syn::Ident>,
+) -> syn::Result<TokenStream> {
+    if !args.zbus {
+        return Ok(quote!());
+    }
+    // The variants of a `opaque` error arent public, so they cant be matched on here.
+    if args.opaque {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`zbus` cant be combined with `opaque`",
+        ));
+    }
+
+    let fdo = quote!(::error_mancer::__private::zbus::fdo);
+    let mut nested_impls = Vec::new();
+    let mut arms = Vec::new();
+    for entry in entries {
+        match &entry.kind {
+            EntryKind::Type(error_type) => {
+                let name = error_type.variant_name();
+                let kind = kind_attr(&entry.attrs, "fdo", "Failed")?;
+                arms.push(quote!(value @ #enum_name::#name(..) => #fdo::Error::#kind(
+                    ::error_mancer::__private::ToString::to_string(&value)
+                )));
+            }
+            EntryKind::Group(group) => {
+                let name = &group.name;
+                let inner_name = group_enum_name(args, enum_name, name);
+                nested_impls.push(zbus_impls(args, &group.entries, &inner_name, None)?);
+                arms.push(quote!(#enum_name::#name(err) => #fdo::Error::from(err)));
+            }
+        }
+    }
+    if let Some(extra) = extra {
+        arms.push(
+            quote!(value @ #enum_name::#extra(..) => #fdo::Error::Failed(
+                ::error_mancer::__private::ToString::to_string(&value)
+            )),
+        );
+    }
+
+    Ok(quote! {
+        #(#nested_impls)*
+
+        impl ::core::convert::From<#enum_name> for #fdo::Error {
+            fn from(value: #enum_name) -> Self {
+                match value {
+                    #(#arms,)*
+                    #[allow(unreachable_patterns)]
+                    _ => unreachable!()
+                }
+            }
+        }
+    })
+}
+
+/// `From<Enum> for clap::Error`, mapping each variant to the error kind given with
+/// `#[clap_kind = Kind]` (`ValueValidation` by default) with the `Display` text as the message.
+pub(crate) fn clap_impls(
+    args: &ErrorsArgs,
+    entries: &[ErrorEntry],
+    enum_name: &syn::Ident,
+    extra: Option<&syn::Ident>,
+) -> syn::Result<TokenStream> {
+    if !cfg!(feature = "clap") || args.opaque {
+        return Ok(quote!());
+    }
+
+    let mut nested_impls = Vec::new();
+    let mut arms = Vec::new();
+    for entry in entries {
+        match &entry.kind {
+            EntryKind::Type(error_type) => {
+                let name = error_type.variant_name();
+                let kind = kind_attr(&entry.attrs, "clap_kind", "ValueValidation")?;
+                arms.push(quote!(
+                    value @ #enum_name::#name(..) => ::error_mancer::__private::clap::Error::raw(
+                        ::error_mancer::__private::clap::error::ErrorKind::#kind,
+                        ::error_mancer::__private::ToString::to_string(&value),
+                    )
+                ));
+            }
+            EntryKind::Group(group) => {
+                let name = &group.name;
+                let inner_name = group_enum_name(args, enum_name, name);
+                nested_impls.push(clap_impls(args, &group.entries, &inner_name, None)?);
+                arms.push(quote!(
+                    #enum_name::#name(err) => ::error_mancer::__private::clap::Error::from(err)
+                ));
+            }
+        }
+    }
+    if let Some(extra) = extra {
+        arms.push(quote!(
+            value @ #enum_name::#extra(..) => ::error_mancer::__private::clap::Error::raw(
+                ::error_mancer::__private::clap::error::ErrorKind::ValueValidation,
+                ::error_mancer::__private::ToString::to_string(&value),
+            )
+        ));
+    }
+
+    Ok(quote! {
+        #(#nested_impls)*
+
+        impl ::core::convert::From<#enum_name> for ::error_mancer::__private::clap::Error {
+            fn from(value: #enum_name) -> Self {
+                match value {
+                    #(#arms,)*
+                    #[allow(unreachable_patterns)]
+                    _ => unreachable!()
+                }
+            }
+        }
+    })
+}
+
+/// `IntoPropValue<String>` and `From<Enum> for Classes`, so errors can be passed to Yew
+/// components. The classes are the kebab-case variant names, including the groups.
+pub(crate) fn yew_impls(
+    args: &ErrorsArgs,
+    entries: &[ErrorEntry],
+    enum_name: &syn::Ident,
+    extra: Option<&syn::Ident>,
+) -> syn::Result<TokenStream> {
+    if !args.yew {
+        return Ok(quote!());
+    }
+    if args.opaque {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`yew` cant be combined with `opaque`",
+        ));
+    }
+
+    let html = quote!(::error_mancer::__private::yew::html);
+    let mut nested_impls = Vec::new();
+    let mut arms = Vec::new();
+    for entry in entries {
+        match &entry.kind {
+            EntryKind::Type(error_type) => {
+                let name = error_type.variant_name();
+                let class = name.to_string().to_case(Case::Kebab);
+                arms.push(quote!(#enum_name::#name(..) => #html::Classes::from(#class)));
+            }
+            EntryKind::Group(group) => {
+                let name = &group.name;
+                let class = name.to_string().to_case(Case::Kebab);
+                let inner_name = group_enum_name(args, enum_name, name);
+                nested_impls.push(yew_impls(args, &group.entries, &inner_name, None)?);
+                arms.push(quote!(#enum_name::#name(err) => {
+                    let mut classes = #html::Classes::from(#class);
+                    classes.push(err);
+                    classes
+                }));
+            }
+        }
+    }
+    if let Some(extra) = extra {
+        let class = extra.to_string().to_case(Case::Kebab);
+        arms.push(quote!(#enum_name::#extra(..) => #html::Classes::from(#class)));
+    }
+
+    Ok(quote! {
+        #(#nested_impls)*
+
+        impl #html::IntoPropValue<::error_mancer::__private::String> for #enum_name {
+            fn into_prop_value(self) -> ::error_mancer::__private::String {
+                ::error_mancer::__private::ToString::to_string(&self)
+            }
+        }
+
+        impl ::core::convert::From<#enum_name> for #html::Classes {
+            fn from(value: #enum_name) -> Self {
+                match value {
+                    #(#arms,)*
+                    #[allow(unreachable_patterns)]
+                    _ => unreachable!()
+                }
+            }
+        }
+    })
+}
+
+/// The error kind given with `#[name = Kind]` on a entry, i.e `#[fdo = AccessDenied]`.
+fn kind_attr(attrs: &[Attribute], name: &str, default: &str) -> syn::Result<syn::Ident> {
+    for attr in attrs {
+        let Meta::NameValue(meta) = &attr.meta else {
+            continue;
+        };
+        if meta.path.is_ident(name) {
+            let kind = match &meta.value {
+                Expr::Path(path) => path.path.get_ident(),
+                _ => None,
+            };
+            return kind
+                .cloned()
+                .ok_or_else(|| syn::Error::new(meta.value.span(), "Expected a error kind"));
+        }
+    }
+    Ok(format_ident!("{default}"))
+}
+
+/// The OpenTelemetry status given with `#[otel_status = Ok]` on a entry, `Error` by default.
+fn otel_status_attr(attrs: &[Attribute]) -> syn::Result<syn::Ident> {
+    let status = kind_attr(attrs, "otel_status", "Error")?;
+    if !["Ok", "Unset", "Error"].iter().any(|valid| status == valid) {
+        return Err(syn::Error::new(
+            status.span(),
+            "Expected `Ok`, `Unset` or `Error`",
+        ));
+    }
+    Ok(status)
+}
+
+/// The status code given with `#[http_status = 503]` on a entry.
+fn http_status_attr(attrs: &[Attribute]) -> syn::Result<Option<syn::LitInt>> {
+    for attr in attrs {
+        let Meta::NameValue(meta) = &attr.meta else {
+            continue;
+        };
+        if meta.path.is_ident("http_status") {
+            let Expr::Lit(ExprLit {
+                lit: Lit::Int(status),
+                ..
+            }) = &meta.value
+            else {
+                return Err(syn::Error::new(meta.value.span(), "Expected a status code"));
+            };
+            status.base10_parse::<u16>()?;
+            return Ok(Some(status.clone()));
+        }
+    }
+    Ok(None)
+}
+
+/// The discriminant given with `#[discriminant = 1]` on a entry.
+fn discriminant_attr(attrs: &[Attribute]) -> Option<&Expr> {
+    attrs.iter().find_map(|attr| match &attr.meta {
+        Meta::NameValue(meta) if meta.path.is_ident("discriminant") => Some(&meta.value),
+        _ => None,
+    })
+}
+
+/// A fieldless `Kind` enum with a variant for each variant of the error enum, using the
+/// discriminants given with `#[discriminant = 1]`, and a `kind()` method returning it.
+fn kind_enum(
+    args: &ErrorsArgs,
+    entries: &[ErrorEntry],
+    enum_name: &syn::Ident,
+    vis: &syn::Visibility,
+    extra: Option<&syn::Ident>,
+) -> syn::Result<TokenStream> {
+    if !args.kind && !args.opaque {
+        if let Some(discriminant) = entries
+            .iter()
+            .find_map(|entry| discriminant_attr(&entry.attrs))
+        {
+            return Err(syn::Error::new_spanned(
+                discriminant,
+                "`#[discriminant]` requires the `kind` flag",
+            ));
+        }
+        if args.iter_kinds {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`iter_kinds` requires the `kind` flag",
+            ));
+        }
+        return Ok(quote!());
+    }
+
+    // The kind of a `opaque` struct is named after the struct, not the hidden enum.
+    let public_name = match enum_name.to_string().strip_suffix(OPAQUE_REPR_SUFFIX) {
+        Some(public_name) if args.opaque => format_ident!("{public_name}"),
+        _ => enum_name.clone(),
+    };
+    let kind_name = format_ident!("{public_name}Kind");
+    let mut variants = Vec::new();
+    let mut names = Vec::new();
+    let mut arms = Vec::new();
+    for entry in entries {
+        let name = match &entry.kind {
+            EntryKind::Type(error_type) => error_type.variant_name(),
+            EntryKind::Group(group) => group.name.clone(),
+        };
+        variants.push(match discriminant_attr(&entry.attrs) {
+            Some(discriminant) => quote!(#name = #discriminant),
+            None => quote!(#name),
+        });
+        arms.push(quote!(Self::#name(..) => #kind_name::#name));
+        names.push(name);
+    }
+    if let Some(extra) = extra {
+        variants.push(quote!(#extra));
+        names.push(extra.clone());
+        arms.push(quote!(Self::#extra(..) => #kind_name::#extra));
+    }
+
+    // A enum without variants cant have a `repr`.
+    let repr = if variants.is_empty() {
+        quote!()
+    } else {
+        quote!(#[repr(i32)])
+    };
+    let kinds = if args.iter_kinds {
+        quote! {
+            /// Every kind this error can have, in declaration order.
+            pub fn kinds() -> impl ::core::iter::Iterator<Item = #kind_name> {
+                [#(#kind_name::#names),*].into_iter()
+            }
+        }
+    } else {
+        quote!()
+    };
+    let doc = format!("The variants of [`{public_name}`] without their payloads.");
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(
+            ::core::fmt::Debug,
+            ::core::clone::Clone,
+            ::core::marker::Copy,
+            ::core::cmp::PartialEq,
+            ::core::cmp::Eq,
+            ::core::hash::Hash,
+        )]
+        #repr
+        #vis enum #kind_name {
+            #(#variants),*
+        }
+
+        impl #enum_name {
+            /// Which variant this error is, without the payload.
+            pub fn kind(&self) -> #kind_name {
+                match self {
+                    #(#arms,)*
+                    _ => unreachable!()
+                }
+            }
+
+            #kinds
+        }
+    })
+}
+
+/// The name of the nested enum generated for a group, i.e `Combined` in `FooError` becomes
+/// `FooCombinedError`.
+fn group_enum_name(args: &ErrorsArgs, enum_name: &syn::Ident, group: &syn::Ident) -> syn::Ident {
+    let suffix = args.suffix();
+    let base = enum_name.to_string();
+    let base = base.trim_end_matches(suffix);
+    format_ident!("{base}{group}{suffix}")
+}
+
+/// All the error types listed in `entries`, this includes the types inside groups.
+pub(crate) fn leaf_errors(entries: &[ErrorEntry]) -> Vec<&ErrorType> {
+    entries
+        .iter()
+        .flat_map(|entry| match &entry.kind {
+            EntryKind::Type(error_type) => vec![error_type],
+            EntryKind::Group(group) => leaf_errors(&group.entries),
+        })
+        .collect()
+}
+
+/// All the payload types that can be converted into a enum generated from `entries`.
+fn leaf_types(entries: &[ErrorEntry]) -> Vec<Type> {
+    leaf_errors(entries)
+        .into_iter()
+        .map(|error_type| error_type.payload().clone())
+        .collect()
+}
+
+/// Whether any of the errors in `entries` is marked `retryable`.
+fn has_retryable(entries: &[ErrorEntry]) -> bool {
+    leaf_errors(entries)
+        .iter()
+        .any(|error_type| error_type.retryable)
+}
+
+/// Converting any `std::sync::PoisonError<G>` into a enum with a `poison` entry, the guard
+/// borrows the lock so it cant be kept in the variant.
+fn poison_from_impl(enum_name: &syn::Ident) -> TokenStream {
+    quote! {
+        #[doc(hidden)]
+        #[diagnostic::do_not_recommend]
+        impl<G> ::error_mancer::ErrorMancerFrom<::error_mancer::__private::PoisonError<G>>
+            for #enum_name
+        {
+            fn from(value: ::error_mancer::__private::PoisonError<G>) -> Self {
+                <Self as ::error_mancer::ErrorMancerFrom<::error_mancer::Poisoned>>::from(
+                    ::core::convert::From::from(value),
+                )
+            }
+        }
+    }
+}
+
+/// Converting `winnow::error::ErrMode<E>` into a enum which already implements
+/// `ErrorMancerFrom<E>`, `ErrMode::Incomplete` goes into the `Incomplete` variant.
+fn err_mode_from_impl(enum_name: &syn::Ident, error_type: &ErrorType) -> TokenStream {
+    let Some((err_mode, inner)) = error_type.err_mode() else {
+        return quote!();
+    };
+    let ty = &error_type.ty;
+    quote! {
+        #[doc(hidden)]
+        #[diagnostic::do_not_recommend]
+        impl ::error_mancer::ErrorMancerFrom<#ty> for #enum_name {
+            fn from(value: #ty) -> Self {
+                match value {
+                    #err_mode::Backtrack(err) | #err_mode::Cut(err) => {
+                        <Self as ::error_mancer::ErrorMancerFrom<#inner>>::from(err)
+                    }
+                    #err_mode::Incomplete(needed) => {
+                        <Self as ::error_mancer::ErrorMancerFrom<::error_mancer::Incomplete>>::from(
+                            ::error_mancer::Incomplete { needed },
+                        )
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The path to the `Error` trait, `core::error::Error` unless the `std_error` feature is
+/// enabled or `use_std_error` is passed.
+fn error_trait(args: &ErrorsArgs) -> TokenStream {
+    if args.use_std_error {
+        quote!(::std::error::Error)
+    } else {
+        quote!(::error_mancer::__private::Error)
+    }
+}
+
+/// The payload of the `allow_extra` variant.
+fn extra_type(args: &ErrorsArgs) -> Type {
+    let error_trait = error_trait(args);
+    parse_quote!(
+        ::error_mancer::__private::Box<
+            dyn #error_trait + ::core::marker::Send + ::core::marker::Sync,
+        >
+    )
+}
+
+/// The Fluent message id used for variants without a `msg_id`, i.e `StdIo` becomes `std-io`.
+fn default_msg_id(name: &syn::Ident) -> String {
+    name.to_string().to_case(Case::Kebab)
+}
+
+/// How a variant is displayed.
+enum DisplayKind {
+    /// Delegate to the payloads `Display` implementation.
+    Delegate,
+    /// A fixed message.
+    Static(String),
+    /// Call a user provided `fn(&Payload, &mut Formatter) -> fmt::Result`.
+    With(syn::Path),
+}
+
+impl DisplayKind {
+    /// Read the `#[display = "..."]` and `#[display_with = path]` attributes, falling back to
+    /// the variant name for redacted errors and trait objects that arent known to be `Display`.
+    fn from_entry(
+        attrs: &[Attribute],
+        ty: &Type,
+        name: &syn::Ident,
+        redact: bool,
+    ) -> syn::Result<Self> {
+        for attr in attrs {
+            let Meta::NameValue(meta) = &attr.meta else {
+                continue;
+            };
+            if meta.path.is_ident("display") {
+                let Expr::Lit(ExprLit {
+                    lit: Lit::Str(message),
+                    ..
+                }) = &meta.value
+                else {
+                    return Err(syn::Error::new(
+                        meta.value.span(),
+                        "Expected a string literal",
+                    ));
+                };
+                return Ok(Self::Static(message.value()));
+            }
+            if meta.path.is_ident("display_with") {
+                let Expr::Path(path) = &meta.value else {
+                    return Err(syn::Error::new(
+                        meta.value.span(),
+                        "Expected a path to a function",
+                    ));
+                };
+                return Ok(Self::With(path.path.clone()));
+            }
+        }
+
+        if redact {
+            Ok(Self::Static(format!("{name} (details redacted)")))
+        } else if is_opaque_trait_object(ty) {
+            Ok(Self::Static(name.to_string()))
+        } else {
+            Ok(Self::Delegate)
+        }
+    }
+
+    /// Delegating is spanned on `ty`, so a missing `Display` implementation is reported there.
+    fn arm(&self, name: &syn::Ident, ty: &Type) -> TokenStream {
+        match self {
+            Self::Delegate => {
+                let payload =
+                    quote_spanned!(ty.span()=> ::error_mancer::__private::display_payload(err));
+                quote!(Self::#name(err, ..) => ::core::fmt::Display::fmt(#payload, f))
+            }
+            Self::Static(message) => quote!(Self::#name(..) => f.write_str(#message)),
+            Self::With(path) => quote!(Self::#name(err, ..) => #path(err, f)),
+        }
+    }
+
+    /// A delegating variant is transparent so its source is the source of the payload,
+    /// otherwise the payload itself is the source.
+    fn source_arm(&self, name: &syn::Ident) -> TokenStream {
+        let forward = match self {
+            Self::Delegate => quote!(forward_source),
+            Self::Static(_) | Self::With(_) => quote!(forward_as_source),
+        };
+        quote!(Self::#name(err, ..) => (&::error_mancer::__private::Transparent(err)).#forward())
+    }
+}
+
+/// Returns true for `Box<dyn Trait>` (or similar) where none of the bounds is `Error` or
+/// `Display`, as those cant be displayed.
+fn is_opaque_trait_object(ty: &Type) -> bool {
+    let object = match ty {
+        Type::TraitObject(object) => object,
+        Type::Path(path) => {
+            let Some(segment) = path.path.segments.last() else {
+                return false;
+            };
+            let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+                return false;
+            };
+            match arguments.args.first() {
+                Some(syn::GenericArgument::Type(Type::TraitObject(object))) => object,
+                _ => return false,
+            }
+        }
+        _ => return false,
+    };
+
+    !object.bounds.iter().any(|bound| match bound {
+        TypeParamBound::Trait(bound) => bound
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Error" || segment.ident == "Display"),
+        _ => false,
+    })
+}
+
+/// A variant of the generated enum.
+#[allow(clippy::large_enum_variant)]
+enum Variant<'a> {
+    /// A variant holding a single error type.
+    Leaf {
+        name: syn::Ident,
+        ty: Type,
+        display: DisplayKind,
+        msg_id: String,
+        retryable: bool,
+        /// Print the payload as `<redacted>` in the `Debug` implementation.
+        redact: bool,
+        /// Hide the payload from `Error::source` and `Error::provide`, set by `redact`.
+        no_source: bool,
+        /// The `embedded_io::ErrorKind` used when the payload doesnt implement `embedded_io::Error`.
+        io_kind: syn::Ident,
+        /// The status code given with `#[http_status = 503]`.
+        http_status: Option<syn::LitInt>,
+        /// The OpenTelemetry status given with `#[otel_status = Ok]`.
+        otel_status: syn::Ident,
+    },
+    /// A variant holding a nested enum.
+    Group(&'a ErrorGroup),
+}
+
+/// `hand_written` is set for the enum `custom_debug` and `no_display` apply to, nested group enums
+/// and the enum wrapped by `opaque` always implement `Debug` and `Display`.
+fn generate_enum(
+    args: &ErrorsArgs,
+    entries: &[ErrorEntry],
+    enum_name: &syn::Ident,
+    vis: &syn::Visibility,
+    derives: &TokenStream,
+    extra: Option<&syn::Ident>,
+    hand_written: bool,
+) -> syn::Result<TokenStream> {
+    let custom_debug = hand_written && args.custom_debug;
+    let error_trait = error_trait(args);
+    let kind_impl = kind_enum(args, entries, enum_name, vis, extra)?;
+    let mut variants = entries
+        .iter()
+        .map(|entry| match &entry.kind {
+            EntryKind::Type(error_type) => {
+                let name = error_type.variant_name();
+                let display = DisplayKind::from_entry(
+                    &entry.attrs,
+                    error_type.payload(),
+                    &name,
+                    error_type.redact,
+                )?;
+                let msg_id = error_type
+                    .msg_id
+                    .as_ref()
+                    .map_or_else(|| default_msg_id(&name), syn::LitStr::value);
+                Ok(Variant::Leaf {
+                    name,
+                    ty: error_type.payload().clone(),
+                    display,
+                    msg_id,
+                    retryable: error_type.retryable,
+                    redact: args.no_debug || error_type.no_debug || error_type.redact,
+                    no_source: error_type.redact,
+                    io_kind: kind_attr(&entry.attrs, "io_kind", "Other")?,
+                    http_status: http_status_attr(&entry.attrs)?,
+                    otel_status: otel_status_attr(&entry.attrs)?,
+                })
+            }
+            EntryKind::Group(group) => Ok(Variant::Group(group)),
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    if let Some(extra) = extra {
+        variants.push(Variant::Leaf {
+            name: extra.clone(),
+            ty: extra_type(args),
+            display: DisplayKind::Delegate,
+            msg_id: default_msg_id(extra),
+            retryable: false,
+            redact: args.no_debug,
+            no_source: false,
+            io_kind: format_ident!("Other"),
+            http_status: None,
+            otel_status: format_ident!("Error"),
+        });
+    }
+
+    let default_status = args
+        .http_status
+        .clone()
+        .unwrap_or_else(|| parse_quote!(500));
+
+    let mut fields = Vec::new();
+    let mut from_impls = Vec::new();
+    let mut flatten_arms = Vec::new();
+    let mut group_flatten_bounds = Vec::new();
+    let mut display_arms = Vec::new();
+    let mut group_patterns = Vec::new();
+    let mut nested_enums = Vec::new();
+    let mut span_trace_arms = Vec::new();
+    let mut msg_id_arms = Vec::new();
+    let mut provide_arms = Vec::new();
+    let mut source_arms = Vec::new();
+    let mut retryable_arms = Vec::new();
+    let mut debug_arms = Vec::new();
+    let mut http_status_arms = Vec::new();
+    let mut io_kind_arms = Vec::new();
+    let mut http_status_code_arms = Vec::new();
+    let mut meta_retryable_arms = Vec::new();
+    let mut payloads = Vec::new();
+    let mut classify_checks = Vec::new();
+    let mut otel_status_arms = Vec::new();
+    let mut try_flatten_arms = Vec::new();
+    let mut variant_name_arms = Vec::new();
+    let mut dispatch_arms = Vec::new();
+    let mut constructors = Vec::new();
+
+    for variant in variants {
+        match variant {
+            Variant::Leaf {
+                name,
+                ty,
+                display,
+                msg_id,
+                retryable,
+                redact,
+                no_source,
+                io_kind,
+                http_status,
+                otel_status,
+            } => {
+                payloads.push((name.clone(), ty.clone()));
+                let variant = name.to_string();
+                variant_name_arms.push(quote!(Self::#name(..) => #variant));
+                // Anything else would be converted into the catch-all variant, so its checked last.
+                if extra != Some(&name) {
+                    classify_checks.push(quote! {
+                        if (&::error_mancer::__private::Downcast::<#ty>::new()).forward_is(err) {
+                            return ::core::option::Option::Some(#variant);
+                        }
+                    });
+                }
+                let payload = if redact {
+                    quote!(&::core::format_args!("<redacted>"))
+                } else {
+                    quote_spanned!(ty.span()=> ::error_mancer::__private::debug_payload(err))
+                };
+                let (pattern, trace) = if args.span_trace {
+                    (quote!(Self::#name(err, trace)), quote!(.field(trace)))
+                } else {
+                    (quote!(Self::#name(err)), quote!())
+                };
+                debug_arms.push(quote! {
+                    #[allow(unused_variables)]
+                    #pattern => f.debug_tuple(#variant).field(#payload)#trace.finish()
+                });
+
+                let construct = if args.span_trace {
+                    fields.push(quote!(#name(#ty, ::error_mancer::SpanTrace)));
+                    span_trace_arms.push(quote!(Self::#name(_, trace) => trace));
+                    quote!(Self::#name(value, ::error_mancer::SpanTrace::capture()))
+                } else {
+                    fields.push(quote!(#name(#ty)));
+                    quote!(Self::#name(value))
+                };
+                let construct = if args.opentelemetry {
+                    quote! {
+                        let err = #construct;
+                        ::error_mancer::__private::record_otel_error(&err, err.otel_status());
+                        err
+                    }
+                } else {
+                    construct
+                };
+                constructors.push((ty.span(), constructor(&name, &ty, &construct)));
+                from_impls.push(quote! {
+                    #[doc(hidden)]
+                    #[diagnostic::do_not_recommend]
+                    impl ::error_mancer::ErrorMancerFrom<#ty> for #enum_name {
+                        fn from(value: #ty) -> Self {
+                            #construct
+                        }
+                    }
+                });
+                otel_status_arms.push(if otel_status == "Error" {
+                    quote!(Self::#name(..) => ::error_mancer::__private::opentelemetry::trace::Status::error(
+                        ::error_mancer::__private::ToString::to_string(self)
+                    ))
+                } else {
+                    quote!(Self::#name(..) => ::error_mancer::__private::opentelemetry::trace::Status::#otel_status)
+                });
+                flatten_arms.push(quote!(Self::#name(err, ..) => T::from(err)));
+                try_flatten_arms.push(if args.span_trace {
+                    quote!(
+                        Self::#name(err, trace) => ::error_mancer::__private::FromPayload::from_payload(err)
+                            .map_err(|err| Self::#name(err, trace))
+                    )
+                } else {
+                    quote!(
+                        Self::#name(err) => ::error_mancer::__private::FromPayload::from_payload(err)
+                            .map_err(Self::#name)
+                    )
+                });
+                if args.dispatch {
+                    if !matches!(display, DisplayKind::Delegate) {
+                        return Err(syn::Error::new(
+                            ty.span(),
+                            "`dispatch` requires every payload to be displayed by its own \
+                             `Display` implementation",
+                        ));
+                    }
+                    // Spanned so a payload that isnt a error is reported on its type.
+                    let inner = quote_spanned!(ty.span()=> err);
+                    dispatch_arms.push(quote!(Self::#name(err, ..) => #inner));
+                }
+                display_arms.push(display.arm(&name, &ty));
+                // Left to the `None` fallback, so the payload cant be reached through the chain.
+                if !no_source {
+                    source_arms.push(display.source_arm(&name));
+                    provide_arms.push(quote!(Self::#name(err, ..) => err));
+                }
+                if retryable {
+                    retryable_arms.push(quote!(Self::#name(..) => true));
+                    meta_retryable_arms.push(quote!(Self::#name(..) => true));
+                } else {
+                    meta_retryable_arms.push(quote!(
+                        Self::#name(err, ..) => (&::error_mancer::__private::Transparent(err)).forward_retryable()
+                    ));
+                }
+                http_status_arms.push(quote!(
+                    Self::#name(err, ..) => (&::error_mancer::__private::Transparent(err)).forward_http_status()
+                ));
+                http_status_code_arms.push(match http_status {
+                    Some(status) => quote!(Self::#name(..) => #status),
+                    None => quote!(
+                        Self::#name(err, ..) => (&::error_mancer::__private::Transparent(err))
+                            .forward_http_status()
+                            .unwrap_or(#default_status)
+                    ),
+                });
+                io_kind_arms.push(quote!(
+                    Self::#name(err, ..) => (&::error_mancer::__private::Transparent(err))
+                        .forward_kind(::error_mancer::__private::embedded_io::ErrorKind::#io_kind)
+                ));
+                msg_id_arms.push(quote!(Self::#name(..) => #msg_id));
+            }
+            Variant::Group(group) => {
+                let name = &group.name;
+                let inner_name = group_enum_name(args, enum_name, name);
+                nested_enums.push(generate_enum(
+                    args,
+                    &group.entries,
+                    &inner_name,
+                    vis,
+                    derives,
+                    None,
+                    false,
+                )?);
+
+                payloads.push((name.clone(), parse_quote!(#inner_name)));
+                let variant = name.to_string();
+                variant_name_arms.push(quote!(Self::#name(..) => #variant));
+                classify_checks.push(quote! {
+                    if #inner_name::classify(err).is_some() {
+                        return ::core::option::Option::Some(#variant);
+                    }
+                });
+                fields.push(quote!(#name(#inner_name)));
+                constructors.push((
+                    name.span(),
+                    constructor(
+                        name,
+                        &parse_quote!(#inner_name),
+                        &quote!(Self::#name(value)),
+                    ),
+                ));
+                from_impls.push(quote! {
+                    #[doc(hidden)]
+                    #[diagnostic::do_not_recommend]
+                    impl ::error_mancer::ErrorMancerFrom<#inner_name> for #enum_name {
+                        fn from(value: #inner_name) -> Self {
+                            Self::#name(value)
+                        }
+                    }
+                });
+                for leaf in leaf_types(&group.entries) {
+                    from_impls.push(quote! {
+                        #[doc(hidden)]
+                        #[diagnostic::do_not_recommend]
+                        impl ::error_mancer::ErrorMancerFrom<#leaf> for #enum_name {
+                            fn from(value: #leaf) -> Self {
+                                Self::#name(
+                                    <#inner_name as ::error_mancer::ErrorMancerFrom<#leaf>>::from(value)
+                                )
+                            }
+                        }
+                    });
+                }
+                flatten_arms.push(quote!(
+                    Self::#name(err) => ::error_mancer::FlattenInto::<T>::flatten(err)
+                ));
+                group_flatten_bounds.push(quote!(#inner_name: ::error_mancer::FlattenInto<T>));
+                try_flatten_arms.push(quote!(
+                    Self::#name(err) => ::error_mancer::TryFlattenInto::<T>::try_flatten(err)
+                        .map_err(Self::#name)
+                ));
+                otel_status_arms.push(quote!(Self::#name(err) => err.otel_status()));
+                display_arms.push(quote!(Self::#name(err) => ::core::fmt::Display::fmt(err, f)));
+                dispatch_arms.push(quote!(Self::#name(err) => err));
+                group_patterns.push(quote!(Self::#name(..)));
+                span_trace_arms.push(quote!(Self::#name(err) => err.span_trace()));
+                msg_id_arms.push(quote!(
+                    Self::#name(err) => ::error_mancer::MessageId::message_id(err)
+                ));
+                provide_arms.push(quote!(Self::#name(err) => err));
+                http_status_arms.push(
+                    quote!(Self::#name(err) => ::error_mancer::ErrorMeta::http_status_for(err)),
+                );
+                meta_retryable_arms
+                    .push(quote!(Self::#name(err) => ::error_mancer::ErrorMeta::is_retryable(err)));
+                http_status_code_arms.push(quote!(
+                    Self::#name(err) => ::error_mancer::HttpStatusCode::http_status(err)
+                ));
+                io_kind_arms.push(quote!(
+                    Self::#name(err) => ::error_mancer::__private::embedded_io::Error::kind(err)
+                ));
+                source_arms.push(quote!(Self::#name(err) => #error_trait::source(err)));
+                let variant = name.to_string();
+                debug_arms
+                    .push(quote!(Self::#name(err) => f.debug_tuple(#variant).field(err).finish()));
+                if has_retryable(&group.entries) {
+                    retryable_arms.push(quote!(Self::#name(err) => err.retryable()));
+                }
+            }
+        }
+    }
+
+    for error_type in leaf_errors(entries) {
+        from_impls.push(err_mode_from_impl(enum_name, error_type));
+        if error_type.poison {
+            from_impls.push(poison_from_impl(enum_name));
+        }
+    }
+
+    let mut leaves = leaf_types(entries);
+    if extra.is_some() {
+        leaves.push(extra_type(args));
+    }
+    let represents_impls = leaves.iter().map(|leaf| {
+        quote! {
+            #[doc(hidden)]
+            impl<S> ::error_mancer::__private::Represents<#leaf, S> for #enum_name {}
+        }
+    });
+    let flatten_impl = if args.no_flatten {
+        quote!()
+    } else {
+        quote! {
+            impl<T> ::error_mancer::FlattenInto<T> for #enum_name
+                where
+                    T: #(::error_mancer::__private::Represents<#leaves, #enum_name>)+*,
+                    #(#group_flatten_bounds,)*
+            {
+                fn flatten(self) -> T {
+                    match self {
+                        #(#flatten_arms,)*
+                        _ => unreachable!()
+                    }
+                }
+            }
+        }
+    };
+
+    // Payloads are matched by downcasting, as `T` is generic.
+    let from_payload_body = if leaves.is_empty() {
+        quote!(::core::result::Result::Err(payload))
+    } else {
+        quote! {
+            let mut payload = ::core::option::Option::Some(payload);
+            let any: &mut dyn ::core::any::Any = &mut payload;
+            #(
+                if let ::core::option::Option::Some(value) = any
+                    .downcast_mut::<::core::option::Option<#leaves>>()
+                    .and_then(::core::option::Option::take)
+                {
+                    return ::core::result::Result::Ok(
+                        <Self as ::error_mancer::ErrorMancerFrom<#leaves>>::from(value)
+                    );
+                }
+            )*
+            match payload {
+                ::core::option::Option::Some(payload) => ::core::result::Result::Err(payload),
+                ::core::option::Option::None => unreachable!(),
+            }
+        }
+    };
+    let try_flatten_impl = if args.try_flatten {
+        quote! {
+            #[doc(hidden)]
+            impl ::error_mancer::__private::FromPayload for #enum_name {
+                fn from_payload<P: 'static>(payload: P) -> ::core::result::Result<Self, P> {
+                    #from_payload_body
+                }
+            }
+
+            impl<T: ::error_mancer::__private::FromPayload> ::error_mancer::TryFlattenInto<T> for #enum_name {
+                fn try_flatten(self) -> ::core::result::Result<T, Self> {
+                    match self {
+                        #(#try_flatten_arms,)*
+                        #[allow(unreachable_patterns)]
+                        _ => unreachable!()
+                    }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    // Groups are displayed with the context by their own enum.
+    let display_context = args.display_context.as_ref().map(|context| {
+        let prefix = format!("{}: ", context.value());
+        if group_patterns.is_empty() {
+            quote!(f.write_str(#prefix)?;)
+        } else {
+            quote! {
+                if !matches!(self, #(#group_patterns)|*) {
+                    f.write_str(#prefix)?;
+                }
+            }
+        }
+    });
+
+    for (conflicting, name) in [
+        (args.dispatch, "dispatch"),
+        (args.impl_display_debug, "impl_display_debug"),
+    ] {
+        if args.no_display && conflicting {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                format!("`no_display` cant be combined with `{name}`"),
+            ));
+        }
+    }
+
+    // Dispatching shares the `Display` and `Error` code through `dyn Error`, so each enum only
+    // generates the `match` in `inner`.
+    let (display_impl, source_fn) = if args.dispatch {
+        if let Some(context) = &args.display_context {
+            return Err(syn::Error::new(
+                context.span(),
+                "`display_context` cant be combined with `dispatch`",
+            ));
+        }
+        if args.impl_display_debug {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`impl_display_debug` cant be combined with `dispatch`",
+            ));
+        }
+        let display_impl = quote! {
+            impl ::error_mancer::VariantDispatch for #enum_name {
+                fn inner(&self) -> &(dyn ::error_mancer::__private::Error + 'static) {
+                    match self {
+                        #(#dispatch_arms,)*
+                        #[allow(unreachable_patterns)]
+                        _ => unreachable!()
+                    }
+                }
+            }
+
+            impl ::core::fmt::Display for #enum_name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::core::fmt::Display::fmt(::error_mancer::VariantDispatch::inner(self), f)
+                }
+            }
+        };
+        let source_fn = quote! {
+            fn source(&self) -> ::core::option::Option<&(dyn #error_trait + 'static)> {
+                ::error_mancer::VariantDispatch::inner(self).source()
+            }
+        };
+        (display_impl, source_fn)
+    } else if args.impl_display_debug {
+        let display_impl = quote! {
+            impl ::core::fmt::Display for #enum_name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    #display_context
+                    ::core::fmt::Debug::fmt(self, f)
+                }
+            }
+        };
+        (display_impl, source_fn(&error_trait, &source_arms))
+    } else {
+        let display_impl = quote! {
+            impl ::core::fmt::Display for #enum_name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    #display_context
+                    match self {
+                        #(#display_arms,)*
+                        _ => unreachable!()
+                    }
+                }
+            }
+        };
+        (display_impl, source_fn(&error_trait, &source_arms))
+    };
+    let display_impl = if hand_written && args.no_display {
+        quote!()
+    } else {
+        display_impl
+    };
+
+    // The constructors share the namespace with the methods generated above.
+    let generated_methods = [
+        ("classify", args.classify),
+        ("kind", args.kind || args.opaque),
+        ("kinds", args.iter_kinds),
+        ("span_trace", args.span_trace),
+        ("otel_status", args.opentelemetry),
+        ("retryable", !retryable_arms.is_empty()),
+    ];
+    for (span, (method, _)) in &constructors {
+        if generated_methods
+            .iter()
+            .any(|&(generated, enabled)| enabled && generated == method)
+        {
+            return Err(syn::Error::new(
+                *span,
+                format!(
+                    "the `{method}` constructor clashes with the generated `{method}` method, \
+                     rename the variant with `as Name`"
+                ),
+            ));
+        }
+    }
+
+    let constructors_impl = if constructors.is_empty() {
+        quote!()
+    } else {
+        let constructors = constructors.iter().map(|(_, (_, constructor))| constructor);
+        // A variant named like the enum, i.e `std::io::Error as FooError`, gives `foo_error`.
+        quote! {
+            #[allow(clippy::self_named_constructors)]
+            impl #enum_name {
+                #(#constructors)*
+            }
+        }
+    };
+
+    let span_trace_impl = if args.span_trace {
+        quote! {
+            impl #enum_name {
+                /// The span trace captured when this error was created.
+                pub fn span_trace(&self) -> &::error_mancer::SpanTrace {
+                    match self {
+                        #(#span_trace_arms,)*
+                        _ => unreachable!()
+                    }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let classify_fallback = match extra {
+        Some(extra) => {
+            let extra = extra.to_string();
+            quote!(::core::option::Option::Some(#extra))
+        }
+        None => quote!(::core::option::Option::None),
+    };
+    let classify_impl = if args.classify {
+        quote! {
+            impl #enum_name {
+                /// The name of the variant `err` would be converted into, based on downcasting it
+                /// to the listed error types. `err` can also be this enum itself.
+                #[allow(unreachable_code)]
+                pub fn classify(err: &(dyn #error_trait + 'static)) -> ::core::option::Option<&'static str> {
+                    #[allow(unused_imports)]
+                    use ::error_mancer::__private::{ForwardDowncast as _, NoDowncast as _};
+                    if let ::core::option::Option::Some(this) = err.downcast_ref::<Self>() {
+                        return ::core::option::Option::Some(match this {
+                            #(#variant_name_arms,)*
+                            #[allow(unreachable_patterns)]
+                            _ => unreachable!()
+                        });
+                    }
+                    #(#classify_checks)*
+                    #classify_fallback
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let otel_status_impl = if args.opentelemetry {
+        quote! {
+            impl #enum_name {
+                /// The OpenTelemetry status for this error, selected with `#[otel_status = ..]`.
+                pub fn otel_status(&self) -> ::error_mancer::__private::opentelemetry::trace::Status {
+                    match self {
+                        #(#otel_status_arms,)*
+                        #[allow(unreachable_patterns)]
+                        _ => unreachable!()
+                    }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let retryable_impl = if retryable_arms.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            impl #enum_name {
+                /// Whether the operation that caused this error can be retried.
+                pub fn retryable(&self) -> bool {
+                    match self {
+                        #(#retryable_arms,)*
+                        _ => false
+                    }
+                }
+            }
+        }
+    };
+
+    let meta_impl = quote! {
+        impl ::error_mancer::ErrorMeta for #enum_name {
+            fn http_status_for(&self) -> ::core::option::Option<u16> {
+                #[allow(unused_imports)]
+                use ::error_mancer::__private::{ForwardMeta as _, ForwardNoMeta as _};
+                match self {
+                    #(#http_status_arms,)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            fn is_retryable(&self) -> bool {
+                #[allow(unused_imports)]
+                use ::error_mancer::__private::{ForwardMeta as _, ForwardNoMeta as _};
+                match self {
+                    #(#meta_retryable_arms,)*
+                    _ => false,
+                }
+            }
+        }
+    };
+
+    let http_status_impl = if cfg!(feature = "http") {
+        quote! {
+            impl ::error_mancer::HttpStatusCode for #enum_name {
+                fn http_status(&self) -> u16 {
+                    #[allow(unused_imports)]
+                    use ::error_mancer::__private::{ForwardMeta as _, ForwardNoMeta as _};
+                    match self {
+                        #(#http_status_code_arms,)*
+                        _ => #default_status
+                    }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let io_kind_impl = if cfg!(feature = "embedded-io") {
+        quote! {
+            impl ::error_mancer::__private::embedded_io::Error for #enum_name {
+                fn kind(&self) -> ::error_mancer::__private::embedded_io::ErrorKind {
+                    #[allow(unused_imports)]
+                    use ::error_mancer::__private::{FallbackKind as _, ForwardKind as _};
+                    match self {
+                        #(#io_kind_arms,)*
+                        _ => unreachable!()
+                    }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let msg_id_impl = if cfg!(feature = "i18n") {
+        quote! {
+            impl ::error_mancer::MessageId for #enum_name {
+                fn message_id(&self) -> &'static str {
+                    match self {
+                        #(#msg_id_arms,)*
+                        _ => unreachable!()
+                    }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let provide_fn = if cfg!(feature = "provide") {
+        quote!(::error_mancer::__provide!(#(#provide_arms),*);)
+    } else {
+        quote!()
+    };
+
+    // Traits from `error_mancer.toml` are only derived if the function doesnt derive them.
+    let derived = derived_traits(derives);
+    // Traits derived with `cfg_derive(..)` only replace the defaults, not `#[derive]`.
+    let default_derives = args
+        .derives
+        .iter()
+        .filter(|path| {
+            !derived.iter().any(|derived| same_trait(derived, path))
+                && !args.derives_conditionally(path)
+        })
+        .collect::<Vec<_>>();
+    let derives_debug = derived
+        .iter()
+        .chain(default_derives.iter().copied())
+        .any(|path| same_trait(path, &parse_quote!(Debug)));
+    let default_derives = if default_derives.is_empty() {
+        quote!()
+    } else {
+        quote!(#[derive(#(#default_derives),*)])
+    };
+    let mut cfg_derives = Vec::new();
+    let mut debug_predicates = Vec::new();
+    for cfg_derive in &args.cfg_derives {
+        let predicate = &cfg_derive.predicate;
+        let traits = cfg_derive
+            .traits
+            .iter()
+            .filter(|path| !derived.iter().any(|derived| same_trait(derived, path)))
+            .collect::<Vec<_>>();
+        if traits.is_empty() {
+            continue;
+        }
+        if traits
+            .iter()
+            .any(|path| same_trait(path, &parse_quote!(Debug)))
+        {
+            debug_predicates.push(predicate);
+        }
+        cfg_derives.push(quote!(#[cfg_attr(#predicate, derive(#(#traits),*))]));
+    }
+    let allow = &args.allow;
+    let allow = if allow.is_empty() {
+        quote!()
+    } else {
+        quote!(#[allow(#(#allow),*)])
+    };
+
+    // Only implemented when none of the `cfg_derive(.., Debug)` predicates hold.
+    let debug_cfg = if debug_predicates.is_empty() {
+        quote!()
+    } else {
+        quote!(#[cfg(not(any(#(#debug_predicates),*)))])
+    };
+    let debug_impl = if custom_debug || derives_debug {
+        quote!()
+    } else {
+        quote! {
+            #debug_cfg
+            impl ::core::fmt::Debug for #enum_name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        #(#debug_arms,)*
+                        _ => unreachable!()
+                    }
+                }
+            }
+        }
+    };
+
+    let uninhabited_impl = if fields.is_empty() {
+        quote! {
+            impl ::error_mancer::Uninhabited for #enum_name {
+                fn unreachable(self) -> ! {
+                    match self {}
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    // The payload of each variant, used by `split_variants!`.
+    let payloads_trait = payloads_trait_name(enum_name);
+    let (payload_names, payload_types): (Vec<_>, Vec<_>) = payloads.into_iter().unzip();
+    let payloads_impl = quote! {
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        trait #payloads_trait {
+            #(type #payload_names;)*
+        }
+
+        impl #payloads_trait for #enum_name {
+            #(type #payload_names = #payload_types;)*
+        }
+    };
+
+    Ok(quote! {
+        #(#nested_enums)*
+
+        #allow
+        #derives
+        #default_derives
+        #(#cfg_derives)*
+        #vis enum #enum_name {
+            #(#fields),*
+        }
+
+        #debug_impl
+
+        #(#from_impls)*
+
+        impl<T> ::core::convert::From<T> for #enum_name where Self: ::error_mancer::ErrorMancerFrom<T> {
+            fn from(value: T) -> Self {
+                ::error_mancer::ErrorMancerFrom::from(value)
+            }
+        }
+
+        #(#represents_impls)*
+
+        #flatten_impl
+
+        #try_flatten_impl
+
+        #constructors_impl
+
+        #display_impl
+
+        impl #error_trait for #enum_name {
+            #source_fn
+            #provide_fn
+        }
+
+        #span_trace_impl
+
+        #classify_impl
+
+        #otel_status_impl
+
+        #retryable_impl
+
+        #uninhabited_impl
+
+        #kind_impl
+
+        #meta_impl
+
+        #http_status_impl
+
+        #io_kind_impl
+
+        #msg_id_impl
+
+        #payloads_impl
+    })
+}
+
+/// A constructor for the variant `name`, named after it in snake case, i.e `StdIo` becomes
+/// `std_io`. `construct` builds the variant from `value`.
+fn constructor(name: &syn::Ident, ty: &Type, construct: &TokenStream) -> (String, TokenStream) {
+    let snake = name.to_string().to_case(Case::Snake);
+    // Variant names such as `Type` are keywords in snake case, `crate`, `self` and `super` cant
+    // be raw identifiers.
+    let method = match syn::parse_str::<syn::Ident>(&snake) {
+        Ok(method) => method,
+        Err(_) if matches!(snake.as_str(), "crate" | "self" | "super") => format_ident!("{snake}_"),
+        Err(_) => syn::Ident::new_raw(&snake, name.span()),
+    };
+    let doc = format!("Create the [`Self::{name}`] variant.");
+    let constructor = quote! {
+        #[doc = #doc]
+        pub fn #method(value: #ty) -> Self {
+            #construct
+        }
+    };
+    (snake, constructor)
+}
+
+/// `Error::source`, returning the source of each variant.
+fn source_fn(error_trait: &TokenStream, source_arms: &[TokenStream]) -> TokenStream {
+    if source_arms.is_empty() {
+        return quote!();
+    }
+    quote! {
+        fn source(&self) -> ::core::option::Option<&(dyn #error_trait + 'static)> {
+            #[allow(unused_imports)]
+            use ::error_mancer::__private::{ForwardError as _, ForwardNothing as _};
+            match self {
+                #(#source_arms,)*
+                _ => ::core::option::Option::None,
+            }
+        }
+    }
+}
+
+/// The hidden trait holding the payload of each variant of `enum_name`, i.e `__FooErrorPayloads`.
+pub(crate) fn payloads_trait_name(enum_name: &syn::Ident) -> syn::Ident {
+    format_ident!("__{enum_name}Payloads")
+}
@@ -1,8 +1,25 @@
-use convert_case::{Case, Casing};
-use proc_macro2::TokenStream;
-use quote::{format_ident, quote, ToTokens};
+mod args;
+mod config;
+mod deny;
+mod doc;
+mod generate;
+mod split;
+
+use args::{
+    CfgDerive,
+    ErrorsArgs,
+    ErrorsBlock,
+    ErrorsClosure,
+    ErrorsInScope,
+    Handle,
+    HandleRest,
+    MetaArgs,
+    SplitVariants,
+};
+use generate::generate_error_type;
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::parse::Parser;
-use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::{
     self,
@@ -13,7 +30,6 @@ use syn::{
     Path,
     PathArguments,
     ReturnType,
-    Token,
     Type,
     TypePath,
 };
@@ -31,61 +47,393 @@ pub fn errors(
     }
 }
 
+#[proc_macro]
+pub fn errors_block(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as ErrorsBlock);
+    match errors_block_impl(input) {
+        Ok(result) => result.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+fn errors_block_impl(mut input: ErrorsBlock) -> syn::Result<TokenStream> {
+    apply_defaults(&mut input.args, None)?;
+    let (error_enum, error_type) = generate_error_type(
+        &input.args,
+        String::from("Block"),
+        syn::Visibility::Inherited,
+        quote!(),
+        None,
+    )?;
+
+    let body = input.block;
+    let (maybe_async, maybe_await) = if input.asyncness.is_some() {
+        (quote!(async), quote!(.await))
+    } else {
+        (quote!(), quote!())
+    };
+
+    Ok(quote! {
+        {
+            #error_enum
+            (#maybe_async move || -> ::core::result::Result<_, #error_type> #body)()#maybe_await
+        }
+    })
+}
+
+#[proc_macro]
+pub fn errors_in_scope(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as ErrorsInScope);
+    match errors_in_scope_impl(input) {
+        Ok(result) => result.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+fn errors_in_scope_impl(mut input: ErrorsInScope) -> syn::Result<TokenStream> {
+    apply_defaults(&mut input.args, None)?;
+    let (error_enum, error_type) = generate_error_type(
+        &input.args,
+        String::from("Spawn"),
+        syn::Visibility::Inherited,
+        quote!(),
+        None,
+    )?;
+
+    let ok_type = match &input.closure.output {
+        ReturnType::Default => quote!(_),
+        output => {
+            let (ok_type, _) = get_return_generics(output)?;
+            quote!(#ok_type)
+        }
+    };
+
+    let scope = input.scope;
+    let closure = input.closure;
+    let capture = closure.capture;
+    // A explicit return type requires the body to be a block.
+    let body = match *closure.body {
+        syn::Expr::Block(body) => quote!(#body),
+        body => quote!({ #body }),
+    };
+    if !closure.inputs.is_empty() {
+        return Err(syn::Error::new(
+            closure.inputs.span(),
+            "Closures passed to `Scope::spawn` cant take arguments",
+        ));
+    }
+
+    Ok(quote! {
+        {
+            #error_enum
+            #scope.spawn(#capture || -> ::core::result::Result<#ok_type, #error_type> #body)
+        }
+    })
+}
+
+#[proc_macro]
+pub fn errors_closure(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as ErrorsClosure);
+    match errors_closure_impl(input) {
+        Ok(result) => result.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+fn errors_closure_impl(mut input: ErrorsClosure) -> syn::Result<TokenStream> {
+    apply_defaults(&mut input.args, None)?;
+    let (ok_type, explicit_error_name) = match &input.closure.output {
+        ReturnType::Default => (parse_quote!(_), None),
+        output => {
+            let (ok_type, explicit_error_name) = get_return_generics(output)?;
+            (ok_type.clone(), explicit_error_name)
+        }
+    };
+    let (error_enum, error_type) = generate_error_type(
+        &input.args,
+        String::from("Closure"),
+        syn::Visibility::Inherited,
+        quote!(),
+        explicit_error_name,
+    )?;
+
+    let mut closure = input.closure;
+    closure.output = parse_quote!(-> ::core::result::Result<#ok_type, #error_type>);
+    // A explicit return type requires the body to be a block.
+    if !matches!(*closure.body, syn::Expr::Block(_)) {
+        let body = &closure.body;
+        closure.body = parse_quote!({ #body });
+    }
+
+    Ok(quote! {
+        {
+            #error_enum
+            #closure
+        }
+    })
+}
+
+#[proc_macro_attribute]
+#[allow(non_snake_case)]
+pub fn ErrorMancerMeta(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let args = parse_macro_input!(attr as MetaArgs);
+    let item = parse_macro_input!(item as syn::DeriveInput);
+    meta_impl(args, item).into()
+}
+
+fn meta_impl(args: MetaArgs, item: syn::DeriveInput) -> TokenStream {
+    let name = &item.ident;
+    let (impl_generics, type_generics, where_clause) = item.generics.split_for_impl();
+
+    let http_status = args.http_status.map(|status| {
+        quote! {
+            fn http_status_for(&self) -> ::core::option::Option<u16> {
+                ::core::option::Option::Some(#status)
+            }
+        }
+    });
+    let retry = args.retry.map(|retry| {
+        quote! {
+            fn is_retryable(&self) -> bool {
+                #retry
+            }
+        }
+    });
+
+    quote! {
+        #item
+
+        impl #impl_generics ::error_mancer::ErrorMeta for #name #type_generics #where_clause {
+            #http_status
+            #retry
+        }
+    }
+}
+
+#[proc_macro]
+pub fn split_variants(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as SplitVariants);
+    match split::split_variants(input) {
+        Ok(result) => result.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+#[proc_macro]
+pub fn handle(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as Handle);
+    handle_impl(input).into()
+}
+
+fn handle_impl(input: Handle) -> TokenStream {
+    let Handle { value, rest, arms } = input;
+    // Mixed site so the binding isnt visible to the `wrap_rest` context expression.
+    let binding = syn::Ident::new("value", proc_macro2::Span::mixed_site());
+
+    let convert = match rest {
+        HandleRest::Propagate(error_type) => quote!(propagate::<#error_type>()),
+        HandleRest::Wrap(context) => quote!(wrap_rest(|| #context)),
+    };
+    let propagate = quote! {
+        match (&::error_mancer::__private::Propagator::new(#binding)).#convert {
+            ::core::result::Result::Ok(value) => value,
+            ::core::result::Result::Err(err) => return ::core::result::Result::Err(err),
+        }
+    };
+    // For `Option`s `None` has to be handled explicitly, the value in `Some` is propagated.
+    let fallback = if arms.iter().any(|arm| is_option_pattern(&arm.pat)) {
+        quote!(::core::option::Option::Some(#binding) => #propagate)
+    } else {
+        quote!(#binding => #propagate)
+    };
+
+    quote! {
+        {
+            #[allow(unused_imports)]
+            use ::error_mancer::__private::{PropagateResult as _, PropagateValue as _};
+            match #value {
+                #(#arms)*
+                #[allow(unreachable_patterns)]
+                #fallback
+            }
+        }
+    }
+}
+
+/// Returns true for `Some(..)` and `None` patterns.
+fn is_option_pattern(pattern: &syn::Pat) -> bool {
+    let path = match pattern {
+        syn::Pat::TupleStruct(pattern) => &pattern.path,
+        syn::Pat::Path(pattern) => &pattern.path,
+        syn::Pat::Ident(pattern) if pattern.subpat.is_none() => {
+            return pattern.ident == "None";
+        }
+        syn::Pat::Or(pattern) => return pattern.cases.iter().any(is_option_pattern),
+        _ => return false,
+    };
+    path.segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Some" || segment.ident == "None")
+}
+
 fn errors_impl(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
     if let Ok(function) = syn::parse2(item.clone()) {
         do_free_function(function, attr)
     } else if let Ok(impl_block) = syn::parse2(item.clone()) {
-        do_impl_block(impl_block)
+        do_impl_block(impl_block, attr)
+    } else if let Ok(item_static) = syn::parse2(item.clone()) {
+        do_static(item_static, attr)
     } else {
         Err(syn::Error::new(
             item.span(),
-            "Expected function or impl block",
+            "Expected function, impl block or static",
         ))
     }
 }
 
-fn do_impl_block(mut impl_block: syn::ItemImpl) -> syn::Result<TokenStream> {
+fn do_impl_block(mut impl_block: syn::ItemImpl, attr: TokenStream) -> syn::Result<TokenStream> {
+    // Arguments on the impl block are defaults for its methods.
+    let defaults: ErrorsArgs = parse2(attr)?;
+    if !defaults.entries.is_empty() {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`#[errors]` on a impl block only takes flags and options, list the errors on the \
+             methods",
+        ));
+    }
+    // Associated types set by the impl itself, the others can be set to a generated enum.
+    let mut associated_types = Vec::new();
+    // Their values, a `Poll<Self::Output>` is handled as if the method returned `Output`.
+    let mut associated_values = Vec::new();
+    if impl_block.trait_.is_some() {
+        for item in &impl_block.items {
+            if let syn::ImplItem::Type(item) = item {
+                associated_types.push(item.ident.clone());
+                associated_values.push((item.ident.clone(), item.ty.clone()));
+            }
+        }
+    }
+    let mut resolved_types: Vec<(syn::Ident, Type)> = Vec::new();
+    let mut bound_types: Vec<syn::Ident> = Vec::new();
     let mut enums = Vec::new();
-    for item in &mut impl_block.items {
-        if let syn::ImplItem::Fn(method) = item {
-            if let Some(attr) = method
-                .attrs
-                .iter()
-                .find(|&attr| attr.path().is_ident("errors"))
-            {
-                match attr.meta.clone() {
-                    syn::Meta::List(list) => {
-                        let arguments = list.tokens;
-                        let function = method.into_token_stream();
-                        let function = parse2(function)?;
-                        let (enum_decl, function) = create_function(function, arguments)?;
-                        enums.push(enum_decl);
-
-                        let function = function.into_token_stream();
-                        let function = parse2(function)?;
-                        *method = function;
-                    }
-                    syn::Meta::Path(_) => {
-                        let arguments = quote!();
-                        let function = method.into_token_stream();
-                        let function = parse2(function)?;
-                        let (enum_decl, function) = create_function(function, arguments)?;
-                        enums.push(enum_decl);
-
-                        let function = function.into_token_stream();
-                        let function = parse2(function)?;
-                        *method = function;
-                    }
-                    _ => {
-                        return Err(syn::Error::new(
-                            attr.span(),
-                            "Expected list or simple `#[errors]`",
-                        ))
+    let mut items = Vec::new();
+    for item in std::mem::take(&mut impl_block.items) {
+        let syn::ImplItem::Fn(mut method) = item else {
+            items.push(item);
+            continue;
+        };
+
+        if let Some(attr) = method
+            .attrs
+            .iter()
+            .find(|&attr| attr.path().is_ident("errors"))
+        {
+            let arguments = errors_arguments(&attr.meta)?;
+
+            // `Result<T, Self::Error>` with `Error` not set by the impl generates the enum
+            // outside the method, and sets `Error` to it.
+            let output = method.sig.output.clone();
+            let mut associated = None;
+            if impl_block.trait_.is_some() {
+                if let Some(argument) = error_argument(&mut method.sig.output) {
+                    if let Some(name) = self_associated_type(argument)
+                        .filter(|name| !associated_types.contains(name))
+                    {
+                        associated = Some(name.clone());
+                        *argument = parse_quote!(_);
                     }
                 }
             }
+            // `Poll<Self::Output>` with `type Output = Result<T, _>`, the enum is generated as if
+            // the method returned `Poll<Result<T, _>>`, and `Output` is set to the result.
+            let mut polled = None;
+            if let Some(argument) = poll_argument(&mut method.sig.output) {
+                let value = self_associated_type(argument).and_then(|name| {
+                    associated_values
+                        .iter()
+                        .find(|(associated, _)| associated == name)
+                });
+                if let Some((name, value)) = value {
+                    polled = Some(name.clone());
+                    *argument = value.clone();
+                }
+            }
+
+            // The enum is reachable through the trait, so it has to be public. Methods of a
+            // trait impl take the visibility of the trait.
+            if associated.is_some() || polled.is_some() {
+                method.vis = parse_quote!(pub);
+            }
+
+            let (enum_decl, mut method) = errors_method(method, arguments, &defaults)?;
+            if let Some(name) = polled {
+                if let Some(resolved) = poll_argument(&mut method.sig.output) {
+                    resolved_types.push((name, resolved.clone()));
+                }
+                method.sig.output = output.clone();
+                method.vis = syn::Visibility::Inherited;
+            }
+            if let Some(name) = associated {
+                if bound_types.contains(&name) {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        format!(
+                            "`Self::{name}` is already set by another method, set `type {name}` \
+                             by hand to a enum covering both methods"
+                        ),
+                    ));
+                }
+                let enum_type = error_argument(&mut method.sig.output).cloned();
+                items.push(parse_quote!(type #name = #enum_type;));
+                bound_types.push(name);
+                method.sig.output = output;
+                method.vis = syn::Visibility::Inherited;
+            }
+            enums.push(enum_decl);
+            items.push(syn::ImplItem::Fn(method));
+        } else if let Some((index, cfg)) = cfg_errors_attr(&method.attrs)? {
+            // `cfg_attr` isnt expanded before the impl block is passed to `#[errors]`, so a
+            // copy of the method is emitted for each outcome of the predicate.
+            let predicate = &cfg.predicate;
+            let mut disabled = method.clone();
+            disabled.attrs[index] = parse_quote!(#[cfg(not(#predicate))]);
+            let mut disabled_attrs = Vec::new();
+            for attr in std::mem::take(&mut disabled.attrs) {
+                if !attr.path().is_ident("derive") {
+                    disabled_attrs.extend(cfg_derive_attr(attr)?.1);
+                }
+            }
+            disabled.attrs = disabled_attrs;
+
+            let rest = &cfg.rest;
+            method.attrs.splice(
+                index..=index,
+                [parse_quote!(#[cfg(#predicate)])]
+                    .into_iter()
+                    .chain(rest.iter().map(|meta| parse_quote!(#[#meta]))),
+            );
+            // The `#[cfg(predicate)]` on the method is copied to the enum by `create_function`.
+            let arguments = errors_arguments(&cfg.errors)?;
+            let (enum_decl, method) = errors_method(method, arguments, &defaults)?;
+            enums.push(enum_decl);
+            items.push(syn::ImplItem::Fn(method));
+            items.push(syn::ImplItem::Fn(disabled));
+        } else {
+            items.push(syn::ImplItem::Fn(method));
+        }
+    }
+    for item in &mut items {
+        if let syn::ImplItem::Type(item) = item {
+            if let Some((_, resolved)) = resolved_types.iter().find(|(name, _)| *name == item.ident)
+            {
+                item.ty = resolved.clone();
+            }
         }
     }
+    impl_block.items = items;
 
     Ok(quote! {
         #(#enums)*
@@ -93,46 +441,481 @@ fn do_impl_block(mut impl_block: syn::ItemImpl) -> syn::Result<TokenStream> {
     })
 }
 
+/// The arguments of `#[errors(...)]`, empty for `#[errors]`.
+fn errors_arguments(meta: &syn::Meta) -> syn::Result<TokenStream> {
+    match meta {
+        syn::Meta::List(list) => Ok(list.tokens.clone()),
+        syn::Meta::Path(_) => Ok(quote!()),
+        syn::Meta::NameValue(_) => Err(syn::Error::new(
+            meta.span(),
+            "Expected list or simple `#[errors]`",
+        )),
+    }
+}
+
+/// Apply `#[errors]` to a method of a impl block, returning the enum and the new method.
+fn errors_method(
+    method: syn::ImplItemFn,
+    arguments: TokenStream,
+    defaults: &ErrorsArgs,
+) -> syn::Result<(TokenStream, syn::ImplItemFn)> {
+    // Checked here so the error mentions methods, the same check in `create_function` covers
+    // free functions.
+    if let Some(constness) = method.sig.constness {
+        if !cfg!(feature = "const_closures") {
+            return Err(syn::Error::new(
+                constness.span(),
+                "`#[errors]` on a `const fn` method requires the `const_closures` feature \
+                 (nightly only), as const closures arent stable yet",
+            ));
+        }
+    }
+    let function = parse2(method.into_token_stream())?;
+    let (enum_decl, function) = create_function(function, arguments, Some(defaults))?;
+    Ok((enum_decl, parse2(function)?))
+}
+
+/// A `#[cfg_attr(predicate, errors(...), rest...)]` attribute.
+struct CfgErrors {
+    predicate: syn::Meta,
+    errors: syn::Meta,
+    rest: Vec<syn::Meta>,
+}
+
+/// The predicate and attributes of a `#[cfg_attr(predicate, attrs...)]`, `None` for other
+/// attributes.
+fn parse_cfg_attr(
+    attr: &syn::Attribute,
+) -> syn::Result<
+    Option<(
+        syn::Meta,
+        syn::punctuated::Punctuated<syn::Meta, syn::Token![,]>,
+    )>,
+> {
+    if !attr.path().is_ident("cfg_attr") {
+        return Ok(None);
+    }
+    attr.parse_args_with(|input: syn::parse::ParseStream| {
+        let predicate = input.parse::<syn::Meta>()?;
+        input.parse::<syn::Token![,]>()?;
+        let metas =
+            syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated(input)?;
+        Ok(Some((predicate, metas)))
+    })
+}
+
+/// Find a `cfg_attr` applying `#[errors]`, returning its index in `attrs`.
+fn cfg_errors_attr(attrs: &[syn::Attribute]) -> syn::Result<Option<(usize, CfgErrors)>> {
+    for (index, attr) in attrs.iter().enumerate() {
+        let Some((predicate, metas)) = parse_cfg_attr(attr)? else {
+            continue;
+        };
+        let (errors, rest): (Vec<_>, Vec<_>) = metas
+            .into_iter()
+            .partition(|meta| meta.path().is_ident("errors"));
+        if let Some(errors) = errors.into_iter().next() {
+            return Ok(Some((
+                index,
+                CfgErrors {
+                    predicate,
+                    errors,
+                    rest,
+                },
+            )));
+        }
+    }
+    Ok(None)
+}
+
+/// Lints about the docs of a function. Clippy skips the rewritten function and the `# Errors`
+/// section documents it, so expecting these would always fail.
+const DOC_LINTS: [&str; 4] = [
+    "missing_docs",
+    "clippy::missing_errors_doc",
+    "clippy::missing_panics_doc",
+    "clippy::missing_safety_doc",
+];
+
+/// Turn the doc lints of a `#[expect(..)]` into a `#[allow(..)]`, keeping the other lints (and
+/// the `reason`) in the `#[expect(..)]`.
+fn split_doc_expectations(attr: syn::Attribute) -> syn::Result<Vec<syn::Attribute>> {
+    if !attr.path().is_ident("expect") {
+        return Ok(vec![attr]);
+    }
+    let metas = attr.parse_args_with(
+        syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
+    )?;
+    let is_doc_lint = |meta: &syn::Meta| {
+        let path = meta.path().to_token_stream().to_string().replace(' ', "");
+        DOC_LINTS.contains(&path.as_str())
+    };
+    if !metas.iter().any(is_doc_lint) {
+        return Ok(vec![attr]);
+    }
+
+    let (reason, lints): (Vec<_>, Vec<_>) = metas
+        .into_iter()
+        .partition(|meta| meta.path().is_ident("reason"));
+    let (doc_lints, rest): (Vec<_>, Vec<_>) = lints.into_iter().partition(is_doc_lint);
+    let mut attrs = vec![parse_quote!(#[allow(#(#doc_lints,)* #(#reason),*)])];
+    if !rest.is_empty() {
+        attrs.push(parse_quote!(#[expect(#(#rest,)* #(#reason),*)]));
+    }
+    Ok(attrs)
+}
+
+/// Split the `derive(..)`s out of a `#[cfg_attr(predicate, ..)]`, returning them and the
+/// attribute with the remaining arguments. Like `cfg_attr(.., errors(..))` these are only left
+/// unexpanded on methods in impl blocks.
+fn cfg_derive_attr(
+    attr: syn::Attribute,
+) -> syn::Result<(Option<CfgDerive>, Option<syn::Attribute>)> {
+    let Some((predicate, metas)) = parse_cfg_attr(&attr)? else {
+        return Ok((None, Some(attr)));
+    };
+    let (derives, rest): (Vec<_>, Vec<_>) = metas
+        .into_iter()
+        .partition(|meta| meta.path().is_ident("derive"));
+    if derives.is_empty() {
+        return Ok((None, Some(attr)));
+    }
+
+    let mut traits = Vec::new();
+    for derive in derives {
+        traits.extend(derive.require_list()?.parse_args_with(
+            syn::punctuated::Punctuated::<Path, syn::Token![,]>::parse_terminated,
+        )?);
+    }
+    let rest = if rest.is_empty() {
+        None
+    } else {
+        Some(parse_quote!(#[cfg_attr(#predicate, #(#rest),*)]))
+    };
+    Ok((Some(CfgDerive { predicate, traits }), rest))
+}
+
+/// The `#[cfg(..)]`s of a function, and the `cfg(..)`s in its `#[cfg_attr(..)]`s.
+fn cfg_attrs(attrs: &[syn::Attribute]) -> syn::Result<Vec<syn::Attribute>> {
+    let mut cfgs = Vec::new();
+    for attr in attrs {
+        if attr.path().is_ident("cfg") {
+            cfgs.push(attr.clone());
+        } else if let Some((predicate, metas)) = parse_cfg_attr(attr)? {
+            let metas: Vec<_> = metas
+                .into_iter()
+                .filter(|meta| meta.path().is_ident("cfg"))
+                .collect();
+            if !metas.is_empty() {
+                cfgs.push(parse_quote!(#[cfg_attr(#predicate, #(#metas),*)]));
+            }
+        }
+    }
+    Ok(cfgs)
+}
+
+/// Put `cfgs` on each of the generated items, so they are only emitted along with the function.
+fn gate_items(cfgs: &[syn::Attribute], items: TokenStream) -> syn::Result<TokenStream> {
+    if cfgs.is_empty() {
+        return Ok(items);
+    }
+    let items = parse2::<syn::File>(items)?.items.into_iter();
+    Ok(items.map(|item| quote!(#(#cfgs)* #item)).collect())
+}
+
+/// Apply the defaults given on the impl block and in `error_mancer.toml`, the arguments of the
+/// attribute itself take precedence over both.
+fn apply_defaults(args: &mut ErrorsArgs, impl_defaults: Option<&ErrorsArgs>) -> syn::Result<()> {
+    if let Some(defaults) = impl_defaults {
+        args.apply_defaults(defaults)?;
+    }
+    if let Some(defaults) = config::load()? {
+        args.apply_defaults(&defaults)?;
+    }
+    args.add_implied_entries();
+    Ok(())
+}
+
 fn do_free_function(function: syn::ItemFn, attr: TokenStream) -> Result<TokenStream, syn::Error> {
-    let (enum_decl, new_function) = create_function(function, attr)?;
+    let (enum_decl, new_function) = create_function(function, attr, None)?;
     Ok(quote! {
         #enum_decl
         #new_function
     })
 }
 
+/// `#[errors]` on a `static` holding a closure coerced to a `fn` pointer, the enum is named after
+/// the static.
+fn do_static(mut item: syn::ItemStatic, attr: TokenStream) -> syn::Result<TokenStream> {
+    let Type::BareFn(fn_type) = item.ty.as_mut() else {
+        return Err(syn::Error::new_spanned(
+            &item.ty,
+            "`#[errors]` on a static requires a `fn` pointer type, such as `fn() -> Result<T, _>`",
+        ));
+    };
+    let syn::Expr::Closure(closure) = item.expr.as_mut() else {
+        return Err(syn::Error::new_spanned(
+            &item.expr,
+            "`#[errors]` on a static requires a closure as the value",
+        ));
+    };
+
+    let mut derives = Vec::new();
+    let mut attrs = Vec::new();
+    for attr in std::mem::take(&mut item.attrs) {
+        if attr.path().is_ident("errors") {
+            continue;
+        }
+        if attr.path().is_ident("derive") {
+            derives.push(attr);
+        } else {
+            attrs.push(attr);
+        }
+    }
+    let derives = quote!(#(#derives)*);
+
+    let mut args: ErrorsArgs = parse2(attr)?;
+    apply_defaults(&mut args, None)?;
+    if let Some(denied) = args.deny.first() {
+        return Err(syn::Error::new_spanned(
+            denied,
+            "`deny` isnt supported on statics, list the allowed errors instead",
+        ));
+    }
+    if args.none {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`none` isnt supported on statics, use `error_mancer::NoError` as the error type",
+        ));
+    }
+    if args.erase {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`erase` isnt supported on statics, the closure type always names the enum",
+        ));
+    }
+    if args.boxed_self {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`boxed_self` isnt supported on statics",
+        ));
+    }
+
+    let (ok_return_type, explicit_error_name) = get_return_generics(&fn_type.output)?;
+    let mut ok_return_type = ok_return_type.clone();
+    let (error_enum, enum_name) = generate_error_type(
+        &args,
+        item.ident.to_string(),
+        item.vis.clone(),
+        derives,
+        explicit_error_name,
+    )?;
+    replace_error_value(&mut fn_type.output, parse_quote!(#enum_name));
+    replace_partial_error(&mut ok_return_type, &parse_quote!(#enum_name));
+    if let Some(argument) = result_argument(&mut fn_type.output, 0) {
+        replace_partial_error(argument, &parse_quote!(#enum_name));
+    }
+
+    // The closure return type is replaced so `?` converts into the enum.
+    let body = &closure.body;
+    closure.body = parse_quote!({ #body });
+    closure.output = parse_quote!(-> ::core::result::Result<#ok_return_type, #enum_name>);
+
+    if !args.no_doc && !doc::has_errors_section(&attrs) {
+        let errors_doc = doc::errors_section(&args, Some(&parse_quote!(#enum_name)));
+        attrs.extend(syn::Attribute::parse_outer.parse2(errors_doc)?);
+    }
+    item.attrs = attrs;
+    let zbus_impls =
+        generate::zbus_impls(&args, &args.entries, &enum_name, args.allow_extra.as_ref())?;
+    let clap_impls =
+        generate::clap_impls(&args, &args.entries, &enum_name, args.allow_extra.as_ref())?;
+
+    let yew_impls =
+        generate::yew_impls(&args, &args.entries, &enum_name, args.allow_extra.as_ref())?;
+    let enum_items = gate_items(
+        &cfg_attrs(&item.attrs)?,
+        quote!(#error_enum #zbus_impls #clap_impls #yew_impls),
+    )?;
+
+    Ok(quote! {
+        #enum_items
+        #item
+    })
+}
+
 fn create_function(
     function: syn::ItemFn,
     attr: TokenStream,
+    impl_defaults: Option<&ErrorsArgs>,
 ) -> Result<(TokenStream, TokenStream), syn::Error> {
-    let derives = match function
-        .attrs
-        .iter()
-        .find(|attr| attr.path().is_ident("derive"))
-    {
-        Some(attr) => attr.to_token_stream(),
-        None => quote!(),
-    };
+    // `derive` attributes are moved to the enum, everything else stays on the function.
+    let mut derives = Vec::new();
+    let mut cfg_derives = Vec::new();
+    let mut attrs = Vec::new();
+    for attr in function.attrs {
+        if attr.path().is_ident("errors") {
+            continue;
+        }
+        if attr.path().is_ident("derive") {
+            derives.push(attr);
+            continue;
+        }
+        let (cfg_derive, rest) = cfg_derive_attr(attr)?;
+        cfg_derives.extend(cfg_derive);
+        if let Some(attr) = rest {
+            attrs.extend(split_doc_expectations(attr)?);
+        }
+    }
+    let derives = quote!(#(#derives)*);
+
+    let mut args: ErrorsArgs = parse2(attr)?;
+    args.cfg_derives.extend(cfg_derives);
+    apply_defaults(&mut args, impl_defaults)?;
 
     let vis = function.vis;
     let mut signature = function.sig;
-    let body = function.block;
+    let mut body = function.block;
 
+    // `Poll<Result<T, E>>`, as returned by `Future::poll`, is handled as `Result<T, E>` and the
+    // `Poll` is put back around it.
+    let poll = unwrap_poll(&mut signature.output);
     let (ok_return_type, explicit_error_name) = get_return_generics(&signature.output)?;
-    let (error_enum, error_return_type) = generate_error_type(
-        attr,
-        signature.ident.to_string(),
-        vis.clone(),
-        derives,
-        explicit_error_name.clone(),
-    )?;
+    let explicit_error_name = if args.boxed_self {
+        boxed_error_name(&signature.output, explicit_error_name)?
+    } else {
+        explicit_error_name
+    };
+    if args.enum_only {
+        rewrap_poll(&mut signature.output, poll.as_ref());
+        let function = syn::ItemFn {
+            attrs,
+            vis,
+            sig: signature,
+            block: body,
+        };
+        return enum_only(&args, function, derives, explicit_error_name);
+    }
+    unwrap_try_tail(&mut body);
 
-    let inner_type: syn::ReturnType =
-        parse_quote!(-> ::core::result::Result<#ok_return_type, #error_return_type>);
+    if let Some(denied) = args.deny.first() {
+        if !args.entries.is_empty() {
+            return Err(syn::Error::new_spanned(
+                denied,
+                "`deny` cant be combined with listed errors, only the listed errors are allowed \
+                 already",
+            ));
+        }
+        // Checked on a copy, the signature is kept as is.
+        let infers_error = replace_error_value(&mut signature.output.clone(), parse_quote!(()));
+        if infers_error || explicit_error_name.is_some() {
+            return Err(syn::Error::new_spanned(
+                denied,
+                "`deny` requires a passthrough error type, such as `anyhow::Result<T>`",
+            ));
+        }
+        let body = deny::deny_body(&args.deny, *body);
+        rewrap_poll(&mut signature.output, poll.as_ref());
+        return Ok((
+            quote!(),
+            quote! {
+                #(#attrs)*
+                #vis #signature #body
+            },
+        ));
+    }
+    let generated = if args.none {
+        check_none(&args, &signature.output, explicit_error_name.is_some())?;
+        None
+    } else {
+        Some(generate_error_type(
+            &args,
+            signature.ident.to_string(),
+            vis.clone(),
+            derives,
+            explicit_error_name.clone(),
+        )?)
+    };
+    let (error_enum, enum_type): (TokenStream, Type) = match &generated {
+        Some((error_enum, enum_name)) => (error_enum.clone(), parse_quote!(#enum_name)),
+        None => (quote!(), parse_quote!(::error_mancer::NoError)),
+    };
+    let error_return_type: Type = if args.boxed_self {
+        parse_quote!(::error_mancer::__private::Box<#enum_type>)
+    } else {
+        enum_type.clone()
+    };
+
+    // `Self` cant always be named inside the closure, so its left to inference.
+    let mut ok_return_type = if mentions_self(ok_return_type.to_token_stream()) {
+        parse_quote!(_)
+    } else {
+        ok_return_type.clone()
+    };
+    replace_partial_error(&mut ok_return_type, &error_return_type);
+    let inner_type: syn::ReturnType = if poll.is_some() {
+        parse_quote!(
+            -> ::core::task::Poll<::core::result::Result<#ok_return_type, #error_return_type>>
+        )
+    } else {
+        parse_quote!(-> ::core::result::Result<#ok_return_type, #error_return_type>)
+    };
 
-    let replaced = replace_error_value(&mut signature.output, error_return_type);
+    let replaced = replace_error_value(&mut signature.output, error_return_type.clone());
+    let replaced = match result_argument(&mut signature.output, 0) {
+        Some(argument) => replace_partial_error(argument, &error_return_type) || replaced,
+        None => replaced,
+    };
 
     let emit_enum_outside = replaced || explicit_error_name.is_some();
+    if args.erase && emit_enum_outside {
+        return Err(syn::Error::new_spanned(
+            &signature.output,
+            "`erase` requires a passthrough error type, such as `anyhow::Result<T>`, so the enum \
+             stays out of the signature",
+        ));
+    }
+    if args.boxed_self && !emit_enum_outside {
+        return Err(syn::Error::new_spanned(
+            &signature.output,
+            "`boxed_self` requires the error type to be `_` or `Box<Name>`",
+        ));
+    }
+
+    // Foreign trait impls are only emitted next to a enum at module level.
+    let foreign_impls = match &generated {
+        Some((_, enum_name)) if emit_enum_outside => {
+            let zbus_impls =
+                generate::zbus_impls(&args, &args.entries, enum_name, args.allow_extra.as_ref())?;
+            let clap_impls =
+                generate::clap_impls(&args, &args.entries, enum_name, args.allow_extra.as_ref())?;
+            let yew_impls =
+                generate::yew_impls(&args, &args.entries, enum_name, args.allow_extra.as_ref())?;
+            quote!(#zbus_impls #clap_impls #yew_impls)
+        }
+        _ => quote!(),
+    };
+
+    // A proc macro cant emit items in other modules, so the re-export has to be written by
+    // hand. This checks it points at the generated enum.
+    let reexport_check =
+        match &args.reexport {
+            Some(module) if emit_enum_outside => quote_spanned! {module.span()=>
+                const _: fn(#enum_type) -> #module::#enum_type = |value| value;
+            },
+            Some(module) => return Err(syn::Error::new(
+                module.span(),
+                "`reexport` requires the enum to be emitted outside the function, use `_` or a \
+                 name as the error type",
+            )),
+            None => quote!(),
+        };
+
+    let errors_doc = if args.no_doc || doc::has_errors_section(&attrs) {
+        quote!()
+    } else {
+        let enum_name = emit_enum_outside.then_some(&enum_type);
+        doc::errors_section(&args, enum_name)
+    };
 
     let maybe_async = if signature.asyncness.is_some() {
         quote!(async)
@@ -144,27 +927,205 @@ fn create_function(
     } else {
         quote!()
     };
+    let maybe_const =
+        match signature.constness {
+            Some(_) if cfg!(feature = "const_closures") => quote!(const),
+            Some(constness) => return Err(syn::Error::new(
+                constness.span(),
+                "`#[errors]` on a `const fn` requires the `const_closures` feature (nightly only)",
+            )),
+            None => quote!(),
+        };
+
+    let call = quote!((#maybe_const #maybe_async move || #inner_type { #body })()#maybe_await);
+    let message = format!("`{}` returned an error", signature.ident);
+    // With `Result<!, _>` the `Ok` would be unreachable code, so only the error is matched.
+    let result = match (matches!(ok_return_type, Type::Never(_)), args.panic) {
+        _ if poll.is_some() => {
+            if args.panic {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "`panic` isnt supported on functions returning `Poll`",
+                ));
+            }
+            quote! {
+                match #call {
+                    ::core::task::Poll::Ready(Ok(value)) => ::core::task::Poll::Ready(Ok(value)),
+                    ::core::task::Poll::Ready(Err(err)) => {
+                        ::core::task::Poll::Ready(Err(::core::convert::From::from(err)))
+                    }
+                    ::core::task::Poll::Pending => ::core::task::Poll::Pending,
+                }
+            }
+        }
+        (false, false) => quote!(Ok(#call?)),
+        (false, true) => quote!(Ok(#call.expect(#message))),
+        (true, false) => quote! {
+            match #call {
+                Err(err) => Err(::core::convert::From::from(err)),
+            }
+        },
+        (true, true) => quote! {
+            match #call {
+                Err(err) => ::core::panic!("{}: {:?}", #message, err),
+            }
+        },
+    };
+    rewrap_poll(&mut signature.output, poll.as_ref());
 
     if emit_enum_outside {
         let new_func = quote! {
+            #(#attrs)*
+            #errors_doc
             #[allow(clippy::needless_question_mark)]
             #vis #signature {
-                Ok((#maybe_async move || #inner_type { #body })()#maybe_await?)
+                #result
             }
         };
-        Ok((error_enum, new_func))
+        let enum_items = gate_items(
+            &cfg_attrs(&attrs)?,
+            quote!(#error_enum #foreign_impls #reexport_check),
+        )?;
+        Ok((enum_items, new_func))
     } else {
         let new_func = quote! {
+            #(#attrs)*
+            #errors_doc
             #[allow(clippy::needless_question_mark)]
             #vis #signature {
                 #error_enum
-                Ok((#maybe_async move || #inner_type { #body })()#maybe_await?)
+                #result
             }
         };
         Ok((quote!(), new_func))
     }
 }
 
+/// The items for `enum_only`, the enum is generated as usual and the function is kept as is.
+fn enum_only(
+    args: &ErrorsArgs,
+    mut function: syn::ItemFn,
+    derives: TokenStream,
+    explicit_error_name: Option<syn::Ident>,
+) -> syn::Result<(TokenStream, TokenStream)> {
+    if args.none {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`enum_only` cant be combined with `none`, there is no enum to generate",
+        ));
+    }
+    if args.panic || args.erase || args.boxed_self {
+        let flag = if args.panic {
+            "panic"
+        } else if args.erase {
+            "erase"
+        } else {
+            "boxed_self"
+        };
+        return Err(syn::Error::new(
+            Span::call_site(),
+            format!("`enum_only` cant be combined with `{flag}`, the function is left unchanged"),
+        ));
+    }
+    if let Some(denied) = args.deny.first() {
+        return Err(syn::Error::new_spanned(
+            denied,
+            "`enum_only` cant be combined with `deny`, there is no enum to generate",
+        ));
+    }
+
+    let (error_enum, enum_name) = generate_error_type(
+        args,
+        function.sig.ident.to_string(),
+        function.vis.clone(),
+        derives,
+        explicit_error_name,
+    )?;
+    let zbus_impls =
+        generate::zbus_impls(args, &args.entries, &enum_name, args.allow_extra.as_ref())?;
+    let clap_impls =
+        generate::clap_impls(args, &args.entries, &enum_name, args.allow_extra.as_ref())?;
+    let yew_impls =
+        generate::yew_impls(args, &args.entries, &enum_name, args.allow_extra.as_ref())?;
+    let reexport_check = args.reexport.as_ref().map(|module| {
+        quote_spanned! {module.span()=>
+            const _: fn(#enum_name) -> #module::#enum_name = |value| value;
+        }
+    });
+
+    if !args.no_doc && !doc::has_errors_section(&function.attrs) {
+        let errors_doc = doc::errors_section(args, Some(&parse_quote!(#enum_name)));
+        function
+            .attrs
+            .extend(syn::Attribute::parse_outer.parse2(errors_doc)?);
+    }
+    let enum_items = gate_items(
+        &cfg_attrs(&function.attrs)?,
+        quote!(#error_enum #zbus_impls #clap_impls #yew_impls #reexport_check),
+    )?;
+    Ok((enum_items, function.to_token_stream()))
+}
+
+/// The enum name given with `Result<T, Box<Name>>` for `boxed_self`, a plain `Result<T, Name>`
+/// would hide that the function returns the box.
+fn boxed_error_name(
+    output: &ReturnType,
+    named: Option<syn::Ident>,
+) -> syn::Result<Option<syn::Ident>> {
+    if let Some(name) = named {
+        return Err(syn::Error::new(
+            name.span(),
+            format!("`boxed_self` returns the enum boxed, write `Box<{name}>`"),
+        ));
+    }
+    let mut output = output.clone();
+    let Some(Type::Path(TypePath { qself: None, path })) = error_argument(&mut output) else {
+        return Ok(None);
+    };
+    let Some(last) = path.segments.last() else {
+        return Ok(None);
+    };
+    let PathArguments::AngleBracketed(arguments) = &last.arguments else {
+        return Ok(None);
+    };
+    match arguments.args.iter().collect::<Vec<_>>()[..] {
+        [GenericArgument::Type(Type::Path(TypePath {
+            qself: None,
+            path: name,
+        }))] if last.ident == "Box" => Ok(name.get_ident().cloned()),
+        _ => Ok(None),
+    }
+}
+
+/// `none` replaces the generated enum, so nothing that would end up in the enum can be set.
+fn check_none(args: &ErrorsArgs, output: &ReturnType, named: bool) -> syn::Result<()> {
+    if !args.entries.is_empty() || args.allow_extra.is_some() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`none` cant be combined with listed errors, the function cant fail",
+        ));
+    }
+    if args.boxed_self {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`none` cant be combined with `boxed_self`, there is no enum to box",
+        ));
+    }
+    if named {
+        return Err(syn::Error::new_spanned(
+            output,
+            "`none` uses `error_mancer::NoError` as the error type, use `_` instead of a name",
+        ));
+    }
+    if let Some(module) = &args.reexport {
+        return Err(syn::Error::new(
+            module.span(),
+            "`none` doesnt generate a enum to re-export",
+        ));
+    }
+    Ok(())
+}
+
 fn get_return_generics(return_type: &ReturnType) -> syn::Result<(&Type, Option<syn::Ident>)> {
     match return_type {
         ReturnType::Default => Err(syn::Error::new(
@@ -172,6 +1133,15 @@ fn get_return_generics(return_type: &ReturnType) -> syn::Result<(&Type, Option<s
             "Function must have a return type of Result<Ok, Err>",
         )),
         ReturnType::Type(_, ty) => {
+            if let Some(output) = returned_closure(ty) {
+                return Err(syn::Error::new_spanned(
+                    output,
+                    "`#[errors]` cant generate the error type of a returned closure, put \
+                     `#[errors]` on a named function returning `Result<T, _>` and return or call \
+                     that function instead, naming the generated enum in the closure type",
+                ));
+            }
+
             // Ensure the return type is a Path type
             let type_path = match ty.as_ref() {
                 Type::Path(TypePath { path, .. }) => path,
@@ -189,6 +1159,14 @@ fn get_return_generics(return_type: &ReturnType) -> syn::Result<(&Type, Option<s
             })?;
 
             if last_segment.ident != "Result" {
+                if is_boxed_future(ty) {
+                    return Err(syn::Error::new(
+                        ty.span(),
+                        "Expected return type to be Result<...>, `#[errors]` has to be placed \
+                         before attributes turning a `async fn` into a boxed future, such as \
+                         `#[async_recursion]`",
+                    ));
+                }
                 return Err(syn::Error::new(
                     last_segment.ident.span(),
                     "Expected return type to be Result<...>",
@@ -250,122 +1228,200 @@ fn get_return_generics(return_type: &ReturnType) -> syn::Result<(&Type, Option<s
     }
 }
 
-fn replace_error_value(return_type: &mut ReturnType, error_type: syn::Type) -> bool {
-    let ReturnType::Type(_, return_type) = return_type else {
-        return false;
+/// Replace a `try` block at the end of the body with its contents wrapped in `Ok`. `try` blocks
+/// dont convert the error, so the `?`s have to return from the closure instead.
+fn unwrap_try_tail(body: &mut syn::Block) {
+    let Some(syn::Stmt::Expr(tail, None)) = body.stmts.last_mut() else {
+        return;
     };
-
-    let syn::Type::Path(return_type) = return_type.as_mut() else {
-        return false;
-    };
-
-    let Some(last) = return_type.path.segments.last_mut() else {
-        return false;
+    let syn::Expr::TryBlock(try_block) = tail else {
+        return;
     };
+    let block = &try_block.block;
+    *tail = parse_quote!(::core::result::Result::Ok(#block));
+}
 
-    if last.ident != "Result" {
-        return false;
-    }
+/// Returns true if `tokens` contains `Self`.
+fn mentions_self(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => ident == "Self",
+        proc_macro2::TokenTree::Group(group) => mentions_self(group.stream()),
+        _ => false,
+    })
+}
 
-    let syn::PathArguments::AngleBracketed(arguments) = &mut last.arguments else {
-        return false;
+/// The return type of `impl FnOnce() -> R`, `Box<dyn Fn() -> R>` and similar.
+fn returned_closure(ty: &Type) -> Option<&ReturnType> {
+    let bounds = match ty {
+        Type::ImplTrait(ty) => &ty.bounds,
+        Type::TraitObject(ty) => &ty.bounds,
+        Type::Path(TypePath { path, .. }) => {
+            let segment = path.segments.last()?;
+            let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+                return None;
+            };
+            let Some(GenericArgument::Type(inner)) = arguments.args.first() else {
+                return None;
+            };
+            return returned_closure(inner);
+        }
+        _ => return None,
     };
+    bounds.iter().find_map(|bound| {
+        let syn::TypeParamBound::Trait(bound) = bound else {
+            return None;
+        };
+        let segment = bound.path.segments.last()?;
+        let PathArguments::Parenthesized(arguments) = &segment.arguments else {
+            return None;
+        };
+        ["Fn", "FnMut", "FnOnce"]
+            .iter()
+            .any(|name| segment.ident == name)
+            .then_some(&arguments.output)
+    })
+}
 
-    if arguments.args.len() < 2 {
-        return false;
+/// Returns true for `Pin<Box<dyn Future<...>>>` and similar.
+fn is_boxed_future(ty: &Type) -> bool {
+    match ty {
+        Type::Path(TypePath { path, .. }) => {
+            let Some(segment) = path.segments.last() else {
+                return false;
+            };
+            let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+                return false;
+            };
+            matches!(arguments.args.first(), Some(GenericArgument::Type(inner)) if is_boxed_future(inner))
+        }
+        Type::TraitObject(object) => object.bounds.iter().any(|bound| match bound {
+            syn::TypeParamBound::Trait(bound) => bound
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Future"),
+            _ => false,
+        }),
+        _ => false,
     }
+}
 
-    if let syn::GenericArgument::Type(syn::Type::Infer(_)) = arguments.args[1] {
-        arguments.args[1] = syn::GenericArgument::Type(error_type);
-        return true;
+fn replace_error_value(return_type: &mut ReturnType, error_type: syn::Type) -> bool {
+    match error_argument(return_type) {
+        Some(argument @ syn::Type::Infer(_)) => {
+            *argument = error_type;
+            true
+        }
+        _ => false,
     }
-    false
 }
 
-fn generate_error_type(
-    args: TokenStream,
-    function_name: String,
-    vis: syn::Visibility,
-    derives: TokenStream,
-    enum_name: Option<syn::Ident>,
-) -> syn::Result<(TokenStream, Type)> {
-    let enum_name = if let Some(enum_name) = enum_name {
-        enum_name
-    } else {
-        let enum_name = function_name.to_case(Case::Pascal);
-        format_ident!("{enum_name}Error")
+/// The argument of a `Poll<T>` return type.
+fn poll_argument(return_type: &mut ReturnType) -> Option<&mut Type> {
+    let ReturnType::Type(_, return_type) = return_type else {
+        return None;
     };
+    let Type::Path(TypePath { qself: None, path }) = return_type.as_mut() else {
+        return None;
+    };
+    let last = path.segments.last_mut()?;
+    if last.ident != "Poll" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &mut last.arguments else {
+        return None;
+    };
+    if arguments.args.len() != 1 {
+        return None;
+    }
+    match arguments.args.first_mut()? {
+        GenericArgument::Type(argument) => Some(argument),
+        _ => None,
+    }
+}
 
-    let error_types = Punctuated::<syn::Path, Token![,]>::parse_terminated.parse2(args)?;
-    let error_types_clone = error_types.clone().into_iter().collect::<Vec<_>>();
-    let (fields, from_impls): (Vec<_>, Vec<_>) = error_types
-        .iter()
-        .map(|path| {
-            let name = path
-                .segments
-                .iter()
-                .map(|segment| segment.ident.to_string() + "_")
-                .collect::<String>()
-                .to_case(Case::Pascal);
-            let name = name.trim_end_matches("Error");
-            let name = format_ident!("{name}");
-
-            (
-                (
-                    name.clone(),
-                    quote!(
-                        #name(#path)
-                    ),
-                ),
-                quote!(
-                    impl ::error_mancer::ErrorMancerFrom<#path> for #enum_name {
-                        fn from(value: #path) -> Self {
-                            Self::#name(value)
-                        }
-                    }
-                ),
-            )
-        })
-        .unzip();
-    let (names, fields): (Vec<_>, Vec<_>) = fields.into_iter().unzip();
-
-    let enum_stream = quote! {
-        #[derive(::core::fmt::Debug)]
-        #derives
-        #vis enum #enum_name {
-            #(#fields),*
-        }
-
-        #(#from_impls)*
+/// Replace a `Poll<T>` return type with `T`, returning the `Poll` type so it can be put back
+/// with [`rewrap_poll`].
+fn unwrap_poll(return_type: &mut ReturnType) -> Option<Type> {
+    let inner = poll_argument(return_type)?.clone();
+    let ReturnType::Type(_, return_type) = return_type else {
+        return None;
+    };
+    Some(std::mem::replace(return_type.as_mut(), inner))
+}
 
-        impl<T> ::core::convert::From<T> for #enum_name where Self: ::error_mancer::ErrorMancerFrom<T> {
-            fn from(value: T) -> Self {
-                ::error_mancer::ErrorMancerFrom::from(value)
-            }
-        }
+/// Put the `Poll` removed by [`unwrap_poll`] back around the return type.
+fn rewrap_poll(return_type: &mut ReturnType, poll: Option<&Type>) {
+    let Some(poll) = poll else {
+        return;
+    };
+    let ReturnType::Type(_, inner) = return_type else {
+        return;
+    };
+    let inner = std::mem::replace(inner.as_mut(), poll.clone());
+    if let Some(argument) = poll_argument(return_type) {
+        *argument = inner;
+    }
+}
 
-        impl<T> ::error_mancer::FlattenInto<T> for #enum_name
-            where T: #(::error_mancer::ErrorMancerFrom<#error_types_clone>)+* {
-            fn flatten(self) -> T {
-                match self {
-                    #(Self::#names(err) => T::from(err),)*
-                    _ => unreachable!()
-                }
-            }
+/// Replaces the `_` in `Partial<T, _>`, so non-fatal errors use the same enum as fatal ones.
+fn replace_partial_error(ty: &mut Type, error_type: &Type) -> bool {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return false;
+    };
+    let Some(last) = path.segments.last_mut() else {
+        return false;
+    };
+    if last.ident != "Partial" {
+        return false;
+    }
+    let syn::PathArguments::AngleBracketed(arguments) = &mut last.arguments else {
+        return false;
+    };
+    match arguments.args.get_mut(1) {
+        Some(syn::GenericArgument::Type(argument @ Type::Infer(_))) => {
+            *argument = error_type.clone();
+            true
         }
+        _ => false,
+    }
+}
 
-        impl ::core::fmt::Display for #enum_name {
-            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-                match self {
-                    #(Self::#names(err) => err.fmt(f),)*
-                    _ => unreachable!()
-                }
-            }
-        }
+/// The second generic argument of a `Result` return type.
+fn error_argument(return_type: &mut ReturnType) -> Option<&mut Type> {
+    result_argument(return_type, 1)
+}
 
-        impl ::core::error::Error for #enum_name {}
+/// The generic argument at `index` of a `Result` return type.
+fn result_argument(return_type: &mut ReturnType, index: usize) -> Option<&mut Type> {
+    let ReturnType::Type(_, return_type) = return_type else {
+        return None;
     };
-    let enum_type = parse_quote!(#enum_name);
+    let syn::Type::Path(return_type) = return_type.as_mut() else {
+        return None;
+    };
+    let last = return_type.path.segments.last_mut()?;
+    if last.ident != "Result" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(arguments) = &mut last.arguments else {
+        return None;
+    };
+    match arguments.args.get_mut(index)? {
+        syn::GenericArgument::Type(argument) => Some(argument),
+        _ => None,
+    }
+}
 
-    Ok((enum_stream, enum_type))
+/// The name of `Self::Error` in `Result<T, Self::Error>`.
+fn self_associated_type(ty: &Type) -> Option<&syn::Ident> {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    match path.segments.iter().collect::<Vec<_>>()[..] {
+        [self_type, associated] if self_type.ident == "Self" && associated.arguments.is_none() => {
+            Some(&associated.ident)
+        }
+        _ => None,
+    }
 }
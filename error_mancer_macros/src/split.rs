@@ -0,0 +1,130 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+
+use crate::args::{SplitHalf, SplitVariants};
+use crate::generate::payloads_trait_name;
+
+/// The two enums generated by `split_variants!` and the conversion from the original enum.
+pub(crate) fn split_variants(input: SplitVariants) -> syn::Result<TokenStream> {
+    let SplitVariants {
+        enum_path,
+        first,
+        second,
+    } = input;
+
+    let mut seen = Vec::new();
+    for variant in first.variants.iter().chain(&second.variants) {
+        if seen.contains(&variant) {
+            return Err(syn::Error::new(
+                variant.span(),
+                format!("`{variant}` is listed twice"),
+            ));
+        }
+        seen.push(variant);
+    }
+
+    let Some(last) = enum_path.segments.last() else {
+        return Err(syn::Error::new(enum_path.span(), "Expected a enum"));
+    };
+    let mut payloads = enum_path.clone();
+    if let Some(segment) = payloads.segments.last_mut() {
+        *segment = payloads_trait_name(&last.ident).into();
+    }
+    let payload = |variant: &syn::Ident| quote!(<#enum_path as #payloads>::#variant);
+
+    let first_enum = split_enum(&first, &enum_path, payload);
+    let second_enum = split_enum(&second, &enum_path, payload);
+
+    let first_name = &first.name;
+    let second_name = &second.name;
+    let first_arms = first.variants.iter().map(|variant| {
+        quote!(#enum_path::#variant(err, ..) => ::core::result::Result::Ok(#first_name::#variant(err)))
+    });
+    let second_arms = second.variants.iter().map(|variant| {
+        quote!(#enum_path::#variant(err, ..) => ::core::result::Result::Err(#second_name::#variant(err)))
+    });
+    // Spanned so a variant missing from both lists is reported on the enum.
+    let split_match = quote_spanned! {enum_path.span()=>
+        match value {
+            #(#first_arms,)*
+            #(#second_arms,)*
+        }
+    };
+
+    Ok(quote! {
+        #first_enum
+        #second_enum
+
+        impl ::core::convert::From<#enum_path>
+            for ::core::result::Result<#first_name, #second_name>
+        {
+            fn from(value: #enum_path) -> Self {
+                #split_match
+            }
+        }
+    })
+}
+
+/// One of the enums generated by `split_variants!`, it can be converted back into the original
+/// enum with `From`.
+fn split_enum(
+    half: &SplitHalf,
+    enum_path: &syn::Path,
+    payload: impl Fn(&syn::Ident) -> TokenStream,
+) -> TokenStream {
+    let SplitHalf {
+        attrs,
+        vis,
+        name,
+        variants,
+    } = half;
+    let payloads = variants.iter().map(payload);
+    let variant_names = variants.iter().map(syn::Ident::to_string);
+
+    quote! {
+        #(#attrs)*
+        #vis enum #name {
+            #(#variants(#payloads)),*
+        }
+
+        impl ::core::fmt::Debug for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match *self {
+                    #(Self::#variants(ref err) => f
+                        .debug_tuple(#variant_names)
+                        .field(::error_mancer::__private::debug_payload(err))
+                        .finish(),)*
+                }
+            }
+        }
+
+        impl ::core::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match *self {
+                    #(Self::#variants(ref err) => ::core::fmt::Display::fmt(err, f),)*
+                }
+            }
+        }
+
+        impl ::error_mancer::__private::Error for #name {
+            fn source(&self) -> ::core::option::Option<&(dyn ::error_mancer::__private::Error + 'static)> {
+                #[allow(unused_imports)]
+                use ::error_mancer::__private::{ForwardError as _, ForwardNothing as _};
+                match *self {
+                    #(Self::#variants(ref err) => (&::error_mancer::__private::Transparent(err)).forward_source(),)*
+                }
+            }
+        }
+
+        #[doc(hidden)]
+        #[diagnostic::do_not_recommend]
+        impl ::error_mancer::ErrorMancerFrom<#name> for #enum_path {
+            fn from(value: #name) -> Self {
+                match value {
+                    #(#name::#variants(err) => ::core::convert::From::from(err),)*
+                }
+            }
+        }
+    }
+}
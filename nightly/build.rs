@@ -0,0 +1,19 @@
+//! Sets `cfg(nightly)` when building with a nightly compiler.
+
+use std::env;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(nightly)");
+
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .unwrap_or_default();
+    if version.contains("nightly") || version.contains("-dev") {
+        println!("cargo:rustc-cfg=nightly");
+    }
+}
@@ -0,0 +1 @@
+//! Tests for `error_mancer` with nightly only language features, see the `tests` directory.
@@ -0,0 +1,6 @@
+//! `Result<!, _>` for functions that only return on errors, `!` as a type is nightly only.
+#![cfg_attr(nightly, feature(never_type))]
+
+#[cfg(nightly)]
+#[path = "never/cases.rs"]
+mod cases;
@@ -0,0 +1,33 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[errors(ParseIntError, std::io::Error)]
+fn run(inputs: &[&str]) -> Result<!, _> {
+    let mut total = 0;
+    for input in inputs {
+        total += input.parse::<i32>()?;
+        if total > 10 {
+            Err(std::io::Error::other("too large"))?;
+        }
+    }
+    loop {
+        Err(std::io::Error::other("out of input"))?;
+    }
+}
+
+#[errors(std::fmt::Error)]
+fn fail() -> Result<!, _> {
+    Err(std::fmt::Error)?
+}
+
+#[test]
+fn returns_only_errors() {
+    let Err(err) = run(&["1", "x"]);
+    assert!(matches!(err, RunError::ParseInt(_)));
+    let Err(err) = run(&["5", "6"]);
+    assert_eq!(err.to_string(), "too large");
+    let Err(err) = run(&["1"]);
+    assert_eq!(err.to_string(), "out of input");
+    let Err(FailError::StdFmt(_)) = fail();
+}
@@ -0,0 +1,7 @@
+//! Other toolchains warn about `try` blocks while parsing, so the tests are in a module which
+//! is only loaded on nightly.
+#![cfg_attr(nightly, feature(try_blocks))]
+
+#[cfg(nightly)]
+#[path = "try_blocks/cases.rs"]
+mod cases;
@@ -0,0 +1,54 @@
+use std::num::ParseIntError;
+
+use error_mancer::prelude::*;
+
+#[errors(std::io::Error)]
+fn open() -> Result<i32, _> {
+    try {
+        let _file = std::fs::File::open("/this/file/does/not/exist")?;
+        10
+    }
+}
+
+#[errors(ParseIntError, std::io::Error)]
+fn parse(x: &str) -> Result<i32, _> {
+    try {
+        let value: i32 = x.parse()?;
+        if value < 0 {
+            Err(std::io::Error::other("negative"))?;
+        }
+        value * 2
+    }
+}
+
+#[errors(ParseIntError)]
+fn nested(x: &str) -> Result<i32, _> {
+    let doubled: Result<i32, ParseIntError> = try { x.parse::<i32>()? * 2 };
+    Ok(doubled? + 1)
+}
+
+#[errors(ParseIntError)]
+fn early_return(x: &str) -> Result<i32, _> {
+    try {
+        if x.is_empty() {
+            return Ok(0);
+        }
+        x.parse::<i32>()?
+    }
+}
+
+#[test]
+fn tail_try_block() {
+    assert!(matches!(open(), Err(OpenError::StdIo(_))));
+    assert_eq!(parse("4").unwrap(), 8);
+    assert!(matches!(parse("x"), Err(ParseError::ParseInt(_))));
+    assert!(matches!(parse("-1"), Err(ParseError::StdIo(_))));
+    assert_eq!(early_return("").unwrap(), 0);
+    assert_eq!(early_return("3").unwrap(), 3);
+}
+
+#[test]
+fn inner_try_block() {
+    assert_eq!(nested("2").unwrap(), 5);
+    assert!(matches!(nested("x"), Err(NestedError::ParseInt(_))));
+}